@@ -0,0 +1,163 @@
+use crate::client::LinktorClient;
+use crate::error::{LinktorError, Result};
+use futures_util::stream::{self, StreamExt};
+use std::future::Future;
+use std::time::Duration;
+
+/// Runs one operation per item with bounded concurrency.
+///
+/// Items that were rate limited (429) are collected and run again in a later
+/// pass, up to `retries` extra passes. Network errors and 5xx responses are
+/// not retried unless [`retry_idempotent`](Self::retry_idempotent) is set: a
+/// timeout or a 502 can come after the server already accepted the request,
+/// so re-running a non-idempotent operation such as `send_text` may send it twice.
+///
+/// ```rust,no_run
+/// # use linktor::{LinktorClient, types::*};
+/// # async fn run(client: LinktorClient, ids: Vec<String>) {
+/// let report = client
+///     .bulk()
+///     .concurrency(16)
+///     .run(ids, |client, id| async move { client.conversations().send_text(&id, "Hi").await })
+///     .await;
+/// println!("{} sent, {} failed", report.succeeded.len(), report.failed.len());
+/// # }
+/// ```
+pub struct BulkExecutor {
+    client: LinktorClient,
+    concurrency: usize,
+    retries: u32,
+    retry_delay: Duration,
+    retry_idempotent: bool,
+}
+
+#[derive(Debug)]
+pub struct BulkSuccess<I, T> {
+    /// Position of the item in the input.
+    pub index: usize,
+    pub item: I,
+    pub value: T,
+}
+
+#[derive(Debug)]
+pub struct BulkFailure<I> {
+    /// Position of the item in the input.
+    pub index: usize,
+    pub item: I,
+    pub error: LinktorError,
+    pub attempts: u32,
+}
+
+#[derive(Debug)]
+pub struct BulkReport<I, T> {
+    /// Successful items, ordered by input position.
+    pub succeeded: Vec<BulkSuccess<I, T>>,
+    /// Failed items, ordered by input position.
+    pub failed: Vec<BulkFailure<I>>,
+}
+
+impl<I, T> BulkReport<I, T> {
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    pub fn total(&self) -> usize {
+        self.succeeded.len() + self.failed.len()
+    }
+}
+
+impl BulkExecutor {
+    pub(crate) fn new(client: LinktorClient) -> Self {
+        Self {
+            client,
+            concurrency: 8,
+            retries: 2,
+            retry_delay: Duration::from_secs(1),
+            retry_idempotent: false,
+        }
+    }
+
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Extra passes for items that failed with a retryable error.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Base delay before a retry pass; multiplied by the pass number.
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Also retry items that failed with a network error or a 5xx response.
+    /// Only safe when the operation is idempotent, e.g. a GET or a PUT; for
+    /// sends and other POSTs a retry may duplicate work the server already did.
+    pub fn retry_idempotent(mut self, retry: bool) -> Self {
+        self.retry_idempotent = retry;
+        self
+    }
+
+    pub async fn run<I, T, F, Fut>(self, items: impl IntoIterator<Item = I>, operation: F) -> BulkReport<I, T>
+    where
+        I: Clone,
+        F: Fn(LinktorClient, I) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut pending: Vec<(usize, I)> = items.into_iter().enumerate().collect();
+        let mut succeeded = Vec::new();
+        let mut failed = Vec::new();
+        let mut pass = 0;
+
+        while !pending.is_empty() {
+            pass += 1;
+            let results: Vec<(usize, I, Result<T>)> = stream::iter(pending.drain(..))
+                .map(|(index, item)| {
+                    let fut = operation(self.client.clone(), item.clone());
+                    async move { (index, item, fut.await) }
+                })
+                .buffer_unordered(self.concurrency)
+                .collect()
+                .await;
+
+            let mut retry_after = Duration::ZERO;
+            for (index, item, result) in results {
+                match result {
+                    Ok(value) => succeeded.push(BulkSuccess { index, item, value }),
+                    Err(error) if pass <= self.retries && self.is_retryable(&error) => {
+                        if let Some(secs) = error.retry_after() {
+                            retry_after = retry_after.max(Duration::from_secs(secs));
+                        }
+                        pending.push((index, item));
+                    }
+                    Err(error) => failed.push(BulkFailure {
+                        index,
+                        item,
+                        error,
+                        attempts: pass,
+                    }),
+                }
+            }
+
+            if !pending.is_empty() {
+                tokio::time::sleep(retry_after.max(self.retry_delay * pass)).await;
+            }
+        }
+
+        succeeded.sort_by_key(|s| s.index);
+        failed.sort_by_key(|f| f.index);
+        BulkReport { succeeded, failed }
+    }
+
+    fn is_retryable(&self, error: &LinktorError) -> bool {
+        match error {
+            LinktorError::RateLimit { .. } => true,
+            LinktorError::Network(_) | LinktorError::Server { .. } => self.retry_idempotent,
+            _ => false,
+        }
+    }
+}
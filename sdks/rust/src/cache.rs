@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResponse {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+/// Entries kept when the builder does not set a capacity.
+pub(crate) const DEFAULT_CAPACITY: usize = 256;
+
+#[derive(Debug, Default)]
+struct Entries {
+    /// Each response with the tick it was last used at.
+    map: HashMap<String, (CachedResponse, u64)>,
+    tick: u64,
+}
+
+/// Path-keyed store of GET response bodies and their validators. Once full,
+/// storing a new path evicts the least recently used one.
+#[derive(Debug, Clone)]
+pub(crate) struct ResponseCache {
+    entries: Arc<Mutex<Entries>>,
+    capacity: usize,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl ResponseCache {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Arc::default(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// An empty cache with the same capacity, for scoped clients.
+    pub fn empty_like(&self) -> Self {
+        Self::with_capacity(self.capacity)
+    }
+
+    pub fn get(&self, path: &str) -> Option<CachedResponse> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.tick += 1;
+        let tick = entries.tick;
+        let (response, used) = entries.map.get_mut(path)?;
+        *used = tick;
+        Some(response.clone())
+    }
+
+    pub fn store(&self, path: &str, etag: Option<String>, last_modified: Option<String>, body: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        if etag.is_none() && last_modified.is_none() {
+            entries.map.remove(path);
+            return;
+        }
+        if entries.map.len() >= self.capacity && !entries.map.contains_key(path) {
+            let oldest = entries.map.iter().min_by_key(|(_, (_, used))| *used).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.map.remove(&oldest);
+            }
+        }
+        entries.tick += 1;
+        let tick = entries.tick;
+        entries.map.insert(
+            path.to_string(),
+            (
+                CachedResponse {
+                    etag,
+                    last_modified,
+                    body: body.to_string(),
+                },
+                tick,
+            ),
+        );
+    }
+
+    /// Drop every entry belonging to the same top-level resource as `path`,
+    /// e.g. a write to `/channels/123` evicts `/channels` and `/channels/123`.
+    pub fn invalidate(&self, path: &str) {
+        let prefix = resource_prefix(path);
+        self.entries
+            .lock()
+            .unwrap()
+            .map
+            .retain(|key, _| resource_prefix(key) != prefix);
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().map.clear();
+    }
+}
+
+fn resource_prefix(path: &str) -> &str {
+    let path = path.split('?').next().unwrap_or(path);
+    let trimmed = path.trim_start_matches('/');
+    match trimmed.find('/') {
+        Some(idx) => &trimmed[..idx],
+        None => trimmed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalidate_by_resource() {
+        let cache = ResponseCache::default();
+        cache.store("/channels", Some("\"a\"".into()), None, "[]");
+        cache.store("/channels/1", Some("\"b\"".into()), None, "{}");
+        cache.store("/bots?limit=5", Some("\"c\"".into()), None, "[]");
+
+        cache.invalidate("/channels/1/connect");
+
+        assert!(cache.get("/channels").is_none());
+        assert!(cache.get("/channels/1").is_none());
+        assert!(cache.get("/bots?limit=5").is_some());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = ResponseCache::with_capacity(2);
+        cache.store("/channels/1", Some("\"a\"".into()), None, "{}");
+        cache.store("/channels/2", Some("\"b\"".into()), None, "{}");
+        assert!(cache.get("/channels/1").is_some());
+
+        cache.store("/channels/3", Some("\"c\"".into()), None, "{}");
+
+        assert!(cache.get("/channels/1").is_some());
+        assert!(cache.get("/channels/2").is_none());
+        assert!(cache.get("/channels/3").is_some());
+    }
+}
@@ -0,0 +1,215 @@
+//! Client-side capability limits for clients handed to plugins or other
+//! less-trusted code. Checks run before a request is sent and complement,
+//! not replace, the scopes enforced by the API.
+
+use crate::error::{LinktorError, Result};
+use crate::types::BatchRequest;
+
+/// An API area, matched on the first path segment of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiResource {
+    Auth,
+    Conversations,
+    Contacts,
+    Users,
+    Channels,
+    Bots,
+    Ai,
+    KnowledgeBases,
+    Flows,
+    Vre,
+    Retention,
+    Sms,
+    Webhooks,
+    Media,
+    Broadcasts,
+    Campaigns,
+}
+
+impl ApiResource {
+    pub const ALL: [ApiResource; 16] = [
+        ApiResource::Auth,
+        ApiResource::Conversations,
+        ApiResource::Contacts,
+        ApiResource::Users,
+        ApiResource::Channels,
+        ApiResource::Bots,
+        ApiResource::Ai,
+        ApiResource::KnowledgeBases,
+        ApiResource::Flows,
+        ApiResource::Vre,
+        ApiResource::Retention,
+        ApiResource::Sms,
+        ApiResource::Webhooks,
+        ApiResource::Media,
+        ApiResource::Broadcasts,
+        ApiResource::Campaigns,
+    ];
+
+    /// The first path segment served by this resource.
+    pub fn segment(&self) -> &'static str {
+        match self {
+            ApiResource::Auth => "auth",
+            ApiResource::Conversations => "conversations",
+            ApiResource::Contacts => "contacts",
+            ApiResource::Users => "users",
+            ApiResource::Channels => "channels",
+            ApiResource::Bots => "bots",
+            ApiResource::Ai => "ai",
+            ApiResource::KnowledgeBases => "knowledge-bases",
+            ApiResource::Flows => "flows",
+            ApiResource::Vre => "vre",
+            ApiResource::Retention => "retention",
+            ApiResource::Sms => "sms",
+            ApiResource::Webhooks => "webhooks",
+            ApiResource::Media => "media",
+            ApiResource::Broadcasts => "broadcasts",
+            ApiResource::Campaigns => "campaigns",
+        }
+    }
+
+    /// The resource a request path belongs to, ignoring any query string.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let path = path.split('?').next().unwrap_or_default();
+        let segment = path.trim_start_matches('/').split('/').next().unwrap_or_default();
+        Self::ALL.into_iter().find(|r| r.segment() == segment)
+    }
+}
+
+/// What a restricted client may do. The default allows everything.
+///
+/// ```rust
+/// use linktor::capabilities::{ApiResource, Capabilities};
+///
+/// let caps = Capabilities {
+///     read_only: true,
+///     allowed_resources: vec![ApiResource::Conversations, ApiResource::Contacts],
+/// };
+/// assert!(caps.check("GET", "/contacts/c-1").is_ok());
+/// assert!(caps.check("POST", "/contacts").is_err());
+/// assert!(caps.check("GET", "/bots").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Reject every method other than GET and HEAD.
+    pub read_only: bool,
+    /// Resources the client may call; requests to any other path are rejected.
+    pub allowed_resources: Vec<ApiResource>,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            read_only: false,
+            allowed_resources: ApiResource::ALL.to_vec(),
+        }
+    }
+}
+
+impl Capabilities {
+    /// Read-only access to every resource.
+    pub fn read_only() -> Self {
+        Self {
+            read_only: true,
+            ..Self::default()
+        }
+    }
+
+    /// Full access to the given resources only.
+    pub fn resources(resources: impl IntoIterator<Item = ApiResource>) -> Self {
+        Self {
+            read_only: false,
+            allowed_resources: resources.into_iter().collect(),
+        }
+    }
+
+    pub fn allows(&self, resource: ApiResource) -> bool {
+        self.allowed_resources.contains(&resource)
+    }
+
+    /// Whether `method` on `path` is permitted, as an `Authorization` error if not.
+    /// Paths with `.` or `..` segments, or escaped dots and slashes, are always
+    /// denied, since the URL normalization applied when sending could move them
+    /// into another resource.
+    pub fn check(&self, method: &str, path: &str) -> Result<()> {
+        let bare = path.split(['?', '#']).next().unwrap_or_default();
+        let lower = bare.to_ascii_lowercase();
+        if lower.contains("%2e") || lower.contains("%2f") || lower.contains('\\') {
+            return Err(denied(method, path, "the path contains escaped separators"));
+        }
+        if bare.split('/').any(|segment| segment == "." || segment == "..") {
+            return Err(denied(method, path, "the path contains relative segments"));
+        }
+        if self.read_only && !matches!(method.to_ascii_uppercase().as_str(), "GET" | "HEAD") {
+            return Err(denied(method, path, "the client is read-only"));
+        }
+        match ApiResource::from_path(path) {
+            Some(resource) if self.allows(resource) => Ok(()),
+            Some(resource) => Err(denied(
+                method,
+                path,
+                &format!("the {} resource is not allowed", resource.segment()),
+            )),
+            None => Err(denied(method, path, "the path is outside the allowed resources")),
+        }
+    }
+
+    /// Check a batch request operation by operation; the batch endpoint itself
+    /// is allowed whenever every operation in it is.
+    pub(crate) fn check_batch(&self, request: &BatchRequest) -> Result<()> {
+        request
+            .operations
+            .iter()
+            .try_for_each(|op| self.check(&op.method, &op.path))
+    }
+
+    /// The capabilities allowed by both `self` and `other`.
+    pub(crate) fn intersect(&self, other: &Capabilities) -> Capabilities {
+        Capabilities {
+            read_only: self.read_only || other.read_only,
+            allowed_resources: self
+                .allowed_resources
+                .iter()
+                .copied()
+                .filter(|r| other.allows(*r))
+                .collect(),
+        }
+    }
+}
+
+fn denied(method: &str, path: &str, reason: &str) -> LinktorError {
+    LinktorError::Authorization {
+        message: format!("Restricted client may not {} {}: {}", method, path, reason),
+        code: None,
+        details: None,
+        request_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersection_never_widens() {
+        let parent = Capabilities::resources([ApiResource::Contacts, ApiResource::Bots]);
+        let child = parent.intersect(&Capabilities {
+            read_only: true,
+            allowed_resources: vec![ApiResource::Contacts, ApiResource::Flows],
+        });
+        assert_eq!(child.allowed_resources, vec![ApiResource::Contacts]);
+        assert!(child.read_only);
+        assert!(child.check("GET", "/contacts?limit=5").is_ok());
+        assert!(child.check("GET", "/flows").is_err());
+        assert!(child.check("GET", "/health").is_err());
+    }
+
+    #[test]
+    fn test_relative_and_escaped_segments_are_denied() {
+        let caps = Capabilities::resources([ApiResource::Contacts]);
+        assert!(caps.check("GET", "/contacts/c-1.json").is_ok());
+        for path in ["/contacts/../users", "/contacts/./c-1", "/contacts/%2E%2E/users", "/contacts/..%2Fusers", "/contacts/..\\users"] {
+            assert!(caps.check("GET", path).is_err(), "{} was allowed", path);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+/// Split `text` into pieces of at most `max_chars` characters.
+///
+/// Pieces end at sentence boundaries where possible, then at whitespace, and
+/// only split inside a word when a single word is longer than `max_chars`.
+/// Leading and trailing whitespace of each piece is trimmed.
+pub fn split_text(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut rest = text.trim();
+
+    while rest.chars().count() > max_chars {
+        // Byte offset just past the last character that fits.
+        let limit = rest.char_indices().nth(max_chars).map(|(i, _)| i).unwrap_or(rest.len());
+        let window = &rest[..limit];
+
+        let cut = last_sentence_end(window)
+            .or_else(|| window.rfind(char::is_whitespace).filter(|&i| i > 0))
+            .unwrap_or(limit);
+
+        chunks.push(rest[..cut].trim_end().to_string());
+        rest = rest[cut..].trim_start();
+    }
+
+    if !rest.is_empty() {
+        chunks.push(rest.to_string());
+    }
+    chunks
+}
+
+/// Byte offset just past the last sentence terminator followed by whitespace.
+fn last_sentence_end(window: &str) -> Option<usize> {
+    let mut chars = window.char_indices().peekable();
+    let mut end = None;
+    while let Some((i, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?' | '\n' | '。') {
+            let next_is_space = chars.peek().is_none_or(|(_, n)| n.is_whitespace());
+            if next_is_space {
+                end = Some(i + c.len_utf8());
+            }
+        }
+    }
+    end.filter(|&i| i > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_at_sentences() {
+        let text = "First sentence here. Second one is longer! Third?";
+        assert_eq!(
+            split_text(text, 25),
+            vec!["First sentence here.", "Second one is longer!", "Third?"]
+        );
+    }
+
+    #[test]
+    fn test_split_falls_back_to_words_and_chars() {
+        assert_eq!(split_text("alpha beta gamma", 11), vec!["alpha beta", "gamma"]);
+        assert_eq!(split_text("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        assert_eq!(split_text("çãéíõü", 4), vec!["çãéí", "õü"]);
+        assert_eq!(split_text("short", 100), vec!["short"]);
+        assert!(split_text("   ", 10).is_empty());
+    }
+}
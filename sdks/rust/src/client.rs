@@ -1,19 +1,67 @@
-use crate::error::{LinktorError, Result};
+use crate::bulk::BulkExecutor;
+use crate::cache::{self, ResponseCache};
+use crate::capabilities::Capabilities;
+use crate::chunking::split_text;
+use crate::error::{parse_retry_after, ErrorCode, LinktorError, Result, DEFAULT_RETRY_AFTER_SECS};
+use crate::formatting::Formatter;
+use crate::hooks::{AuthRefreshEvent, ClientHooks, RateLimitedEvent, RetryEvent, RetryReason};
+use crate::jwt::Claims;
+use crate::logging::DebugLogger;
+use crate::metrics;
+use crate::oauth::{self, ClientCredentials, OAuthParams, PendingAuthorization};
+use crate::query::{api_path, with_query};
+use crate::region::{Region, RegionPool};
+use crate::response::Response;
+use crate::signing::RequestSigner;
+use crate::sse;
+use crate::token_refresh::{SessionSender, TokenRefreshHandle, TokenRefreshOptions, TokenSession};
+use crate::token_store::{StoredTokens, TokenStore};
+#[cfg(feature = "trace-context")]
+use crate::trace_context::{self, TraceContext, TraceContextProvider};
 use crate::types::*;
-use reqwest::{Client, Response, StatusCode};
+use futures_util::stream::{BoxStream, StreamExt, TryStreamExt};
+use reqwest::{Client, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
-use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+const SDK_USER_AGENT: &str = concat!("linktor-rust/", env!("CARGO_PKG_VERSION"));
+/// Request timeout when the builder does not set one.
+pub(crate) const DEFAULT_TIMEOUT_SECS: u64 = 30;
+/// Upper bound on a single event-stream connection before it is resumed.
+const EVENT_STREAM_TIMEOUT: Duration = Duration::from_secs(3600);
+/// Upper bound on a media download, which the client-wide timeout would cut short.
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(600);
+/// First and largest gap between polls in the `wait_*` helpers.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub struct LinktorClient {
     http: Client,
-    base_url: String,
+    regions: RegionPool,
+    last_region: Arc<std::sync::Mutex<Option<Region>>>,
     api_key: Option<String>,
+    signer: Option<RequestSigner>,
     access_token: Arc<RwLock<Option<String>>>,
     max_retries: u32,
+    deadline: Option<Duration>,
+    logger: Option<DebugLogger>,
+    cache: Option<ResponseCache>,
+    rate_limit: Arc<std::sync::Mutex<Option<RateLimitStatus>>>,
+    hooks: ClientHooks,
+    #[cfg(feature = "trace-context")]
+    trace_context: Option<TraceContextProvider>,
+    capabilities: Option<Arc<Capabilities>>,
+    session: Arc<SessionSender>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    client_credentials: Option<Arc<ClientCredentials>>,
+    tenant_id: Option<TenantId>,
+    act_as: Option<UserId>,
+    formatter: Arc<tokio::sync::OnceCell<Formatter>>,
 }
 
 impl LinktorClient {
@@ -21,6 +69,12 @@ impl LinktorClient {
         LinktorClientBuilder::default()
     }
 
+    /// Build a client from `LINKTOR_*` environment variables.
+    /// See [`LinktorClientBuilder::from_env`].
+    pub fn from_env() -> Result<Self> {
+        LinktorClientBuilder::from_env()?.build()
+    }
+
     pub fn auth(&self) -> AuthResource {
         AuthResource { client: self.clone() }
     }
@@ -29,6 +83,10 @@ impl LinktorClient {
         ConversationsResource { client: self.clone() }
     }
 
+    pub fn users(&self) -> UsersResource {
+        UsersResource { client: self.clone() }
+    }
+
     pub fn contacts(&self) -> ContactsResource {
         ContactsResource { client: self.clone() }
     }
@@ -57,91 +115,591 @@ impl LinktorClient {
         VREResource { client: self.clone() }
     }
 
+    pub fn retention(&self) -> RetentionResource {
+        RetentionResource { client: self.clone() }
+    }
+
+    pub fn sms(&self) -> SmsResource {
+        SmsResource { client: self.clone() }
+    }
+
+    pub fn webhooks(&self) -> WebhooksResource {
+        WebhooksResource { client: self.clone() }
+    }
+
+    pub fn media(&self) -> MediaResource {
+        MediaResource { client: self.clone() }
+    }
+
+    pub fn broadcasts(&self) -> BroadcastsResource {
+        BroadcastsResource { client: self.clone() }
+    }
+
+    pub fn campaigns(&self) -> CampaignsResource {
+        CampaignsResource { client: self.clone() }
+    }
+
+    /// Run many operations with bounded concurrency and a per-item report.
+    pub fn bulk(&self) -> BulkExecutor {
+        BulkExecutor::new(self.clone())
+    }
+
+    /// Start a batch that sends several operations in a single `/batch` call.
+    pub fn batch(&self) -> BatchBuilder {
+        static NEXT_BATCH: AtomicU64 = AtomicU64::new(0);
+        BatchBuilder {
+            client: self.clone(),
+            batch_id: NEXT_BATCH.fetch_add(1, Ordering::Relaxed),
+            operations: Vec::new(),
+        }
+    }
+
     pub async fn set_access_token(&self, token: Option<String>) {
         let mut guard = self.access_token.write().await;
         *guard = token;
     }
 
+    async fn store_refreshed_token(&self, token: String, expires_in: i64) {
+        self.set_access_token(Some(token)).await;
+        self.hooks.auth_refresh(AuthRefreshEvent { expires_in });
+    }
+
+    /// Record a new session for the refresh task and the token store.
+    async fn start_session(&self, access_token: &str, refresh_token: &str, expires_in: i64) -> Result<()> {
+        self.session
+            .send_replace(Some(TokenSession::new(refresh_token.to_string(), expires_in)));
+        let tokens = StoredTokens::new(access_token, refresh_token, expires_in);
+        self.with_token_store(move |store| store.save(&tokens)).await
+    }
+
+    /// Run `f` against the token store off the async runtime; stores may do
+    /// file I/O and key derivation.
+    async fn with_token_store(&self, f: impl FnOnce(&dyn TokenStore) -> Result<()> + Send + 'static) -> Result<()> {
+        let Some(store) = self.token_store.clone() else {
+            return Ok(());
+        };
+        tokio::task::spawn_blocking(move || f(store.as_ref()))
+            .await
+            .map_err(|e| LinktorError::Unknown {
+                message: format!("token store task failed: {}", e),
+                status_code: None,
+                code: None,
+                details: None,
+            })?
+    }
+
+    /// The most recent rate-limit state reported by the API, if any response carried it.
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        self.rate_limit.lock().unwrap().clone()
+    }
+
+    /// The region that served the most recent successful request.
+    pub fn region(&self) -> Option<Region> {
+        self.last_region.lock().unwrap().clone()
+    }
+
+    /// Probe `GET /health` on every configured region and update their health.
+    pub async fn check_regions(&self) -> Vec<(Region, bool)> {
+        let mut results = Vec::with_capacity(self.regions.len());
+        for index in 0..self.regions.len() {
+            let region = self.regions.get(index);
+            let healthy = match self.http.get(format!("{}/health", region.base_url)).send().await {
+                Ok(response) => response.status().is_success(),
+                Err(_) => false,
+            };
+            if healthy {
+                self.regions.mark_healthy(index);
+            } else {
+                self.regions.mark_unhealthy(index);
+            }
+            results.push((region.clone(), healthy));
+        }
+        results
+    }
+
+    /// A client that shares this client's connection and credentials but
+    /// rejects, without sending, any request outside `capabilities` with an
+    /// `Authorization` error. Restricting an already restricted client can only
+    /// narrow its capabilities.
+    pub fn restricted(&self, capabilities: Capabilities) -> LinktorClient {
+        let capabilities = match self.capabilities {
+            Some(ref current) => current.intersect(&capabilities),
+            None => capabilities,
+        };
+        LinktorClient {
+            capabilities: Some(Arc::new(capabilities)),
+            ..self.clone()
+        }
+    }
+
+    /// The limits of a client created with [`restricted`](Self::restricted).
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_deref()
+    }
+
+    /// A client that shares this client's connection and credentials but sends
+    /// every request on behalf of `tenant_id` via the `X-Tenant-ID` header, for
+    /// users who belong to several tenants (see
+    /// [`AuthResource::list_tenants`]). Scoped clients get their own response
+    /// cache so tenants never see each other's cached bodies.
+    pub fn with_tenant(&self, tenant_id: impl Into<TenantId>) -> LinktorClient {
+        LinktorClient {
+            tenant_id: Some(tenant_id.into()),
+            cache: self.cache.as_ref().map(ResponseCache::empty_like),
+            formatter: Arc::new(tokio::sync::OnceCell::new()),
+            ..self.clone()
+        }
+    }
+
+    /// The tenant set with [`with_tenant`](Self::with_tenant).
+    pub fn tenant_id(&self) -> Option<&TenantId> {
+        self.tenant_id.as_ref()
+    }
+
+    /// A client that shares this client's connection and credentials but
+    /// performs every request on behalf of the agent `user_id` via the
+    /// `X-Act-As-User` header. Only admins may impersonate; the audit log
+    /// records both the admin and the agent. Like tenant-scoped clients, it
+    /// gets its own response cache.
+    pub fn act_as(&self, user_id: impl Into<UserId>) -> LinktorClient {
+        LinktorClient {
+            act_as: Some(user_id.into()),
+            cache: self.cache.as_ref().map(ResponseCache::empty_like),
+            ..self.clone()
+        }
+    }
+
+    /// The user set with [`act_as`](Self::act_as).
+    pub fn acting_as(&self) -> Option<&UserId> {
+        self.act_as.as_ref()
+    }
+
+    /// Money and date formatting for the tenant's locale and currency, built
+    /// from its settings on first use and cached for the life of the client.
+    pub async fn formatter(&self) -> Result<Formatter> {
+        self.formatter
+            .get_or_try_init(|| async {
+                let tenant = self.auth().get_current_tenant().await?;
+                Ok(tenant.settings.as_ref().map(Formatter::from_settings).unwrap_or_default())
+            })
+            .await
+            .cloned()
+    }
+
+    /// Drop all responses held by the conditional GET cache.
+    pub fn clear_cache(&self) {
+        if let Some(ref cache) = self.cache {
+            cache.clear();
+        }
+    }
+
     pub(crate) async fn request<T: DeserializeOwned>(
         &self,
         method: reqwest::Method,
         path: &str,
         body: Option<impl Serialize>,
     ) -> Result<T> {
-        let url = format!("{}{}", self.base_url, path);
-        let mut attempts = 0;
+        let raw = self.send(method, path, RequestBody::json(body)?).await?;
+        parse_body(&raw.body)
+    }
 
-        loop {
-            attempts += 1;
+    pub(crate) async fn request_with_response<T: DeserializeOwned>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<impl Serialize>,
+    ) -> Result<Response<T>> {
+        let raw = self.send(method, path, RequestBody::json(body)?).await?;
+        let data = parse_body(&raw.body)?;
+        Ok(Response::new(data, raw.status, raw.headers, raw.region))
+    }
 
-            let mut request = self.http.request(method.clone(), &url);
+    fn authorize(&self, method: &reqwest::Method, path: &str, body: Option<&RequestBody>) -> Result<()> {
+        let Some(ref capabilities) = self.capabilities else {
+            return Ok(());
+        };
+        if path.split('?').next() == Some("/batch") {
+            let request: BatchRequest = match body {
+                Some(RequestBody::Json(json)) => serde_json::from_value(json.clone())?,
+                _ => BatchRequest { operations: Vec::new() },
+            };
+            return capabilities.check_batch(&request);
+        }
+        capabilities.check(method.as_str(), path)
+    }
 
-            // Add authentication
+    /// Add authentication and tenant scoping; signed requests get their
+    /// authentication headers in `finalize`.
+    async fn authenticate(&self, mut request: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder> {
+        if let Some(ref tenant_id) = self.tenant_id {
+            request = request.header("X-Tenant-ID", tenant_id.as_str());
+        }
+        if let Some(ref user_id) = self.act_as {
+            request = request.header("X-Act-As-User", user_id.as_str());
+        }
+        if self.signer.is_none() {
             if let Some(ref api_key) = self.api_key {
                 request = request.header("X-API-Key", api_key);
             } else {
+                if let Some(ref credentials) = self.client_credentials {
+                    self.renew_client_credentials(credentials).await?;
+                }
                 let token_guard = self.access_token.read().await;
                 if let Some(ref token) = *token_guard {
                     request = request.header("Authorization", format!("Bearer {}", token));
                 }
             }
+        }
+        Ok(request)
+    }
+
+    /// Fetch a service-account token when there is none yet or the current
+    /// one is about to expire. Like event streams, this goes straight to the
+    /// preferred region without retries.
+    async fn renew_client_credentials(&self, credentials: &ClientCredentials) -> Result<()> {
+        let mut renew_at = credentials.lock().await;
+        if renew_at.is_some_and(|at| Instant::now() < at) {
+            return Ok(());
+        }
+
+        let region = self.regions.get(self.regions.candidates()[0]);
+        let url = format!("{}{}", region.base_url, oauth::CLIENT_CREDENTIALS_PATH);
+        let mut request = self.http.post(&url).json(credentials.grant()).build()?;
+        self.finalize(&mut request);
+
+        let response = self.http.execute(request).await?;
+        let status = response.status();
+        metrics::request_completed(&reqwest::Method::POST, status);
+        let request_id = response
+            .headers()
+            .get("X-Request-ID")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let text = response.text().await.unwrap_or_default();
+        if let Some(ref logger) = self.logger {
+            logger.log_response(status, &url, request_id.as_deref(), Some(&text));
+        }
+        if !status.is_success() {
+            return Err(LinktorError::from_response(status, &text, request_id));
+        }
+
+        let token: ClientCredentialsToken = parse_body(&text)?;
+        *renew_at = Some(ClientCredentials::renew_at(token.expires_in));
+        self.store_refreshed_token(token.access_token, token.expires_in).await;
+        Ok(())
+    }
+
+    /// Headers that depend on the built request, then debug logging.
+    fn finalize(&self, request: &mut reqwest::Request) {
+        #[cfg(feature = "trace-context")]
+        trace_context::apply(self.trace_context.as_ref(), request);
+        if let Some(ref signer) = self.signer {
+            signer.apply(request);
+        }
+        if let Some(ref logger) = self.logger {
+            logger.log_request(request);
+        }
+    }
+
+    /// Open a `text/event-stream` response on the preferred region. Unlike
+    /// `send`, there is no retry or failover here; event streams reconnect
+    /// themselves with `Last-Event-ID`.
+    pub(crate) async fn open_event_stream(&self, path: &str, last_event_id: Option<&str>) -> Result<reqwest::Response> {
+        self.authorize(&reqwest::Method::GET, path, None)?;
+        let region = self.regions.get(self.regions.candidates()[0]);
+        let url = format!("{}{}", region.base_url, path);
+        let mut request = self
+            .authenticate(self.http.get(&url))
+            .await?
+            .header(reqwest::header::ACCEPT, "text/event-stream");
+        if let Some(id) = last_event_id {
+            request = request.header("Last-Event-ID", id);
+        }
+        let mut request = request.build()?;
+        // The client-wide timeout covers the whole body, which would cut long streams short.
+        *request.timeout_mut() = Some(EVENT_STREAM_TIMEOUT);
+        self.finalize(&mut request);
+
+        self.execute_streaming(request, &url).await
+    }
+
+    /// Start downloading a file. API paths and URLs on one of the client's
+    /// regions get the client's credentials, and the capability check, whichever
+    /// form they take; other URLs, such as a channel provider's CDN, are fetched
+    /// without them. Like event streams, there is no retry or failover.
+    pub(crate) async fn open_download(&self, target: &str) -> Result<reqwest::Response> {
+        let (url, authenticated) = if target.starts_with('/') {
+            self.authorize(&reqwest::Method::GET, target, None)?;
+            let region = self.regions.get(self.regions.candidates()[0]);
+            (format!("{}{}", region.base_url, target), true)
+        } else {
+            let api_path = self.api_path_of(target);
+            if let Some(path) = &api_path {
+                self.authorize(&reqwest::Method::GET, path, None)?;
+            }
+            (target.to_string(), api_path.is_some())
+        };
+        let mut request = self.http.get(&url);
+        if authenticated {
+            request = self.authenticate(request).await?;
+        }
+        let mut request = request.build()?;
+        *request.timeout_mut() = Some(DOWNLOAD_TIMEOUT);
+        if authenticated {
+            self.finalize(&mut request);
+        }
+        self.execute_streaming(request, &url).await
+    }
+
+    /// The API path of `url` when it has the same origin as one of the client's
+    /// regions, with that region's base path removed.
+    fn api_path_of(&self, url: &str) -> Option<String> {
+        let url = url::Url::parse(url).ok()?;
+        (0..self.regions.len()).find_map(|index| {
+            let base = url::Url::parse(&self.regions.get(index).base_url).ok()?;
+            if base.origin() != url.origin() {
+                return None;
+            }
+            let path = url.path().strip_prefix(base.path().trim_end_matches('/')).unwrap_or(url.path());
+            Some(if path.starts_with('/') { path.to_string() } else { format!("/{}", path) })
+        })
+    }
+
+    /// Send a request whose body is read by the caller, turning error
+    /// statuses into errors.
+    async fn execute_streaming(&self, request: reqwest::Request, url: &str) -> Result<reqwest::Response> {
+        let response = self.http.execute(request).await?;
+        let status = response.status();
+        metrics::request_completed(&reqwest::Method::GET, status);
+        if status.is_success() {
+            return Ok(response);
+        }
+        let request_id = response
+            .headers()
+            .get("X-Request-ID")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let text = response.text().await.unwrap_or_default();
+        if let Some(ref logger) = self.logger {
+            logger.log_response(status, url, request_id.as_deref(), Some(&text));
+        }
+        Err(LinktorError::from_response(status, &text, request_id))
+    }
+
+    pub(crate) fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Execute a request with retries and failover, returning the successful raw response.
+    async fn send(&self, method: reqwest::Method, path: &str, body: Option<RequestBody>) -> Result<RawResponse> {
+        self.authorize(&method, path, body.as_ref())?;
+        let _in_flight = metrics::request_started();
+        let idempotent = matches!(
+            method,
+            reqwest::Method::GET | reqwest::Method::HEAD | reqwest::Method::PUT | reqwest::Method::DELETE
+        );
+        let deadline = self.deadline.map(|d| Instant::now() + d);
+        // Retry only when the wait still leaves time before the deadline.
+        let can_wait = |delay: Duration| deadline.is_none_or(|at| Instant::now() + delay < at);
+        let candidates = self.regions.candidates();
+        let mut candidate = 0;
+        let mut attempts = 0;
+        // A service token may be revoked before it expires; renew it once on 401.
+        let mut renewed_credentials = false;
+        let retry_event = |attempt, reason, status: Option<StatusCode>, delay, region_index: usize| RetryEvent {
+            method: method.to_string(),
+            path: path.to_string(),
+            attempt,
+            reason,
+            status: status.map(|s| s.as_u16()),
+            delay,
+            region: self.regions.get(region_index).name.clone(),
+        };
+        let cache = self.cache.as_ref();
+        let cached = match cache {
+            Some(cache) if method == reqwest::Method::GET => cache.get(path),
+            _ => None,
+        };
+
+        loop {
+            attempts += 1;
+
+            let region_index = candidates[candidate];
+            let url = format!("{}{}", self.regions.get(region_index).base_url, path);
+            let has_fallback = candidate + 1 < candidates.len();
+            let mut request = self.authenticate(self.http.request(method.clone(), &url)).await?;
+
+            // Revalidate cached responses
+            if let Some(ref entry) = cached {
+                if let Some(ref etag) = entry.etag {
+                    request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+                }
+                if let Some(ref last_modified) = entry.last_modified {
+                    request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+                }
+            }
 
             // Add body
-            if let Some(ref body) = body {
-                request = request.json(body);
+            match body {
+                Some(RequestBody::Json(ref json)) => request = request.json(json),
+                Some(RequestBody::Bytes { ref content_type, ref data }) => {
+                    request = request.header(reqwest::header::CONTENT_TYPE, content_type.as_str()).body(data.clone());
+                }
+                None => {}
             }
 
-            let response = request.send().await?;
+            let mut request = request.build()?;
+            if let Some(at) = deadline {
+                let remaining = at.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(LinktorError::DeadlineExceeded {
+                        deadline: self.deadline.unwrap_or_default(),
+                    });
+                }
+                // Cap the attempt so it cannot outlive the deadline.
+                *request.timeout_mut() = Some(remaining);
+            }
+            self.finalize(&mut request);
+
+            let response = match self.http.execute(request).await {
+                Ok(response) => response,
+                Err(e) => {
+                    self.regions.mark_unhealthy(region_index);
+                    // A request that never connected was not delivered, so any method may move on.
+                    if has_fallback && (idempotent || e.is_connect()) {
+                        // Failing over to another region does not consume a retry.
+                        metrics::retry("failover");
+                        self.hooks.retry(|| retry_event(attempts, RetryReason::Failover, None, Duration::ZERO, region_index));
+                        attempts -= 1;
+                        candidate += 1;
+                        continue;
+                    }
+                    if e.is_timeout() && deadline.is_some_and(|at| Instant::now() >= at) {
+                        return Err(LinktorError::DeadlineExceeded {
+                            deadline: self.deadline.unwrap_or_default(),
+                        });
+                    }
+                    return Err(e.into());
+                }
+            };
             let status = response.status();
+            metrics::request_completed(&method, status);
             let request_id = response
                 .headers()
                 .get("X-Request-ID")
                 .and_then(|v| v.to_str().ok())
                 .map(String::from);
+            let headers = response.headers().clone();
+            let region = self.regions.get(region_index).clone();
+            if let Some(rate_limit) = parse_rate_limit(response.headers()) {
+                *self.rate_limit.lock().unwrap() = Some(rate_limit);
+            }
+
+            if status.is_server_error() {
+                self.regions.mark_unhealthy(region_index);
+                if has_fallback && (idempotent || status == StatusCode::SERVICE_UNAVAILABLE) {
+                    if let Some(ref logger) = self.logger {
+                        logger.log_response(status, &url, request_id.as_deref(), None);
+                    }
+                    metrics::retry("failover");
+                    self.hooks.retry(|| retry_event(attempts, RetryReason::Failover, Some(status), Duration::ZERO, region_index));
+                    attempts -= 1;
+                    candidate += 1;
+                    continue;
+                }
+            } else {
+                self.regions.mark_healthy(region_index);
+                *self.last_region.lock().unwrap() = Some(region.clone());
+            }
+
+            if status == StatusCode::NOT_MODIFIED {
+                if let Some(entry) = cached {
+                    if let Some(ref logger) = self.logger {
+                        logger.log_response(status, &url, request_id.as_deref(), None);
+                    }
+                    return Ok(RawResponse {
+                        status,
+                        headers,
+                        region,
+                        body: entry.body,
+                    });
+                }
+            }
 
             if status.is_success() {
+                let etag = header_string(&response, reqwest::header::ETAG);
+                let last_modified = header_string(&response, reqwest::header::LAST_MODIFIED);
                 let text = response.text().await?;
-                if text.is_empty() {
-                    return Ok(serde_json::from_str("null")?);
+                if let Some(ref logger) = self.logger {
+                    logger.log_response(status, &url, request_id.as_deref(), Some(&text));
                 }
-
-                // Try to parse as ApiResponse first
-                if let Ok(api_response) = serde_json::from_str::<ApiResponse<T>>(&text) {
-                    if api_response.success {
-                        if let Some(data) = api_response.data {
-                            return Ok(data);
-                        }
+                if let Some(cache) = cache {
+                    if method == reqwest::Method::GET {
+                        cache.store(path, etag, last_modified, &text);
+                    } else {
+                        cache.invalidate(path);
                     }
                 }
+                return Ok(RawResponse {
+                    status,
+                    headers,
+                    region,
+                    body: text,
+                });
+            }
 
-                // Parse directly
-                return Ok(serde_json::from_str(&text)?);
+            if status == StatusCode::UNAUTHORIZED && !renewed_credentials {
+                if let Some(ref credentials) = self.client_credentials {
+                    if let Some(ref logger) = self.logger {
+                        logger.log_response(status, &url, request_id.as_deref(), None);
+                    }
+                    credentials.invalidate().await;
+                    renewed_credentials = true;
+                    attempts -= 1;
+                    continue;
+                }
             }
 
+            let retry_after = header_string(&response, reqwest::header::RETRY_AFTER)
+                .and_then(|v| parse_retry_after(&v))
+                .unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+
             // Handle rate limiting
-            if status == StatusCode::TOO_MANY_REQUESTS && attempts < self.max_retries {
-                let retry_after = response
-                    .headers()
-                    .get("Retry-After")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok())
-                    .unwrap_or(60);
-                tokio::time::sleep(Duration::from_secs(retry_after)).await;
+            let delay = Duration::from_secs(retry_after);
+            if status == StatusCode::TOO_MANY_REQUESTS && attempts < self.max_retries && can_wait(delay) {
+                if let Some(ref logger) = self.logger {
+                    logger.log_response(status, &url, request_id.as_deref(), None);
+                }
+                metrics::retry("rate_limited");
+                metrics::rate_limit_sleep(retry_after);
+                self.hooks.rate_limited(|| RateLimitedEvent {
+                    method: method.to_string(),
+                    path: path.to_string(),
+                    retry_after: delay,
+                    request_id: request_id.clone(),
+                });
+                self.hooks.retry(|| retry_event(attempts, RetryReason::RateLimited, Some(status), delay, region_index));
+                tokio::time::sleep(delay).await;
                 continue;
             }
 
             // Handle server errors with retry
-            if status.is_server_error() && attempts < self.max_retries {
-                tokio::time::sleep(Duration::from_secs(2u64.pow(attempts))).await;
+            let delay = Duration::from_secs(2u64.pow(attempts));
+            if status.is_server_error() && attempts < self.max_retries && can_wait(delay) {
+                if let Some(ref logger) = self.logger {
+                    logger.log_response(status, &url, request_id.as_deref(), None);
+                }
+                metrics::retry("server_error");
+                self.hooks.retry(|| retry_event(attempts, RetryReason::ServerError, Some(status), delay, region_index));
+                tokio::time::sleep(delay).await;
                 continue;
             }
 
             let text = response.text().await.unwrap_or_default();
-            let message = serde_json::from_str::<ApiError>(&text)
-                .map(|e| e.message)
-                .unwrap_or_else(|_| text);
-
-            return Err(LinktorError::from_status(status, message, request_id));
+            if let Some(ref logger) = self.logger {
+                logger.log_response(status, &url, request_id.as_deref(), Some(&text));
+            }
+            return Err(LinktorError::from_response(status, &text, request_id).with_retry_after(retry_after));
         }
     }
 
@@ -157,27 +715,329 @@ impl LinktorClient {
         self.request(reqwest::Method::PATCH, path, Some(body)).await
     }
 
+    pub(crate) async fn put<T: DeserializeOwned>(&self, path: &str, body: impl Serialize) -> Result<T> {
+        self.request(reqwest::Method::PUT, path, Some(body)).await
+    }
+
+    /// Send a raw binary body, e.g. an image upload.
+    pub(crate) async fn put_bytes<T: DeserializeOwned>(&self, path: &str, content_type: &'static str, data: Vec<u8>) -> Result<T> {
+        let raw = self
+            .send(reqwest::Method::PUT, path, Some(RequestBody::Bytes { content_type: content_type.to_string(), data }))
+            .await?;
+        parse_body(&raw.body)
+    }
+
+    /// POST a single file as `multipart/form-data`. The body is built in
+    /// memory so retries and failover can resend it.
+    pub(crate) async fn post_file<T: DeserializeOwned>(&self, path: &str, file: MultipartFile<'_>) -> Result<T> {
+        let boundary = format!("linktor-{}", oauth::random_token(18)?);
+        let body = RequestBody::Bytes {
+            content_type: format!("multipart/form-data; boundary={}", boundary),
+            data: file.encode(&boundary),
+        };
+        let raw = self.send(reqwest::Method::POST, path, Some(body)).await?;
+        parse_body(&raw.body)
+    }
+
     pub(crate) async fn delete(&self, path: &str) -> Result<()> {
         self.request::<serde_json::Value>(reqwest::Method::DELETE, path, None::<()>).await?;
         Ok(())
     }
+
+    /// GET `path` and return the typed body together with status, headers and request ID.
+    pub async fn get_with_response<T: DeserializeOwned>(&self, path: &str) -> Result<Response<T>> {
+        self.request_with_response(reqwest::Method::GET, path, None::<()>).await
+    }
+
+    pub async fn post_with_response<T: DeserializeOwned>(&self, path: &str, body: impl Serialize) -> Result<Response<T>> {
+        self.request_with_response(reqwest::Method::POST, path, Some(body)).await
+    }
+
+    pub async fn patch_with_response<T: DeserializeOwned>(&self, path: &str, body: impl Serialize) -> Result<Response<T>> {
+        self.request_with_response(reqwest::Method::PATCH, path, Some(body)).await
+    }
+
+    pub async fn delete_with_response(&self, path: &str) -> Result<Response<serde_json::Value>> {
+        self.request_with_response(reqwest::Method::DELETE, path, None::<()>).await
+    }
+}
+
+enum RequestBody {
+    Json(serde_json::Value),
+    Bytes { content_type: String, data: Vec<u8> },
+}
+
+/// The `file` part of a multipart upload.
+pub(crate) struct MultipartFile<'a> {
+    pub data: &'a [u8],
+    pub content_type: &'a str,
+    pub filename: &'a str,
+}
+
+impl MultipartFile<'_> {
+    fn encode(&self, boundary: &str) -> Vec<u8> {
+        // Quotes and line breaks would end the header value early.
+        let filename: String = self
+            .filename
+            .chars()
+            .map(|c| if matches!(c, '"' | '\r' | '\n') { '_' } else { c })
+            .collect();
+        let mut body = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+            boundary, filename, self.content_type
+        )
+        .into_bytes();
+        body.extend_from_slice(self.data);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+        body
+    }
+}
+
+impl RequestBody {
+    fn json(body: Option<impl Serialize>) -> Result<Option<Self>> {
+        Ok(match body {
+            Some(body) => Some(RequestBody::Json(serde_json::to_value(body)?)),
+            None => None,
+        })
+    }
+}
+
+struct RawResponse {
+    status: StatusCode,
+    headers: reqwest::header::HeaderMap,
+    region: Region,
+    body: String,
+}
+
+/// Call `fetch` with exponential backoff until `done` accepts the result, or
+/// fail with `DeadlineExceeded` once `timeout` has passed.
+async fn poll_until<T, F, Fut>(timeout: Duration, mut fetch: F, done: impl Fn(&T) -> bool) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut delay = POLL_INTERVAL;
+    loop {
+        let value = fetch().await?;
+        if done(&value) {
+            return Ok(value);
+        }
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return Err(LinktorError::DeadlineExceeded { deadline: timeout });
+        }
+        tokio::time::sleep(delay.min(deadline - now)).await;
+        delay = (delay * 2).min(MAX_POLL_INTERVAL);
+    }
+}
+
+fn image_content_type(data: &[u8]) -> &'static str {
+    match data {
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+pub(crate) fn parse_body<T: DeserializeOwned>(text: &str) -> Result<T> {
+    if text.is_empty() {
+        return Ok(serde_json::from_str("null")?);
+    }
+
+    // Try to parse as ApiResponse first
+    if let Ok(api_response) = serde_json::from_str::<ApiResponse<T>>(text) {
+        if api_response.success {
+            if let Some(data) = api_response.data {
+                return Ok(data);
+            }
+        }
+    }
+
+    // Parse directly
+    Ok(serde_json::from_str(text)?)
+}
+
+pub(crate) fn parse_rate_limit(headers: &reqwest::header::HeaderMap) -> Option<RateLimitStatus> {
+    let number = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    let limit = number("X-RateLimit-Limit")?;
+    let remaining = number("X-RateLimit-Remaining")?;
+    // Reset is sent either as a Unix timestamp or as seconds until the window resets.
+    let reset_at = number("X-RateLimit-Reset").map(|reset| {
+        if reset > 1_000_000_000 {
+            chrono::DateTime::from_timestamp(reset as i64, 0).unwrap_or_default()
+        } else {
+            chrono::Utc::now() + chrono::Duration::seconds(reset as i64)
+        }
+    });
+
+    Some(RateLimitStatus {
+        limit: limit as u32,
+        remaining: remaining as u32,
+        reset_at,
+    })
+}
+
+fn header_string(response: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
 }
 
 #[derive(Default)]
 pub struct LinktorClientBuilder {
     base_url: Option<String>,
+    regions: Vec<Region>,
+    failover_cooldown: Option<Duration>,
     api_key: Option<String>,
+    signer: Option<RequestSigner>,
     access_token: Option<String>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    client_credentials: Option<(String, String)>,
+    scopes: Vec<String>,
+    act_as: Option<UserId>,
+    formatter: Option<Formatter>,
+    hooks: ClientHooks,
+    #[cfg(feature = "trace-context")]
+    trace_context: Option<TraceContextProvider>,
     timeout_secs: Option<u64>,
     max_retries: Option<u32>,
+    deadline: Option<Duration>,
+    logger: Option<DebugLogger>,
+    user_agent: Option<String>,
+    default_headers: Vec<(String, String)>,
+    gzip: Option<bool>,
+    brotli: Option<bool>,
+    cache: bool,
+    cache_capacity: Option<usize>,
 }
 
 impl LinktorClientBuilder {
+    /// Start a builder configured from the environment:
+    ///
+    /// - `LINKTOR_API_KEY` or `LINKTOR_ACCESS_TOKEN` (one is required)
+    /// - `LINKTOR_BASE_URL`
+    /// - `LINKTOR_TIMEOUT` (seconds)
+    /// - `LINKTOR_MAX_RETRIES`
+    /// - `LINKTOR_USER_AGENT`
+    /// - `LINKTOR_DEBUG` (`1`/`true` to log requests to stderr)
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Start a builder from a profile saved in the default [`CredentialStore`],
+    /// unlocked with `LINKTOR_CREDENTIALS_PASSPHRASE`.
+    ///
+    /// [`CredentialStore`]: crate::credentials::CredentialStore
+    #[cfg(feature = "credentials")]
+    pub fn from_stored_profile(name: &str) -> Result<Self> {
+        Self::from_credential_store(&crate::credentials::CredentialStore::from_env()?, name)
+    }
+
+    /// Start a builder from profile `name` in `store`. A profile with an API key
+    /// uses it; otherwise its login session is restored and kept up to date
+    /// through the store as tokens are refreshed.
+    #[cfg(feature = "credentials")]
+    pub fn from_credential_store(store: &crate::credentials::CredentialStore, name: &str) -> Result<Self> {
+        let profile = store.load(name)?.ok_or_else(|| LinktorError::Configuration {
+            message: format!("No stored credentials profile named {:?}", name),
+        })?;
+
+        let mut builder = Self::default();
+        if let Some(base_url) = profile.base_url {
+            builder = builder.base_url(base_url);
+        }
+        match profile.api_key {
+            Some(api_key) => builder = builder.api_key(api_key),
+            None => builder = builder.token_store(store.token_store(name)),
+        }
+        Ok(builder)
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |name: &str| lookup(name).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let number = |name: &str| -> Result<Option<u64>> {
+            match var(name) {
+                Some(value) => value.parse().map(Some).map_err(|_| LinktorError::Configuration {
+                    message: format!("{} must be a non-negative integer, got {:?}", name, value),
+                }),
+                None => Ok(None),
+            }
+        };
+
+        let mut builder = Self::default();
+        match (var("LINKTOR_API_KEY"), var("LINKTOR_ACCESS_TOKEN")) {
+            (Some(api_key), _) => builder = builder.api_key(api_key),
+            (None, Some(token)) => builder = builder.access_token(token),
+            (None, None) => {
+                return Err(LinktorError::Configuration {
+                    message: "LINKTOR_API_KEY or LINKTOR_ACCESS_TOKEN must be set".to_string(),
+                })
+            }
+        }
+        if let Some(base_url) = var("LINKTOR_BASE_URL") {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(timeout) = number("LINKTOR_TIMEOUT")? {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(retries) = number("LINKTOR_MAX_RETRIES")? {
+            builder = builder.max_retries(retries.min(u32::MAX as u64) as u32);
+        }
+        if let Some(user_agent) = var("LINKTOR_USER_AGENT") {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(debug) = var("LINKTOR_DEBUG") {
+            builder = builder.debug(matches!(debug.to_ascii_lowercase().as_str(), "1" | "true" | "yes"));
+        }
+        Ok(builder)
+    }
+
     pub fn base_url(mut self, url: impl Into<String>) -> Self {
         self.base_url = Some(url.into());
         self
     }
 
+    /// Add a region to the failover list. Regions are tried in the order they
+    /// are added; when any are configured they take precedence over `base_url`.
+    pub fn region(mut self, name: impl Into<String>, base_url: impl Into<String>) -> Self {
+        self.regions.push(Region::new(name, base_url));
+        self
+    }
+
+    /// Set an ordered list of endpoints, primary first. Each is registered as a
+    /// region named after its host.
+    pub fn base_urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for url in urls {
+            let url = url.into();
+            let name = url::Url::parse(&url)
+                .ok()
+                .and_then(|u| u.host_str().map(String::from))
+                .unwrap_or_else(|| url.clone());
+            self.regions.push(Region::new(name, url));
+        }
+        self
+    }
+
+    /// How long a failing region is skipped before it is tried again (default 30 seconds).
+    pub fn failover_cooldown(mut self, cooldown: Duration) -> Self {
+        self.failover_cooldown = Some(cooldown);
+        self
+    }
+
     pub fn api_key(mut self, key: impl Into<String>) -> Self {
         self.api_key = Some(key.into());
         self
@@ -188,6 +1048,27 @@ impl LinktorClientBuilder {
         self
     }
 
+    /// Authenticate as a service account. An access token is obtained from
+    /// `/auth/token` on first use and renewed shortly before it expires, or
+    /// when the API rejects it. Ignored when an API key is set.
+    pub fn client_credentials(mut self, client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        self.client_credentials = Some((client_id.into(), client_secret.into()));
+        self
+    }
+
+    /// Request a scope for [`client_credentials`](Self::client_credentials) tokens.
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    /// Authenticate by signing each request with HMAC-SHA256 instead of sending
+    /// a static key. Takes precedence over `api_key` and `access_token`.
+    pub fn request_signing(mut self, key_id: impl Into<String>, secret: impl Into<String>) -> Self {
+        self.signer = Some(RequestSigner::new(key_id, secret));
+        self
+    }
+
     pub fn timeout(mut self, secs: u64) -> Self {
         self.timeout_secs = Some(secs);
         self
@@ -198,204 +1079,1346 @@ impl LinktorClientBuilder {
         self
     }
 
-    pub fn build(self) -> Result<LinktorClient> {
-        let base_url = self.base_url.unwrap_or_else(|| "https://api.linktor.io".to_string());
-        let base_url = base_url.trim_end_matches('/').to_string();
+    /// Upper bound on the total time of a call, including every retry and
+    /// backoff. `timeout` still applies to each attempt.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
 
-        let http = Client::builder()
-            .timeout(Duration::from_secs(self.timeout_secs.unwrap_or(30)))
-            .build()?;
+    /// Identify your application on every request. The SDK version is appended,
+    /// e.g. `my-app/2.1 linktor-rust/1.0.0`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
 
-        Ok(LinktorClient {
-            http,
-            base_url,
-            api_key: self.api_key,
-            access_token: Arc::new(RwLock::new(self.access_token)),
-            max_retries: self.max_retries.unwrap_or(3),
-        })
+    /// Send an extra header on every request, e.g. a tenant routing header.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Negotiate gzip-compressed responses (enabled by default).
+    pub fn gzip(mut self, enabled: bool) -> Self {
+        self.gzip = Some(enabled);
+        self
+    }
+
+    /// Negotiate brotli-compressed responses (enabled by default).
+    pub fn brotli(mut self, enabled: bool) -> Self {
+        self.brotli = Some(enabled);
+        self
+    }
+
+    /// Cache GET responses that carry an `ETag` or `Last-Modified` header and
+    /// revalidate them with conditional requests, reusing the cached body on 304.
+    /// Writes to a resource, directly or through a batch, evict its cached entries.
+    pub fn response_cache(mut self, enabled: bool) -> Self {
+        self.cache = enabled;
+        self
+    }
+
+    /// Most responses the cache holds (default 256); the least recently used
+    /// one is evicted first. Implies [`response_cache(true)`](Self::response_cache).
+    pub fn response_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache = true;
+        self.cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Called before every retry, including failovers and 429 sleeps.
+    pub fn on_retry(mut self, hook: impl Fn(&RetryEvent) + Send + Sync + 'static) -> Self {
+        self.hooks.on_retry = Some(Arc::new(hook));
+        self
+    }
+
+    /// Called before the client sleeps on a 429 response.
+    pub fn on_rate_limited(mut self, hook: impl Fn(&RateLimitedEvent) + Send + Sync + 'static) -> Self {
+        self.hooks.on_rate_limited = Some(Arc::new(hook));
+        self
+    }
+
+    /// Called after a refreshed access token has been stored on the client.
+    pub fn on_auth_refresh(mut self, hook: impl Fn(&AuthRefreshEvent) + Send + Sync + 'static) -> Self {
+        self.hooks.on_auth_refresh = Some(Arc::new(hook));
+        self
+    }
+
+    /// Source of the trace context attached to each request as
+    /// `traceparent`/`tracestate`; called once per attempt. When it returns
+    /// `None`, the context bound with [`TraceContext::scope`] is used.
+    #[cfg(feature = "trace-context")]
+    pub fn trace_context_provider(mut self, provider: impl Fn() -> Option<TraceContext> + Send + Sync + 'static) -> Self {
+        self.trace_context = Some(Arc::new(provider));
+        self
+    }
+
+    /// Log every request and response to stderr, with credentials redacted.
+    pub fn debug(mut self, enabled: bool) -> Self {
+        self.logger = if enabled { Some(DebugLogger::stderr()) } else { None };
+        self
+    }
+
+    /// Log every request and response through a custom logger.
+    pub fn logger(mut self, logger: DebugLogger) -> Self {
+        self.logger = Some(logger);
+        self
+    }
+
+    /// Perform every request on behalf of `user_id`; see
+    /// [`LinktorClient::act_as`].
+    pub fn act_as(mut self, user_id: impl Into<UserId>) -> Self {
+        self.act_as = Some(user_id.into());
+        self
+    }
+
+    /// Format amounts and dates with `formatter` instead of fetching the
+    /// tenant's settings on first use.
+    pub fn formatter(mut self, formatter: Formatter) -> Self {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    /// Persist the session in `store`. A session saved by an earlier process is
+    /// restored when no API key or access token is configured; start the
+    /// background refresh afterwards to renew its access token as needed.
+    ///
+    /// ```rust,no_run
+    /// # async fn run() -> linktor::Result<()> {
+    /// use linktor::{FileTokenStore, LinktorClient};
+    ///
+    /// let client = LinktorClient::builder()
+    ///     .token_store(FileTokenStore::new("/home/me/.config/linktor/session.json"))
+    ///     .build()?;
+    /// let _refresher = client.auth().start_token_refresh();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn token_store(mut self, store: impl TokenStore + 'static) -> Self {
+        self.token_store = Some(Arc::new(store));
+        self
+    }
+
+    pub fn build(self) -> Result<LinktorClient> {
+        let regions = if self.regions.is_empty() {
+            let base_url = self.base_url.unwrap_or_else(|| "https://api.linktor.io".to_string());
+            vec![Region::new("default", base_url)]
+        } else {
+            self.regions
+        };
+
+        let user_agent = match self.user_agent {
+            Some(ua) => format!("{} {}", ua, SDK_USER_AGENT),
+            None => SDK_USER_AGENT.to_string(),
+        };
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in self.default_headers {
+            let header_name = reqwest::header::HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                LinktorError::Configuration {
+                    message: format!("Invalid header name: {}", name),
+                }
+            })?;
+            let header_value = reqwest::header::HeaderValue::from_str(&value).map_err(|_| {
+                LinktorError::Configuration {
+                    message: format!("Invalid value for header {}", name),
+                }
+            })?;
+            headers.insert(header_name, header_value);
+        }
+
+        let http = Client::builder()
+            .timeout(Duration::from_secs(self.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS)))
+            .user_agent(user_agent)
+            .default_headers(headers)
+            .gzip(self.gzip.unwrap_or(true))
+            .brotli(self.brotli.unwrap_or(true))
+            .build()?;
+
+        let session = tokio::sync::watch::channel(None).0;
+        let mut access_token = self.access_token;
+        if let (Some(store), None, None) = (&self.token_store, &self.api_key, &access_token) {
+            if let Some(stored) = store.load()? {
+                session.send_replace(Some(TokenSession::new(stored.refresh_token.clone(), stored.expires_in())));
+                access_token = Some(stored.access_token);
+            }
+        }
+
+        Ok(LinktorClient {
+            http,
+            regions: RegionPool::new(regions, self.failover_cooldown.unwrap_or(Duration::from_secs(30))),
+            last_region: Arc::new(std::sync::Mutex::new(None)),
+            api_key: self.api_key,
+            signer: self.signer,
+            access_token: Arc::new(RwLock::new(access_token)),
+            max_retries: self.max_retries.unwrap_or(3),
+            deadline: self.deadline,
+            logger: self.logger,
+            cache: self
+                .cache
+                .then(|| ResponseCache::with_capacity(self.cache_capacity.unwrap_or(cache::DEFAULT_CAPACITY))),
+            rate_limit: Arc::new(std::sync::Mutex::new(None)),
+            hooks: self.hooks,
+            #[cfg(feature = "trace-context")]
+            trace_context: self.trace_context,
+            capabilities: None,
+            session: Arc::new(session),
+            token_store: self.token_store,
+            client_credentials: self
+                .client_credentials
+                .map(|(id, secret)| Arc::new(ClientCredentials::new(id, secret, &self.scopes))),
+            tenant_id: None,
+            act_as: self.act_as,
+            formatter: Arc::new(tokio::sync::OnceCell::new_with(self.formatter)),
+        })
+    }
+}
+
+// Resource implementations
+
+pub struct AuthResource {
+    client: LinktorClient,
+}
+
+impl AuthResource {
+    /// Sign in with email and password. Accounts with MFA enabled get a
+    /// challenge to answer with [`complete_login`](Self::complete_login).
+    pub async fn login(&self, email: &str, password: &str) -> Result<LoginOutcome> {
+        let input = LoginInput::new(email, password);
+        match self.client.post::<LoginResponse>("/auth/login", input).await {
+            Ok(response) => {
+                self.sign_in(&response).await?;
+                Ok(LoginOutcome::Authenticated(Box::new(response)))
+            }
+            Err(e) if e.code() == Some(&ErrorCode::MfaRequired) => {
+                let challenge = e
+                    .details()
+                    .and_then(|d| serde_json::to_value(d).ok())
+                    .and_then(|v| serde_json::from_value::<MfaChallenge>(v).ok());
+                match challenge {
+                    Some(challenge) => Ok(LoginOutcome::MfaRequired(challenge)),
+                    None => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Answer an MFA challenge from `login` with a TOTP or recovery code.
+    pub async fn complete_login(&self, challenge: &MfaChallenge, code: &str) -> Result<LoginResponse> {
+        let input = CompleteLoginInput {
+            challenge_token: challenge.challenge_token.clone(),
+            code: code.to_string(),
+            method: None,
+        };
+        self.complete_login_with(input).await
+    }
+
+    pub async fn complete_login_with(&self, input: CompleteLoginInput) -> Result<LoginResponse> {
+        let response: LoginResponse = self.client.post("/auth/mfa/challenge", input).await?;
+        self.sign_in(&response).await?;
+        Ok(response)
+    }
+
+    async fn sign_in(&self, response: &LoginResponse) -> Result<()> {
+        self.client.set_access_token(Some(response.access_token.clone())).await;
+        self.client
+            .start_session(&response.access_token, &response.refresh_token, response.expires_in)
+            .await
+    }
+
+    /// Start TOTP enrollment for the signed-in user. MFA is not enforced until
+    /// the enrollment is confirmed with [`verify_mfa`](Self::verify_mfa).
+    pub async fn enroll_mfa(&self) -> Result<MfaEnrollment> {
+        self.client.post("/auth/mfa/enroll", serde_json::json!({})).await
+    }
+
+    /// Confirm enrollment with a current code from the authenticator app.
+    pub async fn verify_mfa(&self, code: &str) -> Result<MfaVerification> {
+        let input = MfaCodeInput { code: code.to_string() };
+        self.client.post("/auth/mfa/verify", input).await
+    }
+
+    pub async fn disable_mfa(&self, code: &str) -> Result<()> {
+        let input = MfaCodeInput { code: code.to_string() };
+        self.client.post::<serde_json::Value>("/auth/mfa/disable", input).await?;
+        Ok(())
+    }
+
+    pub async fn logout(&self) -> Result<()> {
+        self.client.post::<serde_json::Value>("/auth/logout", serde_json::json!({})).await?;
+        self.client.set_access_token(None).await;
+        self.client.session.send_replace(None);
+        self.client.with_token_store(|store| store.clear()).await
+    }
+
+    pub async fn refresh_token(&self, refresh_token: &str) -> Result<RefreshTokenResponse> {
+        let input = RefreshTokenInput {
+            refresh_token: refresh_token.to_string(),
+        };
+        let response: RefreshTokenResponse = self.client.post("/auth/refresh", input).await?;
+        self.client
+            .store_refreshed_token(response.access_token.clone(), response.expires_in)
+            .await;
+        self.client
+            .start_session(&response.access_token, &response.refresh_token, response.expires_in)
+            .await?;
+        Ok(response)
+    }
+
+    /// Email a password reset link to `email`. The API responds the same way
+    /// whether or not an account exists, so this cannot be used to probe for users.
+    pub async fn request_password_reset(&self, email: &str) -> Result<()> {
+        let input = RequestPasswordResetInput {
+            email: email.to_string(),
+        };
+        self.client.post::<serde_json::Value>("/auth/password/reset-request", input).await?;
+        Ok(())
+    }
+
+    /// Set a new password using the token from the reset email.
+    pub async fn confirm_password_reset(&self, token: &str, new_password: &str) -> Result<()> {
+        let input = ConfirmPasswordResetInput {
+            token: token.to_string(),
+            new_password: new_password.to_string(),
+        };
+        self.client.post::<serde_json::Value>("/auth/password/reset", input).await?;
+        Ok(())
+    }
+
+    /// Change the signed-in user's password. A wrong `current_password` is an
+    /// `Authentication` error.
+    pub async fn change_password(&self, current_password: &str, new_password: &str) -> Result<()> {
+        let input = ChangePasswordInput {
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        };
+        self.client.post::<serde_json::Value>("/auth/password/change", input).await?;
+        Ok(())
+    }
+
+    /// Start an OAuth authorization-code flow with PKCE. Open the returned URL
+    /// in the user's browser and keep the pending authorization for
+    /// [`exchange_code`](Self::exchange_code).
+    pub fn authorize_url(&self, params: OAuthParams) -> Result<PendingAuthorization> {
+        let region = self.client.regions.get(self.client.regions.candidates()[0]);
+        PendingAuthorization::new(&region.base_url, params)
+    }
+
+    /// Trade the code from the OAuth redirect for tokens and sign the client in,
+    /// as `login` does.
+    ///
+    /// ```rust,no_run
+    /// # async fn run(client: linktor::LinktorClient, redirected_to: &str) -> linktor::Result<()> {
+    /// use linktor::oauth::OAuthParams;
+    ///
+    /// let pending = client
+    ///     .auth()
+    ///     .authorize_url(OAuthParams::new("my-app", "http://127.0.0.1:8765/callback"))?;
+    /// println!("Open {}", pending.url);
+    /// // ... receive the browser redirect on the callback URL ...
+    /// let code = pending.code_from_redirect(redirected_to)?;
+    /// client.auth().exchange_code(&pending, &code).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn exchange_code(&self, pending: &PendingAuthorization, code: &str) -> Result<OAuthTokenResponse> {
+        let input = ExchangeCodeInput::new(&pending.client_id, code, &pending.redirect_uri, &pending.code_verifier);
+        self.exchange_code_with(input).await
+    }
+
+    pub async fn exchange_code_with(&self, input: ExchangeCodeInput) -> Result<OAuthTokenResponse> {
+        let response: OAuthTokenResponse = self.client.post(oauth::TOKEN_PATH, input).await?;
+        self.client.set_access_token(Some(response.access_token.clone())).await;
+        self.client
+            .start_session(&response.access_token, &response.refresh_token, response.expires_in)
+            .await?;
+        Ok(response)
+    }
+
+    /// Spawn a task that refreshes the access token shortly before it expires,
+    /// using the session from the latest `login` or `refresh_token` call.
+    /// Must be called within a Tokio runtime.
+    pub fn start_token_refresh(&self) -> TokenRefreshHandle {
+        self.start_token_refresh_with(TokenRefreshOptions::default())
+    }
+
+    pub fn start_token_refresh_with(&self, options: TokenRefreshOptions) -> TokenRefreshHandle {
+        TokenRefreshHandle::spawn(self.client.clone(), self.client.session.subscribe(), options)
+    }
+
+    /// Claims of the client's current access token, read locally without a
+    /// request; `None` when the client has no access token.
+    pub async fn current_claims(&self) -> Result<Option<Claims>> {
+        match self.client.access_token.read().await.as_deref() {
+            Some(token) => crate::jwt::decode_claims(token).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_current_user(&self) -> Result<User> {
+        self.client.get("/auth/me").await
+    }
+
+    pub async fn get_current_tenant(&self) -> Result<Tenant> {
+        self.client.get("/auth/tenant").await
+    }
+
+    /// Every tenant the signed-in user belongs to, with their role in each.
+    /// Use [`LinktorClient::with_tenant`] to act in one of them.
+    pub async fn list_tenants(&self) -> Result<Vec<TenantMembership>> {
+        self.client.get("/auth/tenants").await
+    }
+}
+
+pub struct ConversationsResource {
+    client: LinktorClient,
+}
+
+impl ConversationsResource {
+    pub async fn list(&self, params: Option<ListConversationsParams>) -> Result<PaginatedResponse<Conversation>> {
+        let path = with_query("/conversations", &params)?;
+        self.client.get(&path).await
+    }
+
+    pub async fn get(&self, id: impl Into<ConversationId>) -> Result<Conversation> {
+        self.client.get(&api_path!("/conversations/{}", id.into())).await
+    }
+
+    pub async fn update(&self, id: impl Into<ConversationId>, input: UpdateConversationInput) -> Result<Conversation> {
+        self.client.patch(&api_path!("/conversations/{}", id.into()), input).await
+    }
+
+    pub async fn send_text(&self, id: impl Into<ConversationId>, text: &str) -> Result<Message> {
+        let input = SendMessageInput::text(text);
+        self.send_message(id, input).await
+    }
+
+    pub async fn send_message(&self, id: impl Into<ConversationId>, input: SendMessageInput) -> Result<Message> {
+        self.client.post(&api_path!("/conversations/{}/messages", id.into()), input).await
+    }
+
+    /// Send an approved WhatsApp template, e.g. to message a contact outside
+    /// the 24-hour customer service window.
+    pub async fn send_template(&self, id: impl Into<ConversationId>, template: impl Into<TemplateContent>) -> Result<Message> {
+        self.send_message(id, SendMessageInput::template(template.into())).await
+    }
+
+    /// Validate and send reply buttons or a list menu.
+    pub async fn send_interactive(&self, id: impl Into<ConversationId>, message: InteractiveMessage) -> Result<Message> {
+        let content = message.build()?;
+        self.send_message(id, SendMessageInput::interactive(content)).await
+    }
+
+    /// Upload an image and send it. PNG, JPEG, GIF and WebP are detected from
+    /// the file header.
+    pub async fn send_image(
+        &self,
+        id: impl Into<ConversationId>,
+        data: impl Into<Vec<u8>>,
+        caption: Option<&str>,
+    ) -> Result<Message> {
+        let data = data.into();
+        let mime_type = image_content_type(&data);
+        let extension = mime_type.rsplit('/').next().unwrap_or("bin");
+        self.send_upload(id.into(), MessageType::Image, data, mime_type, &format!("image.{}", extension), caption)
+            .await
+    }
+
+    /// Upload a document, e.g. a PDF invoice, and send it under `filename`.
+    pub async fn send_document(
+        &self,
+        id: impl Into<ConversationId>,
+        data: impl Into<Vec<u8>>,
+        mime_type: &str,
+        filename: &str,
+        caption: Option<&str>,
+    ) -> Result<Message> {
+        self.send_upload(id.into(), MessageType::Document, data.into(), mime_type, filename, caption)
+            .await
+    }
+
+    /// Upload an audio clip, e.g. `audio/ogg` voice notes, and send it.
+    pub async fn send_audio(&self, id: impl Into<ConversationId>, data: impl Into<Vec<u8>>, mime_type: &str) -> Result<Message> {
+        let extension = mime_type.rsplit('/').next().unwrap_or("bin");
+        self.send_upload(id.into(), MessageType::Audio, data.into(), mime_type, &format!("audio.{}", extension), None)
+            .await
+    }
+
+    async fn send_upload(
+        &self,
+        id: ConversationId,
+        message_type: MessageType,
+        data: Vec<u8>,
+        mime_type: &str,
+        filename: &str,
+        caption: Option<&str>,
+    ) -> Result<Message> {
+        let uploaded = self.client.media().upload(data, mime_type, filename).await?;
+        self.send_message(id, SendMessageInput::media(message_type, uploaded.content(caption)))
+            .await
+    }
+
+    /// Send a message with client-side options. With `auto_chunk`, text longer
+    /// than the channel's limit is split at sentence boundaries and sent as
+    /// consecutive messages; all created messages are returned in order.
+    /// Messages with non-text content are never split, so an attachment or a
+    /// set of buttons is not repeated on every part.
+    pub async fn send_message_with(&self, id: impl Into<ConversationId>, input: SendMessageInput, options: SendOptions) -> Result<Vec<Message>> {
+        let id = id.into();
+        let text = match input.text {
+            Some(ref text) if options.auto_chunk && input.is_text_only() => text.clone(),
+            _ => return Ok(vec![self.send_message(id, input).await?]),
+        };
+
+        let max_length = match options.max_length {
+            Some(max) => Some(max),
+            None => {
+                let conversation = self.get(&id).await?;
+                let channel = self.client.channels().get(&conversation.channel_id).await?;
+                channel.channel_type.max_text_length()
+            }
+        };
+        let chunks = match max_length {
+            Some(max) if text.chars().count() > max => split_text(&text, max),
+            _ => return Ok(vec![self.send_message(id, input).await?]),
+        };
+
+        let mut messages = Vec::with_capacity(chunks.len());
+        for chunk in chunks {
+            let part = SendMessageInput {
+                text: Some(chunk),
+                ..input.clone()
+            };
+            messages.push(self.send_message(&id, part).await?);
+        }
+        Ok(messages)
+    }
+
+    /// Render a message as the conversation's channel would deliver it, without sending.
+    pub async fn preview_message(&self, id: impl Into<ConversationId>, input: SendMessageInput) -> Result<MessagePreview> {
+        self.client.post(&api_path!("/conversations/{}/messages/preview", id.into()), input).await
+    }
+
+    pub async fn get_messages(&self, id: impl Into<ConversationId>, params: Option<PaginationParams>) -> Result<PaginatedResponse<Message>> {
+        let path = with_query(&api_path!("/conversations/{}/messages", id.into()), &params)?;
+        self.client.get(&path).await
+    }
+
+    /// Messages with [`Message::translation`] filled in for `language`, next to
+    /// the original text, for agents who don't read the contact's language.
+    pub async fn get_messages_translated(
+        &self,
+        id: impl Into<ConversationId>,
+        language: impl Into<String>,
+        params: Option<PaginationParams>,
+    ) -> Result<PaginatedResponse<Message>> {
+        let query = TranslatedMessagesQuery {
+            translate_to: language.into(),
+            page: params,
+        };
+        let path = with_query(&api_path!("/conversations/{}/messages", id.into()), &query)?;
+        self.client.get(&path).await
+    }
+
+    pub async fn resolve(&self, id: impl Into<ConversationId>) -> Result<Conversation> {
+        self.client.post(&api_path!("/conversations/{}/resolve", id.into()), serde_json::json!({})).await
+    }
+
+    /// The conversation's audit timeline, oldest first: status changes,
+    /// assignments, tag edits and bot handoffs, each with who made them.
+    pub async fn history(&self, id: impl Into<ConversationId>) -> Result<Vec<ConversationEvent>> {
+        self.client.get(&api_path!("/conversations/{}/history", id.into())).await
+    }
+
+    /// Move a resolved or closed conversation back to `open`, clearing its
+    /// resolution time.
+    pub async fn reopen(&self, id: impl Into<ConversationId>) -> Result<Conversation> {
+        self.client.post(&api_path!("/conversations/{}/reopen", id.into()), serde_json::json!({})).await
+    }
+
+    /// Close the conversation for good. Unlike a resolved conversation, a
+    /// closed one is not reopened by a new inbound message; the contact's
+    /// next message starts a new conversation.
+    pub async fn close(&self, id: impl Into<ConversationId>) -> Result<Conversation> {
+        self.client.post(&api_path!("/conversations/{}/close", id.into()), serde_json::json!({})).await
+    }
+
+    /// Link a ticket or record in another system, e.g. a Jira issue. Linking
+    /// the same system and id again updates the URL.
+    pub async fn link_external(&self, id: impl Into<ConversationId>, reference: ExternalReference) -> Result<Conversation> {
+        self.client
+            .post(&api_path!("/conversations/{}/external-references", id.into()), reference)
+            .await
+    }
+
+    pub async fn unlink_external(
+        &self,
+        id: impl Into<ConversationId>,
+        system: impl Into<String>,
+        external_id: impl Into<String>,
+    ) -> Result<Conversation> {
+        let path = with_query(
+            &api_path!("/conversations/{}/external-references", id.into()),
+            &serde_json::json!({ "system": system.into(), "id": external_id.into() }),
+        )?;
+        self.client.request(reqwest::Method::DELETE, &path, None::<()>).await
+    }
+
+    pub async fn assign(&self, id: impl Into<ConversationId>, agent_id: impl Into<UserId>) -> Result<Conversation> {
+        self.client.post(&api_path!("/conversations/{}/assign", id.into()), serde_json::json!({"agentId": agent_id.into()})).await
+    }
+
+    /// Assign the conversation to `agent_id` only if it is currently unassigned.
+    /// Unlike [`assign`](Self::assign), concurrent claims cannot overwrite each other.
+    pub async fn claim(&self, id: impl Into<ConversationId>, agent_id: impl Into<UserId>) -> Result<ClaimOutcome> {
+        self.claim_with(
+            id,
+            ClaimConversationInput {
+                agent_id: agent_id.into(),
+                expected_agent_id: None,
+            },
+        )
+        .await
+    }
+
+    /// Take over a conversation from `previous_agent_id`, failing if someone else got it first.
+    pub async fn claim_from(&self, id: impl Into<ConversationId>, agent_id: impl Into<UserId>, previous_agent_id: impl Into<UserId>) -> Result<ClaimOutcome> {
+        self.claim_with(
+            id,
+            ClaimConversationInput {
+                agent_id: agent_id.into(),
+                expected_agent_id: Some(previous_agent_id.into()),
+            },
+        )
+        .await
+    }
+
+    pub async fn claim_with(&self, id: impl Into<ConversationId>, input: ClaimConversationInput) -> Result<ClaimOutcome> {
+        match self.client.post(&api_path!("/conversations/{}/claim", id.into()), input).await {
+            Ok(conversation) => Ok(ClaimOutcome::Claimed(Box::new(conversation))),
+            Err(e) if e.code() == Some(&ErrorCode::Conflict) => {
+                let holder = e
+                    .details()
+                    .and_then(|d| d.get("assignedAgentId"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from);
+                match holder {
+                    Some(agent) => Ok(ClaimOutcome::AlreadyClaimedBy(agent)),
+                    None => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Silence the bot in this conversation so a human can take over.
+    /// Pass `None` to pause until [`resume_bot`](Self::resume_bot) is called.
+    pub async fn pause_bot(&self, id: impl Into<ConversationId>, duration: Option<Duration>) -> Result<Conversation> {
+        let input = PauseBotInput {
+            duration_seconds: duration.map(|d| d.as_secs()),
+        };
+        self.client.post(&api_path!("/conversations/{}/bot/pause", id.into()), input).await
+    }
+
+    pub async fn resume_bot(&self, id: impl Into<ConversationId>) -> Result<Conversation> {
+        self.client.post(&api_path!("/conversations/{}/bot/resume", id.into()), serde_json::json!({})).await
+    }
+
+    /// Re-sync message delivery statuses with the channel provider, e.g. after
+    /// an outage, and report what changed.
+    pub async fn reconcile_statuses(&self, input: ReconcileStatusesInput) -> Result<ReconciliationReport> {
+        self.client.post("/conversations/statuses/reconcile", input).await
+    }
+
+    /// The conversation's status, assignee and tags as they were at `at`, for
+    /// investigating SLA disputes after the fact.
+    pub async fn get_at(&self, id: impl Into<ConversationId>, at: chrono::DateTime<chrono::Utc>) -> Result<ConversationSnapshot> {
+        let path = with_query(&api_path!("/conversations/{}/state", id.into()), &serde_json::json!({ "at": at }))?;
+        self.client.get(&path).await
+    }
+
+    /// Send the compliance notice to the conversation's contact unless they
+    /// already received its current version. Delivery is tracked per contact
+    /// by the API, so this is safe to call at the start of every conversation.
+    pub async fn ensure_compliance_notice(&self, id: impl Into<ConversationId>) -> Result<ComplianceNotice> {
+        self.client.post(&api_path!("/conversations/{}/compliance-notice", id.into()), serde_json::json!({})).await
+    }
+
+    /// The compliance notice in effect for this conversation: its own override
+    /// if set, otherwise the channel's.
+    pub async fn get_compliance_message(&self, id: impl Into<ConversationId>) -> Result<ComplianceMessageConfig> {
+        self.client.get(&api_path!("/conversations/{}/compliance-message", id.into())).await
+    }
+
+    /// Override the channel's compliance notice for this conversation.
+    pub async fn set_compliance_message(&self, id: impl Into<ConversationId>, config: ComplianceMessageConfig) -> Result<ComplianceMessageConfig> {
+        self.client.put(&api_path!("/conversations/{}/compliance-message", id.into()), config).await
+    }
+
+    /// Remove the override so the channel's notice applies again.
+    pub async fn delete_compliance_message(&self, id: impl Into<ConversationId>) -> Result<()> {
+        self.client.delete(&api_path!("/conversations/{}/compliance-message", id.into())).await
+    }
+
+    /// Override whether the attached AI agent replies on its own in this
+    /// conversation or only drafts suggestions.
+    pub async fn set_autopilot(&self, id: impl Into<ConversationId>, config: AutopilotConfig) -> Result<AutopilotState> {
+        self.client.put(&api_path!("/conversations/{}/autopilot", id.into()), config).await
+    }
+
+    /// Remove the override so the agent's default autopilot setting applies again.
+    pub async fn clear_autopilot(&self, id: impl Into<ConversationId>) -> Result<AutopilotState> {
+        let path = api_path!("/conversations/{}/autopilot", id.into());
+        self.client.request(reqwest::Method::DELETE, &path, None::<()>).await
+    }
+
+    /// Show the contact that someone is typing, on channels that support it.
+    /// Channels drop the indicator after a while or when a message is sent;
+    /// call again during long waits.
+    pub async fn start_typing(&self, id: impl Into<ConversationId>) -> Result<()> {
+        self.typing(id.into(), true).await
+    }
+
+    pub async fn stop_typing(&self, id: impl Into<ConversationId>) -> Result<()> {
+        self.typing(id.into(), false).await
+    }
+
+    /// Mark every message in the conversation as read and reset its
+    /// `unread_count`.
+    pub async fn mark_read(&self, id: impl Into<ConversationId>) -> Result<Conversation> {
+        self.client.post(&api_path!("/conversations/{}/read", id.into()), serde_json::json!({})).await
+    }
+
+    /// Mark a single message as read; the conversation's `unread_count` drops
+    /// accordingly.
+    pub async fn mark_message_read(
+        &self,
+        id: impl Into<ConversationId>,
+        message_id: impl Into<MessageId>,
+    ) -> Result<()> {
+        let path = api_path!("/conversations/{}/messages/read", id.into());
+        let body = serde_json::json!({ "message_ids": [message_id.into()] });
+        self.client.post::<serde_json::Value>(&path, body).await?;
+        Ok(())
+    }
+
+    /// Queue a message to be sent at `send_at`, e.g. an appointment reminder.
+    pub async fn schedule_message(
+        &self,
+        id: impl Into<ConversationId>,
+        input: SendMessageInput,
+        send_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<ScheduledMessage> {
+        let input = ScheduleMessageInput { message: input, send_at };
+        self.client.post(&api_path!("/conversations/{}/scheduled-messages", id.into()), input).await
+    }
+
+    /// Messages scheduled in the conversation that have not been sent yet,
+    /// soonest first.
+    pub async fn list_scheduled_messages(
+        &self,
+        id: impl Into<ConversationId>,
+        params: Option<PaginationParams>,
+    ) -> Result<PaginatedResponse<ScheduledMessage>> {
+        let path = with_query(&api_path!("/conversations/{}/scheduled-messages", id.into()), &params)?;
+        self.client.get(&path).await
+    }
+
+    /// Cancel a pending scheduled message. Messages already sent cannot be
+    /// cancelled.
+    pub async fn cancel_scheduled_message(
+        &self,
+        id: impl Into<ConversationId>,
+        scheduled_id: impl Into<ScheduledMessageId>,
+    ) -> Result<ScheduledMessage> {
+        let path = api_path!("/conversations/{}/scheduled-messages/{}", id.into(), scheduled_id.into());
+        self.client.request(reqwest::Method::DELETE, &path, None::<()>).await
+    }
+
+    /// Copy a message, media included, into another conversation, e.g. to
+    /// escalate to a specialist team. The copy records the original sender in
+    /// [`Message::forwarded_from`].
+    pub async fn forward_message(
+        &self,
+        id: impl Into<ConversationId>,
+        message_id: impl Into<MessageId>,
+        target: impl Into<ConversationId>,
+    ) -> Result<Message> {
+        let path = api_path!("/conversations/{}/messages/{}/forward", id.into(), message_id.into());
+        self.client
+            .post(&path, &serde_json::json!({ "targetConversationId": target.into() }))
+            .await
+    }
+
+    /// Summarize a conversation with its sentiment and suggested tags.
+    pub async fn summarize(&self, id: impl Into<ConversationId>) -> Result<ConversationSummary> {
+        self.client.post(&api_path!("/conversations/{}/summarize", id.into()), serde_json::json!({})).await
+    }
+
+    /// A single message with its current status and delivery receipts.
+    pub async fn get_message(&self, id: impl Into<ConversationId>, message_id: impl Into<MessageId>) -> Result<Message> {
+        self.client
+            .get(&api_path!("/conversations/{}/messages/{}", id.into(), message_id.into()))
+            .await
+    }
+
+    /// Poll a message until it reaches `status` (or a later one), so a flow can
+    /// confirm delivery before moving on. A message that fails is returned as
+    /// soon as the failure is seen; check `message.status` before continuing.
+    /// Gives up with [`LinktorError::DeadlineExceeded`] after `timeout`.
+    pub async fn wait_for_message_status(
+        &self,
+        id: impl Into<ConversationId>,
+        message_id: impl Into<MessageId>,
+        status: MessageStatus,
+        timeout: Duration,
+    ) -> Result<Message> {
+        let (id, message_id) = (id.into(), message_id.into());
+        poll_until(
+            timeout,
+            || self.get_message(id.clone(), message_id.clone()),
+            |message| message.status.has_reached(status) || message.status == MessageStatus::Failed,
+        )
+        .await
+    }
+
+    /// Download the attachment of a message, e.g. an inbound WhatsApp image,
+    /// through the API so provider credentials never leave the server.
+    pub async fn download_media(&self, id: impl Into<ConversationId>, message_id: impl Into<MessageId>) -> Result<bytes::Bytes> {
+        let path = api_path!("/conversations/{}/messages/{}/media", id.into(), message_id.into());
+        self.client.media().download(&path).await
+    }
+
+    /// Like [`download_media`](Self::download_media), as a stream.
+    pub async fn download_media_stream(
+        &self,
+        id: impl Into<ConversationId>,
+        message_id: impl Into<MessageId>,
+    ) -> Result<MediaStream> {
+        let path = api_path!("/conversations/{}/messages/{}/media", id.into(), message_id.into());
+        self.client.media().download_stream(&path).await
+    }
+
+    /// Delete a message. With `for_everyone` the message is also revoked on
+    /// the contact's device where the channel supports it (WhatsApp and
+    /// Telegram, within their time limits); otherwise it is only removed from
+    /// Linktor.
+    pub async fn delete_message(
+        &self,
+        id: impl Into<ConversationId>,
+        message_id: impl Into<MessageId>,
+        for_everyone: bool,
+    ) -> Result<()> {
+        let path = api_path!("/conversations/{}/messages/{}", id.into(), message_id.into());
+        let path = with_query(&path, &serde_json::json!({ "forEveryone": for_everyone }))?;
+        self.client.delete(&path).await
+    }
+
+    /// React to a message with `emoji`, replacing any earlier reaction from
+    /// the same user. Supported on WhatsApp and Telegram channels.
+    pub async fn react(
+        &self,
+        id: impl Into<ConversationId>,
+        message_id: impl Into<MessageId>,
+        emoji: &str,
+    ) -> Result<()> {
+        self.reaction(id.into(), message_id.into(), emoji).await
+    }
+
+    /// Remove the caller's reaction from a message.
+    pub async fn remove_reaction(&self, id: impl Into<ConversationId>, message_id: impl Into<MessageId>) -> Result<()> {
+        self.reaction(id.into(), message_id.into(), "").await
+    }
+
+    /// An empty `emoji` removes the reaction.
+    async fn reaction(&self, id: ConversationId, message_id: MessageId, emoji: &str) -> Result<()> {
+        let path = api_path!("/conversations/{}/messages/{}/reactions", id, message_id);
+        self.client.post::<serde_json::Value>(&path, serde_json::json!({ "emoji": emoji })).await?;
+        Ok(())
+    }
+
+    async fn typing(&self, id: ConversationId, is_typing: bool) -> Result<()> {
+        let path = api_path!("/conversations/{}/typing", id);
+        self.client.post::<serde_json::Value>(&path, serde_json::json!({ "is_typing": is_typing })).await?;
+        Ok(())
+    }
+}
+
+pub struct ContactsResource {
+    client: LinktorClient,
+}
+
+impl ContactsResource {
+    pub async fn list(&self, params: Option<ListContactsParams>) -> Result<PaginatedResponse<Contact>> {
+        let path = with_query("/contacts", &params)?;
+        self.client.get(&path).await
+    }
+
+    pub async fn get(&self, id: impl Into<ContactId>) -> Result<Contact> {
+        self.client.get(&api_path!("/contacts/{}", id.into())).await
+    }
+
+    pub async fn create(&self, input: CreateContactInput) -> Result<Contact> {
+        self.client.post("/contacts", input).await
+    }
+
+    pub async fn update(&self, id: impl Into<ContactId>, input: UpdateContactInput) -> Result<Contact> {
+        self.client.patch(&api_path!("/contacts/{}", id.into()), input).await
+    }
+
+    pub async fn delete(&self, id: impl Into<ContactId>) -> Result<()> {
+        self.client.delete(&api_path!("/contacts/{}", id.into())).await
+    }
+
+    /// The contact's conversations, narrowed further by any other `params`.
+    pub async fn conversations(
+        &self,
+        id: impl Into<ContactId>,
+        params: Option<ListConversationsParams>,
+    ) -> Result<PaginatedResponse<Conversation>> {
+        let params = ListConversationsParams {
+            contact_id: Some(id.into()),
+            ..params.unwrap_or_default()
+        };
+        self.client.conversations().list(Some(params)).await
+    }
+
+    /// Erase a contact for a GDPR/LGPD request: their messages are redacted,
+    /// identifiers removed and the profile anonymized. This cannot be undone.
+    pub async fn erase(&self, id: impl Into<ContactId>) -> Result<ContactErasure> {
+        self.erase_with(id, EraseContactInput::default()).await
+    }
+
+    pub async fn erase_with(&self, id: impl Into<ContactId>, input: EraseContactInput) -> Result<ContactErasure> {
+        self.client.post(&api_path!("/contacts/{}/erase", id.into()), input).await
+    }
+
+    /// Current state of an erasure started with [`erase`](Self::erase).
+    pub async fn get_erasure(&self, id: impl Into<ContactId>, erasure_id: impl Into<ErasureId>) -> Result<ContactErasure> {
+        self.client.get(&api_path!("/contacts/{}/erasures/{}", id.into(), erasure_id.into())).await
+    }
+
+    /// Merge duplicate contacts into the primary one, moving their
+    /// conversations and channel identifiers over.
+    pub async fn merge(&self, input: MergeContactsInput) -> Result<ContactMergeResult> {
+        self.client.post("/contacts/merge", input).await
+    }
+
+    /// Infer the contact's locale and timezone from its phone country code and
+    /// message language, storing them in the `locale`/`timezone` custom fields.
+    pub async fn infer_locale(&self, id: impl Into<ContactId>) -> Result<LocaleInference> {
+        self.infer_locale_with(id, InferLocaleInput::default()).await
+    }
+
+    pub async fn infer_locale_with(&self, id: impl Into<ContactId>, input: InferLocaleInput) -> Result<LocaleInference> {
+        self.client.post(&api_path!("/contacts/{}/infer-locale", id.into()), input).await
+    }
+
+    /// Upload an image as the contact's avatar. PNG, JPEG, GIF and WebP are detected
+    /// from the file header; anything else is sent as `application/octet-stream`.
+    pub async fn upload_avatar(&self, id: impl Into<ContactId>, bytes: impl Into<Vec<u8>>) -> Result<ContactAvatar> {
+        let data = bytes.into();
+        let content_type = image_content_type(&data);
+        self.client.put_bytes(&api_path!("/contacts/{}/avatar", id.into()), content_type, data).await
+    }
+
+    /// Replace the contact's avatar with their profile picture on `channel_id`,
+    /// e.g. their WhatsApp profile photo.
+    pub async fn refresh_avatar_from_channel(&self, id: impl Into<ContactId>, channel_id: impl Into<ChannelId>) -> Result<ContactAvatar> {
+        let input = RefreshAvatarInput {
+            channel_id: channel_id.into(),
+        };
+        self.client.post(&api_path!("/contacts/{}/avatar/refresh", id.into()), input).await
+    }
+}
+
+pub struct UsersResource {
+    client: LinktorClient,
+}
+
+impl UsersResource {
+    pub async fn list(&self, params: Option<ListUsersParams>) -> Result<PaginatedResponse<User>> {
+        let path = with_query("/users", &params)?;
+        self.client.get(&path).await
+    }
+
+    pub async fn get(&self, id: impl Into<UserId>) -> Result<User> {
+        self.client.get(&api_path!("/users/{}", id.into())).await
+    }
+
+    /// Invite someone to the tenant by email. The user is created with
+    /// `Pending` status until they accept.
+    pub async fn invite(&self, input: InviteUserInput) -> Result<User> {
+        self.client.post("/users/invite", input).await
+    }
+
+    /// Send the invitation email again to a user who has not accepted yet.
+    pub async fn resend_invite(&self, id: impl Into<UserId>) -> Result<User> {
+        self.client.post(&api_path!("/users/{}/invite/resend", id.into()), serde_json::json!({})).await
+    }
+
+    pub async fn update(&self, id: impl Into<UserId>, input: UpdateUserInput) -> Result<User> {
+        self.client.patch(&api_path!("/users/{}", id.into()), input).await
+    }
+
+    pub async fn set_role(&self, id: impl Into<UserId>, role: UserRole) -> Result<User> {
+        let input = UpdateUserInput {
+            role: Some(role),
+            ..Default::default()
+        };
+        self.update(id, input).await
+    }
+
+    /// Block the user from signing in and end their sessions. Their
+    /// conversations and history are kept.
+    pub async fn deactivate(&self, id: impl Into<UserId>) -> Result<User> {
+        self.client.post(&api_path!("/users/{}/deactivate", id.into()), serde_json::json!({})).await
+    }
+
+    pub async fn reactivate(&self, id: impl Into<UserId>) -> Result<User> {
+        self.client.post(&api_path!("/users/{}/reactivate", id.into()), serde_json::json!({})).await
+    }
+
+    /// Replace the user's routing skills.
+    pub async fn set_skills(&self, id: impl Into<UserId>, skills: Vec<Skill>) -> Result<User> {
+        self.set_skills_with(id, SetSkillsInput::new(skills)).await
+    }
+
+    pub async fn set_skills_with(&self, id: impl Into<UserId>, input: SetSkillsInput) -> Result<User> {
+        self.client.put(&api_path!("/users/{}/skills", id.into()), input).await
+    }
+}
+
+pub struct ChannelsResource {
+    client: LinktorClient,
+}
+
+impl ChannelsResource {
+    pub async fn list(&self, params: Option<ListChannelsParams>) -> Result<PaginatedResponse<Channel>> {
+        let path = with_query("/channels", &params)?;
+        self.client.get(&path).await
+    }
+
+    pub async fn get(&self, id: impl Into<ChannelId>) -> Result<Channel> {
+        self.client.get(&api_path!("/channels/{}", id.into())).await
+    }
+
+    pub async fn create(&self, input: CreateChannelInput) -> Result<Channel> {
+        self.client.post("/channels", input).await
+    }
+
+    pub async fn update(&self, id: impl Into<ChannelId>, input: UpdateChannelInput) -> Result<Channel> {
+        self.client.patch(&api_path!("/channels/{}", id.into()), input).await
+    }
+
+    pub async fn delete(&self, id: impl Into<ChannelId>) -> Result<()> {
+        self.client.delete(&api_path!("/channels/{}", id.into())).await
     }
-}
 
-// Resource implementations
+    pub async fn connect(&self, id: impl Into<ChannelId>) -> Result<Channel> {
+        self.client.post(&api_path!("/channels/{}/connect", id.into()), serde_json::json!({})).await
+    }
 
-pub struct AuthResource {
-    client: LinktorClient,
-}
+    pub async fn disconnect(&self, id: impl Into<ChannelId>) -> Result<Channel> {
+        self.client.post(&api_path!("/channels/{}/disconnect", id.into()), serde_json::json!({})).await
+    }
 
-impl AuthResource {
-    pub async fn login(&self, email: &str, password: &str) -> Result<LoginResponse> {
-        let input = LoginInput::new(email, password);
-        let response: LoginResponse = self.client.post("/auth/login", input).await?;
-        self.client.set_access_token(Some(response.access_token.clone())).await;
-        Ok(response)
+    /// Where this channel's inbound events are delivered.
+    pub async fn get_webhook(&self, id: impl Into<ChannelId>) -> Result<WebhookConfig> {
+        self.client.get(&api_path!("/channels/{}/webhook", id.into())).await
     }
 
-    pub async fn logout(&self) -> Result<()> {
-        self.client.post::<serde_json::Value>("/auth/logout", serde_json::json!({})).await?;
-        self.client.set_access_token(None).await;
-        Ok(())
+    pub async fn set_webhook(&self, id: impl Into<ChannelId>, config: WebhookConfig) -> Result<WebhookConfig> {
+        self.client.put(&api_path!("/channels/{}/webhook", id.into()), config).await
     }
 
-    pub async fn refresh_token(&self, refresh_token: &str) -> Result<RefreshTokenResponse> {
-        let input = RefreshTokenInput {
-            refresh_token: refresh_token.to_string(),
+    pub async fn webchat_settings(&self, id: impl Into<ChannelId>) -> Result<WebchatSettings> {
+        self.client.get(&api_path!("/channels/{}/webchat", id.into())).await
+    }
+
+    pub async fn update_webchat_settings(&self, id: impl Into<ChannelId>, settings: WebchatSettings) -> Result<WebchatSettings> {
+        self.client.put(&api_path!("/channels/{}/webchat", id.into()), settings).await
+    }
+
+    /// The `<script>` tag that loads a webchat channel's widget on a page,
+    /// served from the origin of the client's active region. The widget picks
+    /// up its settings when it starts.
+    pub fn embed_snippet(&self, id: impl Into<ChannelId>) -> String {
+        let regions = &self.client.regions;
+        let base_url = &regions.get(regions.candidates()[0]).base_url;
+        let origin = match url::Url::parse(base_url) {
+            Ok(url) => url.origin().ascii_serialization(),
+            Err(_) => base_url.trim_end_matches('/').to_string(),
         };
-        let response: RefreshTokenResponse = self.client.post("/auth/refresh", input).await?;
-        self.client.set_access_token(Some(response.access_token.clone())).await;
-        Ok(response)
+        let widget_url = format!("{}/widget/linktor.js", origin);
+        format!(
+            r#"<script>
+  (function(w, d, s, o, f, js, fjs) {{
+    w['LinktorWidget'] = o;
+    w[o] = w[o] || function() {{ (w[o].q = w[o].q || []).push(arguments) }};
+    js = d.createElement(s); fjs = d.getElementsByTagName(s)[0];
+    js.id = o; js.src = f; js.async = 1; fjs.parentNode.insertBefore(js, fjs);
+  }}(window, document, 'script', 'linktor', {}));
+  linktor('init', {{ channelId: {} }});
+</script>"#,
+            script_string(&widget_url),
+            script_string(id.into().as_str())
+        )
+    }
+
+    /// Copy a configured channel, possibly into another tenant. Config is
+    /// copied with `overrides` applied; credentials are reset on the copy.
+    pub async fn clone(&self, id: impl Into<ChannelId>, overrides: CloneChannelInput) -> Result<ClonedChannel> {
+        self.client.post(&api_path!("/channels/{}/clone", id.into()), overrides).await
+    }
+
+    /// Send a text straight through the channel without creating a contact or
+    /// conversation, e.g. as a post-deploy smoke test. `to` is a phone number,
+    /// chat id or address, depending on the channel.
+    pub async fn send_test(&self, id: impl Into<ChannelId>, to: &str, text: &str) -> Result<TestMessageResult> {
+        self.client
+            .post(&api_path!("/channels/{}/test-message", id.into()), serde_json::json!({ "to": to, "text": text }))
+            .await
+    }
+
+    /// Daily sent/received counts, delivery and read rates, and errors.
+    pub async fn analytics(&self, id: impl Into<ChannelId>, range: AnalyticsRange) -> Result<ChannelAnalytics> {
+        let path = with_query(&api_path!("/channels/{}/analytics", id.into()), &range)?;
+        self.client.get(&path).await
     }
 
-    pub async fn get_current_user(&self) -> Result<User> {
-        self.client.get("/auth/me").await
+    /// Check the channel's provider credentials end to end. For email this
+    /// sends a probe message over SMTP and waits for it to arrive over IMAP.
+    pub async fn test_connection(&self, id: impl Into<ChannelId>) -> Result<ConnectionTest> {
+        self.client.post(&api_path!("/channels/{}/test", id.into()), serde_json::json!({})).await
     }
 
-    pub async fn get_current_tenant(&self) -> Result<Tenant> {
-        self.client.get("/auth/tenant").await
+    /// Start OAuth re-consent for a channel whose provider token expired.
+    pub async fn reauthorize(&self, id: impl Into<ChannelId>) -> Result<ReauthorizationSession> {
+        self.client
+            .post(&api_path!("/channels/{}/reauthorize", id.into()), serde_json::json!({}))
+            .await
     }
-}
 
-pub struct ConversationsResource {
-    client: LinktorClient,
-}
+    /// Current state of a session started with [`reauthorize`](Self::reauthorize).
+    pub async fn get_reauthorization(
+        &self,
+        id: impl Into<ChannelId>,
+        session_id: impl Into<ReauthorizationSessionId>,
+    ) -> Result<ReauthorizationSession> {
+        self.client
+            .get(&api_path!("/channels/{}/reauthorize/{}", id.into(), session_id.into()))
+            .await
+    }
 
-impl ConversationsResource {
-    pub async fn list(&self, params: Option<ListConversationsParams>) -> Result<PaginatedResponse<Conversation>> {
-        let path = match params {
-            Some(p) => format!("/conversations?{}", serde_urlencoded::to_string(&p).unwrap_or_default()),
-            None => "/conversations".to_string(),
-        };
-        self.client.get(&path).await
+    /// Have the provider send a verification code to the channel's phone
+    /// number, as part of onboarding a WhatsApp Cloud or SMS number.
+    pub async fn request_verification(&self, id: impl Into<ChannelId>, method: VerificationMethod) -> Result<VerificationRequest> {
+        self.client
+            .post(&api_path!("/channels/{}/verification", id.into()), serde_json::json!({ "method": method }))
+            .await
     }
 
-    pub async fn get(&self, id: &str) -> Result<Conversation> {
-        self.client.get(&format!("/conversations/{}", id)).await
+    /// Submit the code received by SMS or voice call. A wrong or expired code
+    /// is rejected with a validation error.
+    pub async fn submit_verification_code(&self, id: impl Into<ChannelId>, code: &str) -> Result<Channel> {
+        self.client
+            .post(&api_path!("/channels/{}/verification/verify", id.into()), serde_json::json!({ "code": code }))
+            .await
     }
 
-    pub async fn update(&self, id: &str, input: UpdateConversationInput) -> Result<Conversation> {
-        self.client.patch(&format!("/conversations/{}", id), input).await
+    /// Provider tier, remaining daily quota and quality rating, for pacing
+    /// campaigns without getting the number flagged.
+    pub async fn limits(&self, id: impl Into<ChannelId>) -> Result<ChannelLimits> {
+        self.client.get(&api_path!("/channels/{}/limits", id.into())).await
     }
 
-    pub async fn send_text(&self, id: &str, text: &str) -> Result<Message> {
-        let input = SendMessageInput::text(text);
-        self.send_message(id, input).await
+    /// Live connection state of the channel with the provider.
+    pub async fn status(&self, id: impl Into<ChannelId>) -> Result<ChannelStatusResponse> {
+        self.client.get(&api_path!("/channels/{}/status", id.into())).await
     }
 
-    pub async fn send_message(&self, id: &str, input: SendMessageInput) -> Result<Message> {
-        self.client.post(&format!("/conversations/{}/messages", id), input).await
+    /// Poll [`status`](Self::status) after [`connect`](Self::connect) until the
+    /// channel is connected. A channel that reports `Error` is returned right
+    /// away with its `error_message`; check `status` before relying on it.
+    /// Gives up with [`LinktorError::DeadlineExceeded`] after `timeout`.
+    pub async fn wait_until_connected(&self, id: impl Into<ChannelId>, timeout: Duration) -> Result<ChannelStatusResponse> {
+        let id = id.into();
+        poll_until(
+            timeout,
+            || self.status(id.clone()),
+            |health| matches!(health.status, ChannelStatus::Connected | ChannelStatus::Error),
+        )
+        .await
     }
 
-    pub async fn get_messages(&self, id: &str, params: Option<PaginationParams>) -> Result<PaginatedResponse<Message>> {
-        let path = match params {
-            Some(p) => format!("/conversations/{}/messages?{}", id, serde_urlencoded::to_string(&p).unwrap_or_default()),
-            None => format!("/conversations/{}/messages", id),
-        };
-        self.client.get(&path).await
+    pub async fn get_compliance_message(&self, id: impl Into<ChannelId>) -> Result<ComplianceMessageConfig> {
+        self.client.get(&api_path!("/channels/{}/compliance-message", id.into())).await
     }
 
-    pub async fn resolve(&self, id: &str) -> Result<Conversation> {
-        self.client.post(&format!("/conversations/{}/resolve", id), serde_json::json!({})).await
+    /// Set the notice every contact on this channel receives before anything else.
+    pub async fn set_compliance_message(&self, id: impl Into<ChannelId>, config: ComplianceMessageConfig) -> Result<ComplianceMessageConfig> {
+        self.client.put(&api_path!("/channels/{}/compliance-message", id.into()), config).await
     }
 
-    pub async fn assign(&self, id: &str, agent_id: &str) -> Result<Conversation> {
-        self.client.post(&format!("/conversations/{}/assign", id), serde_json::json!({"agentId": agent_id})).await
+    pub async fn delete_compliance_message(&self, id: impl Into<ChannelId>) -> Result<()> {
+        self.client.delete(&api_path!("/channels/{}/compliance-message", id.into())).await
     }
 }
 
-pub struct ContactsResource {
+pub struct BotsResource {
     client: LinktorClient,
 }
 
-impl ContactsResource {
-    pub async fn list(&self, params: Option<ListContactsParams>) -> Result<PaginatedResponse<Contact>> {
-        let path = match params {
-            Some(p) => format!("/contacts?{}", serde_urlencoded::to_string(&p).unwrap_or_default()),
-            None => "/contacts".to_string(),
-        };
+impl BotsResource {
+    pub async fn list(&self, params: Option<ListBotsParams>) -> Result<PaginatedResponse<Bot>> {
+        let path = with_query("/bots", &params)?;
         self.client.get(&path).await
     }
 
-    pub async fn get(&self, id: &str) -> Result<Contact> {
-        self.client.get(&format!("/contacts/{}", id)).await
+    pub async fn get(&self, id: impl Into<BotId>) -> Result<Bot> {
+        self.client.get(&api_path!("/bots/{}", id.into())).await
     }
 
-    pub async fn create(&self, input: CreateContactInput) -> Result<Contact> {
-        self.client.post("/contacts", input).await
+    pub async fn create(&self, input: CreateBotInput) -> Result<Bot> {
+        self.client.post("/bots", input).await
     }
 
-    pub async fn update(&self, id: &str, input: UpdateContactInput) -> Result<Contact> {
-        self.client.patch(&format!("/contacts/{}", id), input).await
+    pub async fn update(&self, id: impl Into<BotId>, input: UpdateBotInput) -> Result<Bot> {
+        self.client.patch(&api_path!("/bots/{}", id.into()), input).await
     }
 
-    pub async fn delete(&self, id: &str) -> Result<()> {
-        self.client.delete(&format!("/contacts/{}", id)).await
+    pub async fn delete(&self, id: impl Into<BotId>) -> Result<()> {
+        self.client.delete(&api_path!("/bots/{}", id.into())).await
     }
-}
 
-pub struct ChannelsResource {
-    client: LinktorClient,
-}
+    /// Start responding to messages on the bot's channels.
+    pub async fn activate(&self, id: impl Into<BotId>) -> Result<()> {
+        self.client
+            .post::<serde_json::Value>(&api_path!("/bots/{}/activate", id.into()), serde_json::json!({}))
+            .await?;
+        Ok(())
+    }
 
-impl ChannelsResource {
-    pub async fn list(&self, params: Option<ListChannelsParams>) -> Result<PaginatedResponse<Channel>> {
-        let path = match params {
-            Some(p) => format!("/channels?{}", serde_urlencoded::to_string(&p).unwrap_or_default()),
-            None => "/channels".to_string(),
+    pub async fn deactivate(&self, id: impl Into<BotId>) -> Result<()> {
+        self.client
+            .post::<serde_json::Value>(&api_path!("/bots/{}/deactivate", id.into()), serde_json::json!({}))
+            .await?;
+        Ok(())
+    }
+
+    /// Stop the bot from responding everywhere for `duration`, e.g. during a
+    /// maintenance window; it resumes on its own afterwards, or earlier with
+    /// [`activate`](Self::activate). To silence it in a single conversation
+    /// use `conversations().pause_bot()` instead.
+    pub async fn pause(&self, id: impl Into<BotId>, duration: Duration) -> Result<()> {
+        let input = PauseBotInput {
+            duration_seconds: Some(duration.as_secs()),
         };
-        self.client.get(&path).await
+        self.client
+            .post::<serde_json::Value>(&api_path!("/bots/{}/pause", id.into()), input)
+            .await?;
+        Ok(())
     }
 
-    pub async fn get(&self, id: &str) -> Result<Channel> {
-        self.client.get(&format!("/channels/{}", id)).await
+    /// Have the bot answer on one more channel. Unlike setting `channel_ids`
+    /// with [`update`](Self::update), this doesn't overwrite channels attached
+    /// concurrently by someone else.
+    pub async fn attach_channel(&self, id: impl Into<BotId>, channel_id: impl Into<ChannelId>) -> Result<()> {
+        let input = AttachChannelInput {
+            channel_id: channel_id.into(),
+        };
+        self.client
+            .post::<serde_json::Value>(&api_path!("/bots/{}/channels", id.into()), input)
+            .await?;
+        Ok(())
     }
 
-    pub async fn create(&self, input: CreateChannelInput) -> Result<Channel> {
-        self.client.post("/channels", input).await
+    pub async fn detach_channel(&self, id: impl Into<BotId>, channel_id: impl Into<ChannelId>) -> Result<()> {
+        self.client
+            .delete(&api_path!("/bots/{}/channels/{}", id.into(), channel_id.into()))
+            .await
     }
 
-    pub async fn update(&self, id: &str, input: UpdateChannelInput) -> Result<Channel> {
-        self.client.patch(&format!("/channels/{}", id), input).await
+    /// Copy a bot's config, flow and knowledge bases under a new name, e.g. to
+    /// start a client's bot from a template. The copy is created as a draft
+    /// with no channels, so it stays silent until attached and activated.
+    pub async fn duplicate(&self, id: impl Into<BotId>, new_name: &str) -> Result<Bot> {
+        let input = DuplicateBotInput {
+            name: new_name.to_string(),
+        };
+        self.client.post(&api_path!("/bots/{}/duplicate", id.into()), input).await
     }
 
-    pub async fn delete(&self, id: &str) -> Result<()> {
-        self.client.delete(&format!("/channels/{}", id)).await
+    pub async fn get_schedule(&self, id: impl Into<BotId>) -> Result<BotSchedule> {
+        self.client.get(&api_path!("/bots/{}/schedule", id.into())).await
+    }
+
+    /// Replace the bot's active hours and off-hours behavior.
+    pub async fn set_schedule(&self, id: impl Into<BotId>, schedule: BotSchedule) -> Result<BotSchedule> {
+        self.client.put(&api_path!("/bots/{}/schedule", id.into()), schedule).await
+    }
+
+    /// NLU intents of a flow or hybrid bot.
+    pub fn intents(&self, id: impl Into<BotId>) -> BotIntentsResource {
+        BotIntentsResource {
+            client: self.client.clone(),
+            bot_id: id.into(),
+        }
     }
 
-    pub async fn connect(&self, id: &str) -> Result<Channel> {
-        self.client.post(&format!("/channels/{}/connect", id), serde_json::json!({})).await
+    /// Match `text` against the bot's intents without starting a conversation,
+    /// e.g. to check training data after a change.
+    pub async fn detect_intent(&self, id: impl Into<BotId>, text: &str) -> Result<IntentDetection> {
+        let input = DetectIntentInput { text: text.to_string() };
+        self.client.post(&api_path!("/bots/{}/detect-intent", id.into()), input).await
     }
 
-    pub async fn disconnect(&self, id: &str) -> Result<Channel> {
-        self.client.post(&format!("/channels/{}/disconnect", id), serde_json::json!({})).await
+    /// Run the bot against a synthetic conversation. Nothing is sent on its
+    /// channels; the result traces the flow nodes, AI calls and tool
+    /// invocations behind each reply, so bot changes can be checked in CI.
+    pub async fn simulate(&self, id: impl Into<BotId>, messages: Vec<SimulatedMessage>) -> Result<BotSimulation> {
+        let input = SimulateBotInput { messages };
+        self.client.post(&api_path!("/bots/{}/simulate", id.into()), input).await
     }
 }
 
-pub struct BotsResource {
+pub struct BotIntentsResource {
     client: LinktorClient,
+    bot_id: BotId,
 }
 
-impl BotsResource {
-    pub async fn list(&self, params: Option<ListBotsParams>) -> Result<PaginatedResponse<Bot>> {
-        let path = match params {
-            Some(p) => format!("/bots?{}", serde_urlencoded::to_string(&p).unwrap_or_default()),
-            None => "/bots".to_string(),
-        };
-        self.client.get(&path).await
+impl BotIntentsResource {
+    pub async fn list(&self) -> Result<Vec<Intent>> {
+        self.client.get(&api_path!("/bots/{}/intents", self.bot_id)).await
     }
 
-    pub async fn get(&self, id: &str) -> Result<Bot> {
-        self.client.get(&format!("/bots/{}", id)).await
+    pub async fn get(&self, name: &str) -> Result<Intent> {
+        self.client.get(&api_path!("/bots/{}/intents/{}", self.bot_id, name)).await
     }
 
-    pub async fn create(&self, input: CreateBotInput) -> Result<Bot> {
-        self.client.post("/bots", input).await
+    pub async fn create(&self, intent: Intent) -> Result<Intent> {
+        self.client.post(&api_path!("/bots/{}/intents", self.bot_id), intent).await
     }
 
-    pub async fn update(&self, id: &str, input: UpdateBotInput) -> Result<Bot> {
-        self.client.patch(&format!("/bots/{}", id), input).await
+    /// Replace the phrases and entities of the intent called `name`.
+    pub async fn update(&self, name: &str, intent: Intent) -> Result<Intent> {
+        self.client.put(&api_path!("/bots/{}/intents/{}", self.bot_id, name), intent).await
     }
 
-    pub async fn delete(&self, id: &str) -> Result<()> {
-        self.client.delete(&format!("/bots/{}", id)).await
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        self.client.delete(&api_path!("/bots/{}/intents/{}", self.bot_id, name)).await
+    }
+
+    /// Make `intents` the bot's full intent set in one step: missing ones are
+    /// created, changed ones updated and any others deleted. Suited to keeping
+    /// training data in version control and syncing it on deploy.
+    pub async fn replace(&self, intents: Vec<Intent>) -> Result<Vec<Intent>> {
+        self.client
+            .put(&api_path!("/bots/{}/intents", self.bot_id), ReplaceIntentsInput { intents })
+            .await
     }
 }
 
@@ -415,6 +2438,17 @@ impl AIResource {
     pub fn agents(&self) -> AgentsResource {
         AgentsResource { client: self.client.clone() }
     }
+
+    /// Score a conversation against `rubric`, with quoted evidence for each criterion.
+    pub async fn score_conversation(
+        &self,
+        conversation_id: impl Into<ConversationId>,
+        rubric: ScoringRubric,
+    ) -> Result<ConversationScore> {
+        self.client
+            .post(&api_path!("/ai/conversations/{}/score", conversation_id.into()), rubric)
+            .await
+    }
 }
 
 pub struct CompletionsResource {
@@ -459,23 +2493,20 @@ pub struct AgentsResource {
 
 impl AgentsResource {
     pub async fn list(&self, params: Option<PaginationParams>) -> Result<PaginatedResponse<Agent>> {
-        let path = match params {
-            Some(p) => format!("/ai/agents?{}", serde_urlencoded::to_string(&p).unwrap_or_default()),
-            None => "/ai/agents".to_string(),
-        };
+        let path = with_query("/ai/agents", &params)?;
         self.client.get(&path).await
     }
 
-    pub async fn get(&self, id: &str) -> Result<Agent> {
-        self.client.get(&format!("/ai/agents/{}", id)).await
+    pub async fn get(&self, id: impl Into<AgentId>) -> Result<Agent> {
+        self.client.get(&api_path!("/ai/agents/{}", id.into())).await
     }
 
     pub async fn create(&self, input: CreateAgentInput) -> Result<Agent> {
         self.client.post("/ai/agents", input).await
     }
 
-    pub async fn delete(&self, id: &str) -> Result<()> {
-        self.client.delete(&format!("/ai/agents/{}", id)).await
+    pub async fn delete(&self, id: impl Into<AgentId>) -> Result<()> {
+        self.client.delete(&api_path!("/ai/agents/{}", id.into())).await
     }
 }
 
@@ -485,32 +2516,39 @@ pub struct KnowledgeBasesResource {
 
 impl KnowledgeBasesResource {
     pub async fn list(&self, params: Option<PaginationParams>) -> Result<PaginatedResponse<KnowledgeBase>> {
-        let path = match params {
-            Some(p) => format!("/knowledge-bases?{}", serde_urlencoded::to_string(&p).unwrap_or_default()),
-            None => "/knowledge-bases".to_string(),
-        };
+        let path = with_query("/knowledge-bases", &params)?;
         self.client.get(&path).await
     }
 
-    pub async fn get(&self, id: &str) -> Result<KnowledgeBase> {
-        self.client.get(&format!("/knowledge-bases/{}", id)).await
+    pub async fn get(&self, id: impl Into<KnowledgeBaseId>) -> Result<KnowledgeBase> {
+        self.client.get(&api_path!("/knowledge-bases/{}", id.into())).await
     }
 
     pub async fn create(&self, input: CreateKnowledgeBaseInput) -> Result<KnowledgeBase> {
         self.client.post("/knowledge-bases", input).await
     }
 
-    pub async fn delete(&self, id: &str) -> Result<()> {
-        self.client.delete(&format!("/knowledge-bases/{}", id)).await
+    pub async fn delete(&self, id: impl Into<KnowledgeBaseId>) -> Result<()> {
+        self.client.delete(&api_path!("/knowledge-bases/{}", id.into())).await
+    }
+
+    pub async fn query(&self, id: impl Into<KnowledgeBaseId>, query: &str, top_k: i32) -> Result<QueryResult> {
+        self.query_with(id, QueryKnowledgeBaseInput::new(query).top_k(top_k)).await
     }
 
-    pub async fn query(&self, id: &str, query: &str, top_k: i32) -> Result<QueryResult> {
-        let input = QueryKnowledgeBaseInput::new(query).top_k(top_k);
-        self.client.post(&format!("/knowledge-bases/{}/query", id), input).await
+    /// Query with full options. When `namespaces` is set, chunks reported in
+    /// any other namespace, or in none, are discarded even if the API returns them.
+    pub async fn query_with(&self, id: impl Into<KnowledgeBaseId>, input: QueryKnowledgeBaseInput) -> Result<QueryResult> {
+        let namespaces = input.namespaces.clone();
+        let mut result: QueryResult = self.client.post(&api_path!("/knowledge-bases/{}/query", id.into()), input).await?;
+        if !namespaces.is_empty() {
+            result.retain_namespaces(&namespaces);
+        }
+        Ok(result)
     }
 
-    pub async fn add_document(&self, id: &str, input: AddDocumentInput) -> Result<Document> {
-        self.client.post(&format!("/knowledge-bases/{}/documents", id), input).await
+    pub async fn add_document(&self, id: impl Into<KnowledgeBaseId>, input: AddDocumentInput) -> Result<Document> {
+        self.client.post(&api_path!("/knowledge-bases/{}/documents", id.into()), input).await
     }
 }
 
@@ -520,40 +2558,92 @@ pub struct FlowsResource {
 
 impl FlowsResource {
     pub async fn list(&self, params: Option<PaginationParams>) -> Result<PaginatedResponse<Flow>> {
-        let path = match params {
-            Some(p) => format!("/flows?{}", serde_urlencoded::to_string(&p).unwrap_or_default()),
-            None => "/flows".to_string(),
-        };
+        let path = with_query("/flows", &params)?;
         self.client.get(&path).await
     }
 
-    pub async fn get(&self, id: &str) -> Result<Flow> {
-        self.client.get(&format!("/flows/{}", id)).await
+    pub async fn get(&self, id: impl Into<FlowId>) -> Result<Flow> {
+        self.client.get(&api_path!("/flows/{}", id.into())).await
     }
 
     pub async fn create(&self, input: CreateFlowInput) -> Result<Flow> {
         self.client.post("/flows", input).await
     }
 
-    pub async fn update(&self, id: &str, input: UpdateFlowInput) -> Result<Flow> {
-        self.client.patch(&format!("/flows/{}", id), input).await
+    pub async fn update(&self, id: impl Into<FlowId>, input: UpdateFlowInput) -> Result<Flow> {
+        self.client.patch(&api_path!("/flows/{}", id.into()), input).await
     }
 
-    pub async fn delete(&self, id: &str) -> Result<()> {
-        self.client.delete(&format!("/flows/{}", id)).await
+    pub async fn delete(&self, id: impl Into<FlowId>) -> Result<()> {
+        self.client.delete(&api_path!("/flows/{}", id.into())).await
     }
 
-    pub async fn execute(&self, id: &str, conversation_id: &str) -> Result<FlowExecution> {
+    pub async fn execute(&self, id: impl Into<FlowId>, conversation_id: impl Into<ConversationId>) -> Result<FlowExecution> {
         let input = ExecuteFlowInput::new(conversation_id);
-        self.client.post(&format!("/flows/{}/execute", id), input).await
+        self.execute_with(id, input).await
+    }
+
+    pub async fn execute_with(&self, id: impl Into<FlowId>, input: ExecuteFlowInput) -> Result<FlowExecution> {
+        self.client.post(&api_path!("/flows/{}/execute", id.into()), input).await
+    }
+
+    /// Fetch the flow's variable schema and check `input` against it before executing.
+    pub async fn execute_validated(&self, id: impl Into<FlowId>, input: ExecuteFlowInput) -> Result<FlowExecution> {
+        let id = id.into();
+        let schema = self.variable_schema(&id).await?;
+        input.validate(&schema)?;
+        self.execute_with(id, input).await
+    }
+
+    pub async fn variable_schema(&self, id: impl Into<FlowId>) -> Result<FlowVariableSchema> {
+        Ok(self.get(id).await?.variable_schema())
+    }
+
+    /// Steps of a running execution as each node finishes, ending once the
+    /// execution completes. Dropped connections resume where they left off.
+    ///
+    /// ```rust,no_run
+    /// # async fn run(client: linktor::LinktorClient) -> linktor::Result<()> {
+    /// use futures_util::StreamExt;
+    ///
+    /// let mut steps = client.flows().stream_execution("exec-1");
+    /// while let Some(step) = steps.next().await {
+    ///     let step = step?;
+    ///     println!("{} ({})", step.node_id, step.node_type);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_execution(&self, execution_id: &str) -> BoxStream<'static, Result<FlowExecutionStep>> {
+        let path = api_path!("/flows/executions/{}/events", execution_id);
+        sse::event_stream(self.client.clone(), path, |event| event.event == "end")
+            .try_filter_map(|event| async move {
+                match event.event.as_str() {
+                    "step" => Ok(Some(serde_json::from_str(&event.data)?)),
+                    _ => Ok(None),
+                }
+            })
+            .boxed()
     }
 }
 
+/// Visual Response Engine renderers.
+///
+/// The `render_*` helpers add `<field>_formatado` amounts in the locale of the
+/// client's own tenant (see [`LinktorClient::formatter`]), so their
+/// `tenant_id` should be that tenant. To render for another tenant, build the
+/// client with that tenant's [`Formatter`]. If the tenant's settings can't be
+/// fetched, amounts are formatted with [`Formatter::default`] and the render
+/// still goes ahead.
 pub struct VREResource {
     client: LinktorClient,
 }
 
 impl VREResource {
+    async fn formatter(&self) -> Formatter {
+        self.client.formatter().await.unwrap_or_default()
+    }
+
     /// Render a VRE template to an image.
     /// Returns base64-encoded image data that can be sent to messaging channels.
     pub async fn render(&self, request: VRERenderRequest) -> Result<VRERenderResponse> {
@@ -566,6 +2656,31 @@ impl VREResource {
         self.client.post("/vre/render-and-send", request).await
     }
 
+    /// Render a branded PDF transcript of a conversation, with timestamps and
+    /// media thumbnails.
+    pub async fn render_transcript_pdf(&self, conversation_id: impl Into<ConversationId>, options: TranscriptPdfOptions) -> Result<TranscriptPdf> {
+        let request = TranscriptPdfRequest {
+            conversation_id: conversation_id.into(),
+            options,
+        };
+        let response: TranscriptPdfResponse = self.client.post("/vre/transcripts/pdf", request).await?;
+        match (response.pdf_base64, response.url) {
+            (Some(data), _) => {
+                let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, data.trim()).map_err(|e| {
+                    LinktorError::Serialization(serde::de::Error::custom(format!("invalid transcript PDF data: {}", e)))
+                })?;
+                Ok(TranscriptPdf::Bytes(bytes))
+            }
+            (None, Some(url)) => Ok(TranscriptPdf::Url {
+                url,
+                expires_at: response.expires_at,
+            }),
+            (None, None) => Err(LinktorError::Serialization(serde::de::Error::custom(
+                "transcript response carries neither PDF data nor a URL",
+            ))),
+        }
+    }
+
     /// List available VRE templates with their schemas and example data.
     pub async fn list_templates(&self, tenant_id: Option<&str>) -> Result<VREListTemplatesResponse> {
         let path = match tenant_id {
@@ -578,7 +2693,7 @@ impl VREResource {
     /// Preview a VRE template with sample data.
     pub async fn preview(&self, template_id: &str, data: Option<std::collections::HashMap<String, serde_json::Value>>) -> Result<VREPreviewResponse> {
         let request = VREPreviewRequest { data };
-        self.client.post(&format!("/vre/templates/{}/preview", template_id), request).await
+        self.client.post(&api_path!("/vre/templates/{}/preview", template_id), request).await
     }
 
     /// Render a menu with numbered options.
@@ -605,12 +2720,8 @@ impl VREResource {
         produto: CardProdutoData,
         channel: VREChannelType,
     ) -> Result<VRERenderResponse> {
-        let data = serde_json::to_value(&produto)
-            .map(|v| v.as_object().cloned().unwrap_or_default())
-            .unwrap_or_default()
-            .into_iter()
-            .map(|(k, v)| (k, v))
-            .collect();
+        let formatter = self.formatter().await;
+        let data = formatted_data(&produto, &["preco"], &formatter);
 
         let request = VRERenderRequest::new(tenant_id, "card_produto", data)
             .channel(channel);
@@ -624,12 +2735,8 @@ impl VREResource {
         status: StatusPedidoData,
         channel: VREChannelType,
     ) -> Result<VRERenderResponse> {
-        let data = serde_json::to_value(&status)
-            .map(|v| v.as_object().cloned().unwrap_or_default())
-            .unwrap_or_default()
-            .into_iter()
-            .map(|(k, v)| (k, v))
-            .collect();
+        let formatter = self.formatter().await;
+        let data = formatted_data(&status, &["valor_total"], &formatter);
 
         let request = VRERenderRequest::new(tenant_id, "status_pedido", data)
             .channel(channel);
@@ -644,6 +2751,11 @@ impl VREResource {
         produtos: Vec<ListaProdutoItem>,
         channel: VREChannelType,
     ) -> Result<VRERenderResponse> {
+        let formatter = self.formatter().await;
+        let produtos: Vec<_> = produtos
+            .iter()
+            .map(|produto| formatted_data(produto, &["preco"], &formatter))
+            .collect();
         let mut data = std::collections::HashMap::new();
         data.insert("titulo".to_string(), serde_json::json!(titulo));
         data.insert("produtos".to_string(), serde_json::to_value(&produtos).unwrap_or_default());
@@ -661,8 +2773,14 @@ impl VREResource {
         itens: Vec<ConfirmacaoItem>,
         channel: VREChannelType,
     ) -> Result<VRERenderResponse> {
+        let formatter = self.formatter().await;
+        let itens: Vec<_> = itens
+            .iter()
+            .map(|item| formatted_data(item, &["preco"], &formatter))
+            .collect();
         let mut data = std::collections::HashMap::new();
         data.insert("valor_total".to_string(), serde_json::json!(valor_total));
+        data.insert("valor_total_formatado".to_string(), serde_json::json!(formatter.money(valor_total)));
         data.insert("itens".to_string(), serde_json::to_value(&itens).unwrap_or_default());
 
         let request = VRERenderRequest::new(tenant_id, "confirmacao", data)
@@ -677,15 +2795,455 @@ impl VREResource {
         pix: CobrancaPixData,
         channel: VREChannelType,
     ) -> Result<VRERenderResponse> {
-        let data = serde_json::to_value(&pix)
-            .map(|v| v.as_object().cloned().unwrap_or_default())
-            .unwrap_or_default()
-            .into_iter()
-            .map(|(k, v)| (k, v))
-            .collect();
+        let formatter = self.formatter().await;
+        let data = formatted_data(&pix, &["valor"], &formatter);
 
         let request = VRERenderRequest::new(tenant_id, "cobranca_pix", data)
             .channel(channel);
         self.render(request).await
     }
 }
+
+/// `value` as a JS string literal that is safe inside an inline `<script>`:
+/// JSON-quoted, with `<` and `/` escaped so it can't close the tag.
+fn script_string(value: &str) -> String {
+    serde_json::Value::from(value)
+        .to_string()
+        .replace('<', "\\u003c")
+        .replace('/', "\\/")
+}
+
+/// Template data for `value`, with a `<field>_formatado` string in the
+/// tenant's locale next to each amount in `money_fields`.
+fn formatted_data(
+    value: &impl Serialize,
+    money_fields: &[&str],
+    formatter: &Formatter,
+) -> std::collections::HashMap<String, serde_json::Value> {
+    let mut data: std::collections::HashMap<String, serde_json::Value> = serde_json::to_value(value)
+        .map(|v| v.as_object().cloned().unwrap_or_default())
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    for field in money_fields {
+        if let Some(amount) = data.get(*field).and_then(serde_json::Value::as_f64) {
+            data.insert(format!("{}_formatado", field), serde_json::json!(formatter.money(amount)));
+        }
+    }
+    data
+}
+
+pub struct WebhooksResource {
+    client: LinktorClient,
+}
+
+impl WebhooksResource {
+    /// One page of events that were not delivered to `webhook_id`, oldest first.
+    pub async fn missed_events_page(
+        &self,
+        webhook_id: impl Into<WebhookId>,
+        params: MissedEventsParams,
+    ) -> Result<MissedEventsPage> {
+        let path = with_query(&api_path!("/webhooks/{}/missed-events", webhook_id.into()), &params)?;
+        self.client.get(&path).await
+    }
+
+    /// Every event not delivered to `webhook_id` since `since`, oldest first,
+    /// fetching pages as the stream is consumed.
+    pub fn missed_events(
+        &self,
+        webhook_id: impl Into<WebhookId>,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> BoxStream<'static, Result<MissedEvent>> {
+        self.missed_events_with(webhook_id, MissedEventsParams::new().since(since))
+    }
+
+    /// Like [`missed_events`](Self::missed_events), starting from `params`;
+    /// pass the [`MissedEvent::cursor`] of the last handled event to resume a
+    /// catch-up that was interrupted.
+    ///
+    /// ```rust,no_run
+    /// # use futures_util::TryStreamExt;
+    /// # async fn run(client: linktor::LinktorClient, saved_cursor: String) -> linktor::Result<()> {
+    /// use linktor::types::MissedEventsParams;
+    ///
+    /// let mut events = client.webhooks().missed_events_with("wh-1", MissedEventsParams::new().cursor(saved_cursor));
+    /// while let Some(missed) = events.try_next().await? {
+    ///     println!("{:?} {}", missed.event.get_event_type(), missed.event.id);
+    ///     // handle, then persist missed.cursor
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn missed_events_with(
+        &self,
+        webhook_id: impl Into<WebhookId>,
+        params: MissedEventsParams,
+    ) -> BoxStream<'static, Result<MissedEvent>> {
+        let client = self.client.clone();
+        let webhook_id = webhook_id.into();
+        futures_util::stream::try_unfold(Some(params), move |params| {
+            next_missed_events(client.clone(), webhook_id.clone(), params)
+        })
+        .map_ok(|events| futures_util::stream::iter(events.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+}
+
+/// Fetch the page at `params`, returning its events and where the next page
+/// starts; `None` once the last page has been read.
+async fn next_missed_events(
+    client: LinktorClient,
+    webhook_id: WebhookId,
+    params: Option<MissedEventsParams>,
+) -> Result<Option<(Vec<MissedEvent>, Option<MissedEventsParams>)>> {
+    let Some(params) = params else {
+        return Ok(None);
+    };
+    let page = client.webhooks().missed_events_page(webhook_id, params.clone()).await?;
+    let next = match page.next_cursor {
+        Some(cursor) if page.has_more => Some(params.cursor(cursor)),
+        _ => None,
+    };
+    Ok(Some((page.data, next)))
+}
+
+pub struct RetentionResource {
+    client: LinktorClient,
+}
+
+impl RetentionResource {
+    pub async fn list(&self, params: Option<PaginationParams>) -> Result<PaginatedResponse<RetentionPolicy>> {
+        let path = with_query("/retention/policies", &params)?;
+        self.client.get(&path).await
+    }
+
+    pub async fn get(&self, id: &str) -> Result<RetentionPolicy> {
+        self.client.get(&api_path!("/retention/policies/{}", id)).await
+    }
+
+    pub async fn create(&self, input: CreateRetentionPolicyInput) -> Result<RetentionPolicy> {
+        self.client.post("/retention/policies", input).await
+    }
+
+    pub async fn update(&self, id: &str, input: UpdateRetentionPolicyInput) -> Result<RetentionPolicy> {
+        self.client.patch(&api_path!("/retention/policies/{}", id), input).await
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        self.client.delete(&api_path!("/retention/policies/{}", id)).await
+    }
+
+    /// List purge batches scheduled by the retention policies, soonest first.
+    pub async fn upcoming_purges(&self, params: Option<ListPurgeBatchesParams>) -> Result<PaginatedResponse<PurgeBatch>> {
+        let path = with_query("/retention/purges", &params)?;
+        self.client.get(&path).await
+    }
+}
+
+pub struct SmsResource {
+    client: LinktorClient,
+}
+
+impl SmsResource {
+    /// Estimate what sending `text` through an SMS channel to `recipients` will cost.
+    /// Use [`crate::sms::segment_count`] for an offline segment count.
+    pub async fn estimate_cost(&self, channel_id: impl Into<ChannelId>, text: &str, recipients: Vec<String>) -> Result<SmsCostEstimate> {
+        let input = EstimateSmsCostInput::new(channel_id, text, recipients);
+        self.client.post("/sms/estimate", input).await
+    }
+}
+
+pub struct BatchBuilder {
+    client: LinktorClient,
+    /// Unique per builder, so a handle cannot read another batch's results.
+    batch_id: u64,
+    operations: Vec<BatchOperation>,
+}
+
+/// Typed reference to an operation queued on a [`BatchBuilder`]; it only
+/// resolves against the results of that batch.
+#[derive(Debug)]
+pub struct BatchHandle<T> {
+    batch_id: u64,
+    id: String,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl BatchBuilder {
+    pub fn get_conversation(&mut self, id: impl Into<ConversationId>) -> BatchHandle<Conversation> {
+        self.push(reqwest::Method::GET, api_path!("/conversations/{}", id.into()), None)
+    }
+
+    pub fn get_contact(&mut self, id: impl Into<ContactId>) -> BatchHandle<Contact> {
+        self.push(reqwest::Method::GET, api_path!("/contacts/{}", id.into()), None)
+    }
+
+    pub fn send_message(&mut self, conversation_id: impl Into<ConversationId>, input: SendMessageInput) -> Result<BatchHandle<Message>> {
+        let body = serde_json::to_value(input)?;
+        Ok(self.push(reqwest::Method::POST, api_path!("/conversations/{}/messages", conversation_id.into()), Some(body)))
+    }
+
+    /// Queue an arbitrary GET whose result is parsed as `T`.
+    pub fn get<T: DeserializeOwned>(&mut self, path: impl Into<String>) -> BatchHandle<T> {
+        self.push(reqwest::Method::GET, path.into(), None)
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub async fn send(self) -> Result<BatchResults> {
+        let writes: Vec<String> = self
+            .operations
+            .iter()
+            .filter(|op| op.method != "GET")
+            .map(|op| op.path.clone())
+            .collect();
+        let request = BatchRequest {
+            operations: self.operations,
+        };
+        let response: BatchResponse = self.client.post("/batch", request).await?;
+        if let Some(ref cache) = self.client.cache {
+            writes.iter().for_each(|path| cache.invalidate(path));
+        }
+        Ok(BatchResults {
+            batch_id: self.batch_id,
+            results: response.results,
+        })
+    }
+
+    fn push<T>(&mut self, method: reqwest::Method, path: String, body: Option<serde_json::Value>) -> BatchHandle<T> {
+        let id = self.operations.len().to_string();
+        self.operations.push(BatchOperation {
+            id: id.clone(),
+            method: method.to_string(),
+            path,
+            body,
+        });
+        BatchHandle {
+            batch_id: self.batch_id,
+            id,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchResults {
+    batch_id: u64,
+    results: Vec<BatchResult>,
+}
+
+impl BatchResults {
+    /// Typed result of a single operation, or the error the API returned for it.
+    /// A handle from a different batch is a configuration error.
+    pub fn get<T: DeserializeOwned>(&self, handle: &BatchHandle<T>) -> Result<T> {
+        if handle.batch_id != self.batch_id {
+            return Err(LinktorError::Configuration {
+                message: format!("Batch handle {} belongs to a different batch", handle.id),
+            });
+        }
+        let result = self
+            .results
+            .iter()
+            .find(|r| r.id == handle.id)
+            .ok_or_else(|| LinktorError::Unknown {
+                message: format!("Batch response is missing operation {}", handle.id),
+                status_code: None,
+                code: None,
+                details: None,
+            })?;
+        let body = result.body.clone().unwrap_or(serde_json::Value::Null);
+
+        let status = StatusCode::from_u16(result.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        if !status.is_success() {
+            return Err(LinktorError::from_response(status, &body.to_string(), None));
+        }
+
+        parse_body(&body.to_string())
+    }
+
+    pub fn raw(&self) -> &[BatchResult] {
+        &self.results
+    }
+}
+
+pub struct MediaResource {
+    client: LinktorClient,
+}
+
+impl MediaResource {
+    /// Upload a file and get back its hosted URL, e.g. to send an image held
+    /// in memory. `mime_type` is the file's type such as `image/png`.
+    pub async fn upload(&self, data: impl Into<Vec<u8>>, mime_type: &str, filename: &str) -> Result<UploadedMedia> {
+        let data = data.into();
+        let file = MultipartFile {
+            data: &data,
+            content_type: mime_type,
+            filename,
+        };
+        self.client.post_file("/media", file).await
+    }
+
+    /// Like [`upload`](Self::upload), reading the file from `reader` first.
+    pub async fn upload_reader<R>(&self, mut reader: R, mime_type: &str, filename: &str) -> Result<UploadedMedia>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        let mut data = Vec::new();
+        tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut data).await?;
+        self.upload(data, mime_type, filename).await
+    }
+
+    /// Download a file into memory, e.g. the `url` of a message's
+    /// [`MediaContent`]. See [`download_stream`](Self::download_stream) for
+    /// which URLs get the client's credentials.
+    pub async fn download(&self, url: &str) -> Result<bytes::Bytes> {
+        Ok(self.client.open_download(url).await?.bytes().await?)
+    }
+
+    /// Download a file as an [`AsyncRead`](tokio::io::AsyncRead) stream, for
+    /// attachments too large to hold in memory. `url` is either an API path
+    /// such as `/media/media-1` or an absolute URL; credentials are only sent
+    /// to the client's own API hosts.
+    pub async fn download_stream(&self, url: &str) -> Result<MediaStream> {
+        Ok(MediaStream::new(self.client.open_download(url).await?))
+    }
+}
+
+pub struct BroadcastsResource {
+    client: LinktorClient,
+}
+
+impl BroadcastsResource {
+    pub async fn list(&self, params: Option<PaginationParams>) -> Result<PaginatedResponse<Broadcast>> {
+        let path = with_query("/broadcasts", &params)?;
+        self.client.get(&path).await
+    }
+
+    pub async fn get(&self, id: impl Into<BroadcastId>) -> Result<Broadcast> {
+        self.client.get(&api_path!("/broadcasts/{}", id.into())).await
+    }
+
+    /// Send a message to an audience over one channel, now or at `send_at`.
+    /// Progress is reported in [`Broadcast::stats`].
+    pub async fn create(&self, input: CreateBroadcastInput) -> Result<Broadcast> {
+        self.client.post("/broadcasts", input).await
+    }
+
+    /// Stop a scheduled or running broadcast; messages already sent stay sent.
+    pub async fn cancel(&self, id: impl Into<BroadcastId>) -> Result<Broadcast> {
+        self.client.post(&api_path!("/broadcasts/{}/cancel", id.into()), serde_json::json!({})).await
+    }
+
+    /// Delivery status per contact, e.g. to retry the failed ones.
+    pub async fn recipients(
+        &self,
+        id: impl Into<BroadcastId>,
+        params: Option<ListBroadcastRecipientsParams>,
+    ) -> Result<PaginatedResponse<BroadcastRecipient>> {
+        let path = with_query(&api_path!("/broadcasts/{}/recipients", id.into()), &params)?;
+        self.client.get(&path).await
+    }
+}
+
+pub struct CampaignsResource {
+    client: LinktorClient,
+}
+
+impl CampaignsResource {
+    /// Render the campaign's message for one contact without sending it, so
+    /// merges from custom fields can be checked before launch.
+    pub async fn preview_for_contact(
+        &self,
+        campaign_id: impl Into<CampaignId>,
+        contact_id: impl Into<ContactId>,
+    ) -> Result<CampaignPreview> {
+        self.client
+            .get(&api_path!("/campaigns/{}/contacts/{}/preview", campaign_id.into(), contact_id.into()))
+            .await
+    }
+}
+
+/// A file being downloaded. Read it with `tokio::io::AsyncReadExt` or copy it
+/// with `tokio::io::copy`.
+pub struct MediaStream {
+    /// From the `Content-Type` header.
+    pub content_type: Option<String>,
+    /// From the `Content-Length` header, when the server sent one.
+    pub content_length: Option<u64>,
+    reader: std::pin::Pin<Box<dyn tokio::io::AsyncRead + Send>>,
+}
+
+impl MediaStream {
+    fn new(response: reqwest::Response) -> Self {
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let content_length = response.content_length();
+        let chunks = futures_util::stream::try_unfold(response, |mut response| async move {
+            let chunk = response.chunk().await.map_err(std::io::Error::other)?;
+            Ok::<_, std::io::Error>(chunk.map(|chunk| (chunk, response)))
+        });
+        Self {
+            content_type,
+            content_length,
+            reader: Box::pin(tokio_util::io::StreamReader::new(chunks)),
+        }
+    }
+}
+
+impl std::fmt::Debug for MediaStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MediaStream")
+            .field("content_type", &self.content_type)
+            .field("content_length", &self.content_length)
+            .finish_non_exhaustive()
+    }
+}
+
+impl tokio::io::AsyncRead for MediaStream {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        self.reader.as_mut().poll_read(cx, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |name| vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+    }
+
+    #[test]
+    fn test_builder_from_env() {
+        let builder = LinktorClientBuilder::from_lookup(lookup(&[
+            ("LINKTOR_API_KEY", "key"),
+            ("LINKTOR_BASE_URL", "https://eu.api.linktor.io"),
+            ("LINKTOR_TIMEOUT", "10"),
+        ]))
+        .unwrap();
+        assert_eq!(builder.api_key.as_deref(), Some("key"));
+        assert_eq!(builder.base_url.as_deref(), Some("https://eu.api.linktor.io"));
+        assert_eq!(builder.timeout_secs, Some(10));
+
+        let missing = LinktorClientBuilder::from_lookup(lookup(&[])).err().unwrap();
+        assert!(missing.to_string().contains("LINKTOR_API_KEY"));
+
+        let invalid = LinktorClientBuilder::from_lookup(lookup(&[("LINKTOR_API_KEY", "key"), ("LINKTOR_TIMEOUT", "soon")]));
+        assert!(matches!(invalid, Err(LinktorError::Configuration { .. })));
+    }
+}
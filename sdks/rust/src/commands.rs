@@ -0,0 +1,309 @@
+//! Slash-command parsing and dispatch for utility bots.
+//!
+//! ```rust
+//! use linktor::commands::{CommandRegistry, Rest};
+//!
+//! # async fn run() {
+//! let mut commands: CommandRegistry<(), String> = CommandRegistry::new();
+//! commands.command("status", "Show an order's status", |_, (order_id,): (u64,)| async move {
+//!     format!("Order {} is on its way", order_id)
+//! });
+//! commands.command("echo", "Repeat a message", |_, (Rest(text),): (Rest,)| async move { text });
+//!
+//! let reply = commands.dispatch((), "/status 1234").await.unwrap();
+//! assert_eq!(reply.as_deref(), Some("Order 1234 is on its way"));
+//! # }
+//! ```
+//!
+//! Argument types implement [`CommandArg`]; tuples of them implement
+//! [`FromCommandArgs`], so handlers receive typed arguments without
+//! hand-written parsing.
+
+use crate::types::conversation::Message;
+use futures_util::future::BoxFuture;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::slice::Iter;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum CommandError {
+    #[error("Unknown command '{name}'")]
+    UnknownCommand { name: String },
+
+    #[error("Invalid arguments for '{command}': {reason}")]
+    InvalidArguments { command: String, reason: String },
+}
+
+/// A single command argument taken from the token stream.
+pub trait CommandArg: Sized {
+    fn take(tokens: &mut Iter<'_, String>) -> Result<Self, String>;
+}
+
+macro_rules! from_str_arg {
+    ($($ty:ty),*) => {$(
+        impl CommandArg for $ty {
+            fn take(tokens: &mut Iter<'_, String>) -> Result<Self, String> {
+                let token = tokens.next().ok_or_else(|| format!("missing {}", stringify!($ty)))?;
+                token
+                    .parse()
+                    .map_err(|_| format!("'{}' is not a valid {}", token, stringify!($ty)))
+            }
+        }
+    )*};
+}
+
+from_str_arg!(String, bool, char, i32, i64, u32, u64, usize, f64);
+
+/// Optional trailing argument.
+impl<T: CommandArg> CommandArg for Option<T> {
+    fn take(tokens: &mut Iter<'_, String>) -> Result<Self, String> {
+        if tokens.as_slice().is_empty() {
+            Ok(None)
+        } else {
+            T::take(tokens).map(Some)
+        }
+    }
+}
+
+/// Every remaining argument.
+impl<T: CommandArg> CommandArg for Vec<T> {
+    fn take(tokens: &mut Iter<'_, String>) -> Result<Self, String> {
+        let mut values = Vec::new();
+        while !tokens.as_slice().is_empty() {
+            values.push(T::take(tokens)?);
+        }
+        Ok(values)
+    }
+}
+
+/// The remaining arguments joined by spaces, e.g. free text after `/note`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rest(pub String);
+
+impl CommandArg for Rest {
+    fn take(tokens: &mut Iter<'_, String>) -> Result<Self, String> {
+        Ok(Rest(tokens.map(String::as_str).collect::<Vec<_>>().join(" ")))
+    }
+}
+
+/// The full argument list of a command.
+pub trait FromCommandArgs: Sized {
+    fn from_args(args: &[String]) -> Result<Self, String>;
+}
+
+impl FromCommandArgs for () {
+    fn from_args(args: &[String]) -> Result<Self, String> {
+        match args.first() {
+            Some(extra) => Err(format!("unexpected argument '{}'", extra)),
+            None => Ok(()),
+        }
+    }
+}
+
+macro_rules! tuple_args {
+    ($($name:ident),+) => {
+        impl<$($name: CommandArg),+> FromCommandArgs for ($($name,)+) {
+            fn from_args(args: &[String]) -> Result<Self, String> {
+                let mut tokens = args.iter();
+                let value = ($($name::take(&mut tokens)?,)+);
+                match tokens.next() {
+                    Some(extra) => Err(format!("unexpected argument '{}'", extra)),
+                    None => Ok(value),
+                }
+            }
+        }
+    };
+}
+
+tuple_args!(A);
+tuple_args!(A, B);
+tuple_args!(A, B, C);
+tuple_args!(A, B, C, D);
+tuple_args!(A, B, C, D, E);
+
+/// A command invocation split into name and arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedCommand {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+type Handler<Ctx, Out> = Box<dyn Fn(Ctx, &[String]) -> Result<BoxFuture<'static, Out>, String> + Send + Sync>;
+
+struct Command<Ctx, Out> {
+    description: String,
+    handler: Handler<Ctx, Out>,
+}
+
+/// Registered commands with their handlers. `Ctx` is passed to every handler,
+/// typically the client or the inbound message; `Out` is the handler result.
+pub struct CommandRegistry<Ctx, Out = ()> {
+    prefix: String,
+    commands: BTreeMap<String, Command<Ctx, Out>>,
+}
+
+impl<Ctx, Out> Default for CommandRegistry<Ctx, Out> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Ctx, Out> CommandRegistry<Ctx, Out> {
+    pub fn new() -> Self {
+        Self {
+            prefix: "/".to_string(),
+            commands: BTreeMap::new(),
+        }
+    }
+
+    /// Change the command prefix (default `/`).
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Register a command. Names are matched case-insensitively.
+    pub fn command<A, F, Fut>(&mut self, name: &str, description: &str, handler: F) -> &mut Self
+    where
+        A: FromCommandArgs,
+        F: Fn(Ctx, A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Out> + Send + 'static,
+    {
+        let handler: Handler<Ctx, Out> = Box::new(move |ctx, args| {
+            let args = A::from_args(args)?;
+            Ok(Box::pin(handler(ctx, args)))
+        });
+        self.commands.insert(
+            name.to_lowercase(),
+            Command {
+                description: description.to_string(),
+                handler,
+            },
+        );
+        self
+    }
+
+    /// Split `text` into a command, or `None` if it does not start with the prefix.
+    /// Double quotes group words into one argument and `/cmd@bot` is treated as `/cmd`.
+    pub fn parse(&self, text: &str) -> Option<ParsedCommand> {
+        let body = text.trim().strip_prefix(self.prefix.as_str())?;
+        let mut tokens = tokenize(body).into_iter();
+        let name = tokens.next()?;
+        let name = name.split('@').next().unwrap_or_default().to_lowercase();
+        if name.is_empty() {
+            return None;
+        }
+        Some(ParsedCommand {
+            name,
+            args: tokens.collect(),
+        })
+    }
+
+    /// Run the handler for `text`. Returns `Ok(None)` when the text is not a command.
+    pub async fn dispatch(&self, ctx: Ctx, text: &str) -> Result<Option<Out>, CommandError> {
+        let parsed = match self.parse(text) {
+            Some(parsed) => parsed,
+            None => return Ok(None),
+        };
+        let command = self
+            .commands
+            .get(&parsed.name)
+            .ok_or_else(|| CommandError::UnknownCommand {
+                name: parsed.name.clone(),
+            })?;
+        let fut = (command.handler)(ctx, &parsed.args).map_err(|reason| CommandError::InvalidArguments {
+            command: parsed.name.clone(),
+            reason,
+        })?;
+        Ok(Some(fut.await))
+    }
+
+    /// Dispatch the text of an inbound message; non-text messages are ignored.
+    pub async fn dispatch_message(&self, ctx: Ctx, message: &Message) -> Result<Option<Out>, CommandError> {
+        match message.text {
+            Some(ref text) => self.dispatch(ctx, text).await,
+            None => Ok(None),
+        }
+    }
+
+    /// One line per command, e.g. `/status - Show an order's status`.
+    pub fn help(&self) -> String {
+        self.commands
+            .iter()
+            .map(|(name, command)| format!("{}{} - {}", self.prefix, name, command.description))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut has_token = false;
+
+    for c in text.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                has_token = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if has_token {
+                    tokens.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> CommandRegistry<(), String> {
+        let mut commands = CommandRegistry::new();
+        commands.command("status", "Show an order's status", |_, (id, verbose): (u64, Option<bool>)| async move {
+            format!("{}:{}", id, verbose.unwrap_or(false))
+        });
+        commands.command("note", "Add a note", |_, (Rest(text),): (Rest,)| async move { text });
+        commands
+    }
+
+    #[test]
+    fn test_parse() {
+        let commands = registry();
+        let parsed = commands.parse("/Status@linktor_bot 42 \"two words\"").unwrap();
+        assert_eq!(parsed.name, "status");
+        assert_eq!(parsed.args, vec!["42", "two words"]);
+        assert!(commands.parse("hello").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch() {
+        let commands = registry();
+        assert_eq!(commands.dispatch((), "/status 42").await.unwrap().as_deref(), Some("42:false"));
+        assert_eq!(commands.dispatch((), "/status 42 true").await.unwrap().as_deref(), Some("42:true"));
+        assert_eq!(commands.dispatch((), "/note call back  later").await.unwrap().as_deref(), Some("call back later"));
+        assert_eq!(commands.dispatch((), "just chatting").await.unwrap(), None);
+
+        assert!(matches!(
+            commands.dispatch((), "/status abc").await,
+            Err(CommandError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            commands.dispatch((), "/refund 1").await,
+            Err(CommandError::UnknownCommand { .. })
+        ));
+    }
+}
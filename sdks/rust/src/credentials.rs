@@ -0,0 +1,395 @@
+//! Named credential profiles encrypted at rest, so command-line users do not
+//! paste API keys into their shell history.
+//!
+//! Each profile is a file under `<dir>/profiles/`, encrypted with AES-256-GCM
+//! under a key derived from a passphrase with PBKDF2-HMAC-SHA256. Profiles
+//! holding a login session are kept up to date as tokens are refreshed. A
+//! store derives each key once and reuses it, so saving refreshed tokens does
+//! not repeat the 600,000 PBKDF2 rounds.
+//!
+//! The files are not in the `age` format, and nothing is kept in the OS
+//! keychain. To keep sessions in the keychain, implement
+//! [`TokenStore`](crate::token_store::TokenStore) over it and pass it to
+//! [`LinktorClientBuilder::token_store`](crate::LinktorClientBuilder::token_store).
+//!
+//! ```rust,no_run
+//! use linktor::credentials::{CredentialStore, StoredProfile};
+//! use linktor::LinktorClientBuilder;
+//!
+//! # fn run() -> linktor::Result<()> {
+//! // Once, e.g. from a `login` subcommand:
+//! let store = CredentialStore::from_env()?;
+//! store.save("work", &StoredProfile::api_key("lk_live_...").base_url("https://api.linktor.io"))?;
+//!
+//! // On every run; the passphrase comes from LINKTOR_CREDENTIALS_PASSPHRASE.
+//! let client = LinktorClientBuilder::from_stored_profile("work")?.build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{LinktorError, Result};
+use crate::token_store::{write_private, StoredTokens, TokenStore};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+pub const PASSPHRASE_ENV: &str = "LINKTOR_CREDENTIALS_PASSPHRASE";
+pub const CONFIG_DIR_ENV: &str = "LINKTOR_CONFIG_DIR";
+
+const DEFAULT_ITERATIONS: u32 = 600_000;
+const FORMAT_VERSION: u32 = 1;
+const KDF: &str = "pbkdf2-sha256";
+
+/// What a profile stores: an API key, a login session, or both.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl StoredProfile {
+    pub fn api_key(key: impl Into<String>) -> Self {
+        Self {
+            api_key: Some(key.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn session(tokens: StoredTokens) -> Self {
+        let mut profile = Self::default();
+        profile.set_tokens(Some(tokens));
+        profile
+    }
+
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// The login session, if the profile has a complete one.
+    pub fn tokens(&self) -> Option<StoredTokens> {
+        Some(StoredTokens {
+            access_token: self.access_token.clone()?,
+            refresh_token: self.refresh_token.clone()?,
+            expires_at: self.expires_at?,
+        })
+    }
+
+    fn set_tokens(&mut self, tokens: Option<StoredTokens>) {
+        self.access_token = tokens.as_ref().map(|t| t.access_token.clone());
+        self.refresh_token = tokens.as_ref().map(|t| t.refresh_token.clone());
+        self.expires_at = tokens.map(|t| t.expires_at);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+    version: u32,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// A key derived from the store's passphrase.
+struct DerivedKey {
+    salt: Vec<u8>,
+    iterations: u32,
+    key: [u8; 32],
+}
+
+/// Encrypted profiles in a directory, all sealed with the same passphrase.
+#[derive(Clone)]
+pub struct CredentialStore {
+    dir: PathBuf,
+    passphrase: String,
+    iterations: u32,
+    /// Keys derived so far, shared by clones. Saving reuses one with the
+    /// store's iteration count and its salt; every save has a fresh nonce.
+    keys: Arc<Mutex<Vec<DerivedKey>>>,
+}
+
+impl fmt::Debug for CredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialStore")
+            .field("dir", &self.dir)
+            .field("passphrase", &"<redacted>")
+            .finish()
+    }
+}
+
+impl CredentialStore {
+    pub fn new(dir: impl Into<PathBuf>, passphrase: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            passphrase: passphrase.into(),
+            iterations: DEFAULT_ITERATIONS,
+            keys: Arc::default(),
+        }
+    }
+
+    /// The store in the default location, unlocked with `LINKTOR_CREDENTIALS_PASSPHRASE`.
+    /// The directory is `LINKTOR_CONFIG_DIR` if set, otherwise `linktor` under
+    /// the user's configuration directory.
+    pub fn from_env() -> Result<Self> {
+        let passphrase = std::env::var(PASSPHRASE_ENV)
+            .ok()
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| LinktorError::Configuration {
+                message: format!("{} must be set to unlock stored credentials", PASSPHRASE_ENV),
+            })?;
+        Ok(Self::new(default_dir()?, passphrase))
+    }
+
+    /// PBKDF2 rounds used when saving (default 600,000). Existing profiles keep
+    /// the count they were saved with.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations.max(1);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn save(&self, name: &str, profile: &StoredProfile) -> Result<()> {
+        let path = self.profile_path(name)?;
+        let rng = SystemRandom::new();
+        let no_random = || credentials_error("no secure random source available".to_string());
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill(&mut nonce).map_err(|_| no_random())?;
+        let cached = self.keys.lock().unwrap().iter().find(|k| k.iterations == self.iterations).map(|k| k.salt.clone());
+        let salt = match cached {
+            Some(salt) => salt,
+            None => {
+                let mut salt = vec![0u8; 16];
+                rng.fill(&mut salt).map_err(|_| no_random())?;
+                salt
+            }
+        };
+
+        let mut data = serde_json::to_vec(profile)?;
+        self.key(&salt, self.iterations)
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(name.as_bytes()), &mut data)
+            .map_err(|_| credentials_error(format!("failed to encrypt profile {}", name)))?;
+
+        let envelope = Envelope {
+            version: FORMAT_VERSION,
+            kdf: KDF.to_string(),
+            iterations: self.iterations,
+            salt: STANDARD.encode(salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(data),
+        };
+        write_private(&path, &serde_json::to_vec_pretty(&envelope)?)
+            .map_err(|e| credentials_error(format!("failed to write {}: {}", path.display(), e)))
+    }
+
+    /// The profile called `name`, or `None` if it was never saved. A wrong
+    /// passphrase or a tampered file is an error.
+    pub fn load(&self, name: &str) -> Result<Option<StoredProfile>> {
+        let path = self.profile_path(name)?;
+        let contents = match std::fs::read(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(credentials_error(format!("failed to read {}: {}", path.display(), e))),
+        };
+
+        let corrupt = || credentials_error(format!("{} is not a valid credentials file", path.display()));
+        let envelope: Envelope = serde_json::from_slice(&contents).map_err(|_| corrupt())?;
+        if envelope.version != FORMAT_VERSION || envelope.kdf != KDF || envelope.iterations == 0 {
+            return Err(corrupt());
+        }
+        let salt = STANDARD.decode(&envelope.salt).map_err(|_| corrupt())?;
+        let nonce = STANDARD.decode(&envelope.nonce).map_err(|_| corrupt())?;
+        let nonce = Nonce::try_assume_unique_for_key(&nonce).map_err(|_| corrupt())?;
+        let mut data = STANDARD.decode(&envelope.ciphertext).map_err(|_| corrupt())?;
+
+        let plaintext = self
+            .key(&salt, envelope.iterations)
+            .open_in_place(nonce, Aad::from(name.as_bytes()), &mut data)
+            .map_err(|_| credentials_error(format!("could not decrypt profile {}; wrong passphrase?", name)))?;
+        Ok(Some(serde_json::from_slice(plaintext)?))
+    }
+
+    pub fn delete(&self, name: &str) -> Result<()> {
+        let path = self.profile_path(name)?;
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(credentials_error(format!("failed to remove {}: {}", path.display(), e)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Names of the saved profiles, sorted.
+    pub fn profiles(&self) -> Result<Vec<String>> {
+        let entries = match std::fs::read_dir(self.dir.join("profiles")) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(credentials_error(format!("failed to list profiles: {}", e))),
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_str()?.strip_suffix(".json").map(String::from))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// A [`TokenStore`] backed by the session in profile `name`. Saving
+    /// tokens keeps the profile's API key and base URL.
+    pub fn token_store(&self, name: impl Into<String>) -> ProfileTokenStore {
+        ProfileTokenStore {
+            store: self.clone(),
+            name: name.into(),
+        }
+    }
+
+    fn profile_path(&self, name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            && !name.starts_with('.');
+        if !valid {
+            return Err(LinktorError::Configuration {
+                message: format!(
+                    "Invalid profile name {:?}: use letters, digits, '-', '_' and '.'",
+                    name
+                ),
+            });
+        }
+        Ok(self.dir.join("profiles").join(format!("{}.json", name)))
+    }
+
+    fn key(&self, salt: &[u8], iterations: u32) -> LessSafeKey {
+        let cached = self
+            .keys
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|k| k.salt == salt && k.iterations == iterations)
+            .map(|k| k.key);
+        let key = cached.unwrap_or_else(|| {
+            let mut key = [0u8; 32];
+            let rounds = NonZeroU32::new(iterations).unwrap_or(NonZeroU32::MIN);
+            pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, rounds, salt, self.passphrase.as_bytes(), &mut key);
+            self.keys.lock().unwrap().push(DerivedKey {
+                salt: salt.to_vec(),
+                iterations,
+                key,
+            });
+            key
+        });
+        LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &key).expect("AES-256 key is 32 bytes"))
+    }
+}
+
+/// The login session of one profile; see [`CredentialStore::token_store`].
+#[derive(Debug, Clone)]
+pub struct ProfileTokenStore {
+    store: CredentialStore,
+    name: String,
+}
+
+impl TokenStore for ProfileTokenStore {
+    fn load(&self) -> Result<Option<StoredTokens>> {
+        Ok(self.store.load(&self.name)?.and_then(|profile| profile.tokens()))
+    }
+
+    fn save(&self, tokens: &StoredTokens) -> Result<()> {
+        let mut profile = self.store.load(&self.name)?.unwrap_or_default();
+        profile.set_tokens(Some(tokens.clone()));
+        self.store.save(&self.name, &profile)
+    }
+
+    fn clear(&self) -> Result<()> {
+        match self.store.load(&self.name)? {
+            Some(mut profile) => {
+                profile.set_tokens(None);
+                self.store.save(&self.name, &profile)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+fn default_dir() -> Result<PathBuf> {
+    let var = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    if let Some(dir) = var(CONFIG_DIR_ENV) {
+        return Ok(dir);
+    }
+    let config = if cfg!(windows) {
+        var("APPDATA")
+    } else {
+        var("XDG_CONFIG_HOME").or_else(|| var("HOME").map(|home| home.join(".config")))
+    };
+    config
+        .map(|dir| dir.join("linktor"))
+        .ok_or_else(|| LinktorError::Configuration {
+            message: format!("Cannot locate a configuration directory; set {}", CONFIG_DIR_ENV),
+        })
+}
+
+fn credentials_error(message: String) -> LinktorError {
+    LinktorError::Credentials { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_round_trip_encrypted() {
+        let dir = std::env::temp_dir().join(format!("linktor-credentials-{}", std::process::id()));
+        let store = CredentialStore::new(&dir, "correct horse").iterations(1_000);
+
+        let profile = StoredProfile::api_key("lk_secret").base_url("https://eu.linktor.io");
+        store.save("work", &profile).unwrap();
+        let raw = std::fs::read_to_string(dir.join("profiles/work.json")).unwrap();
+        assert!(!raw.contains("lk_secret"));
+        assert_eq!(store.load("work").unwrap(), Some(profile));
+        assert_eq!(store.load("home").unwrap(), None);
+        assert!(CredentialStore::new(&dir, "wrong").load("work").is_err());
+        assert!(store.load("../work").is_err());
+
+        // Sessions saved through the token store keep the API key.
+        let tokens = store.token_store("work");
+        tokens.save(&StoredTokens::new("access", "refresh", 3600)).unwrap();
+        let updated = store.load("work").unwrap().unwrap();
+        assert_eq!(updated.api_key.as_deref(), Some("lk_secret"));
+        assert_eq!(tokens.load().unwrap().unwrap().refresh_token, "refresh");
+        tokens.clear().unwrap();
+        assert_eq!(tokens.load().unwrap(), None);
+
+        // One key serves every save and load of this store.
+        assert_eq!(store.keys.lock().unwrap().len(), 1);
+        let fresh = CredentialStore::new(&dir, "correct horse").iterations(1_000);
+        assert_eq!(fresh.load("work").unwrap(), store.load("work").unwrap());
+
+        assert_eq!(store.profiles().unwrap(), vec!["work"]);
+        store.delete("work").unwrap();
+        assert!(store.profiles().unwrap().is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,24 +1,176 @@
+use crate::types::common::{ApiError, ApiResponse};
+use std::collections::HashMap;
+use std::fmt;
 use thiserror::Error;
 
+/// Machine-readable error code returned by the API in `ApiError.code`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    Internal,
+    Validation,
+    NotFound,
+    Unauthorized,
+    Forbidden,
+    Conflict,
+    BadRequest,
+    InvalidCredentials,
+    TokenExpired,
+    TokenInvalid,
+    /// Login needs a second factor; the details carry the MFA challenge.
+    MfaRequired,
+    TenantNotFound,
+    UserNotFound,
+    ChannelNotFound,
+    ContactNotFound,
+    ConversationNotFound,
+    MessageNotFound,
+    ConversationClosed,
+    ChannelDisconnected,
+    ChannelError,
+    RateLimited,
+    QuotaExceeded,
+    Timeout,
+    /// A code this SDK version does not know about.
+    Other(String),
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ErrorCode::Internal => "INTERNAL_ERROR",
+            ErrorCode::Validation => "VALIDATION_ERROR",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::InvalidCredentials => "INVALID_CREDENTIALS",
+            ErrorCode::TokenExpired => "TOKEN_EXPIRED",
+            ErrorCode::TokenInvalid => "TOKEN_INVALID",
+            ErrorCode::MfaRequired => "MFA_REQUIRED",
+            ErrorCode::TenantNotFound => "TENANT_NOT_FOUND",
+            ErrorCode::UserNotFound => "USER_NOT_FOUND",
+            ErrorCode::ChannelNotFound => "CHANNEL_NOT_FOUND",
+            ErrorCode::ContactNotFound => "CONTACT_NOT_FOUND",
+            ErrorCode::ConversationNotFound => "CONVERSATION_NOT_FOUND",
+            ErrorCode::MessageNotFound => "MESSAGE_NOT_FOUND",
+            ErrorCode::ConversationClosed => "CONVERSATION_CLOSED",
+            ErrorCode::ChannelDisconnected => "CHANNEL_DISCONNECTED",
+            ErrorCode::ChannelError => "CHANNEL_ERROR",
+            ErrorCode::RateLimited => "RATE_LIMITED",
+            ErrorCode::QuotaExceeded => "QUOTA_EXCEEDED",
+            ErrorCode::Timeout => "TIMEOUT",
+            ErrorCode::Other(code) => code,
+        }
+    }
+}
+
+impl From<&str> for ErrorCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "INTERNAL_ERROR" => ErrorCode::Internal,
+            "VALIDATION_ERROR" => ErrorCode::Validation,
+            "NOT_FOUND" => ErrorCode::NotFound,
+            "UNAUTHORIZED" => ErrorCode::Unauthorized,
+            "FORBIDDEN" => ErrorCode::Forbidden,
+            "CONFLICT" => ErrorCode::Conflict,
+            "BAD_REQUEST" => ErrorCode::BadRequest,
+            "INVALID_CREDENTIALS" => ErrorCode::InvalidCredentials,
+            "TOKEN_EXPIRED" => ErrorCode::TokenExpired,
+            "TOKEN_INVALID" => ErrorCode::TokenInvalid,
+            "MFA_REQUIRED" => ErrorCode::MfaRequired,
+            "TENANT_NOT_FOUND" => ErrorCode::TenantNotFound,
+            "USER_NOT_FOUND" => ErrorCode::UserNotFound,
+            "CHANNEL_NOT_FOUND" => ErrorCode::ChannelNotFound,
+            "CONTACT_NOT_FOUND" => ErrorCode::ContactNotFound,
+            "CONVERSATION_NOT_FOUND" => ErrorCode::ConversationNotFound,
+            "MESSAGE_NOT_FOUND" => ErrorCode::MessageNotFound,
+            "CONVERSATION_CLOSED" => ErrorCode::ConversationClosed,
+            "CHANNEL_DISCONNECTED" => ErrorCode::ChannelDisconnected,
+            "CHANNEL_ERROR" => ErrorCode::ChannelError,
+            "RATE_LIMITED" => ErrorCode::RateLimited,
+            "QUOTA_EXCEEDED" => ErrorCode::QuotaExceeded,
+            "TIMEOUT" => ErrorCode::Timeout,
+            other => ErrorCode::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Fallback used when a 429 response carries no usable `Retry-After` header.
+pub(crate) const DEFAULT_RETRY_AFTER_SECS: u64 = 60;
+
+/// Parse a `Retry-After` value given either as delta-seconds or as an HTTP-date.
+pub(crate) fn parse_retry_after(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(secs);
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delta = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delta.num_seconds().max(0) as u64)
+}
+
+pub type ErrorDetails = HashMap<String, serde_json::Value>;
+
 #[derive(Error, Debug)]
 pub enum LinktorError {
     #[error("Authentication failed: {message}")]
-    Authentication { message: String, request_id: Option<String> },
+    Authentication {
+        message: String,
+        code: Option<ErrorCode>,
+        details: Option<Box<ErrorDetails>>,
+        request_id: Option<String>,
+    },
 
     #[error("Authorization failed: {message}")]
-    Authorization { message: String, request_id: Option<String> },
+    Authorization {
+        message: String,
+        code: Option<ErrorCode>,
+        details: Option<Box<ErrorDetails>>,
+        request_id: Option<String>,
+    },
 
     #[error("Resource not found: {message}")]
-    NotFound { message: String, request_id: Option<String> },
+    NotFound {
+        message: String,
+        code: Option<ErrorCode>,
+        details: Option<Box<ErrorDetails>>,
+        request_id: Option<String>,
+    },
 
     #[error("Validation error: {message}")]
-    Validation { message: String, request_id: Option<String> },
+    Validation {
+        message: String,
+        code: Option<ErrorCode>,
+        details: Option<Box<ErrorDetails>>,
+        request_id: Option<String>,
+    },
 
     #[error("Rate limit exceeded. Retry after {retry_after} seconds")]
-    RateLimit { retry_after: u64, message: String, request_id: Option<String> },
+    RateLimit {
+        retry_after: u64,
+        message: String,
+        code: Option<ErrorCode>,
+        details: Option<Box<ErrorDetails>>,
+        request_id: Option<String>,
+    },
 
     #[error("Server error: {message}")]
-    Server { message: String, request_id: Option<String> },
+    Server {
+        message: String,
+        code: Option<ErrorCode>,
+        details: Option<Box<ErrorDetails>>,
+        request_id: Option<String>,
+    },
+
+    #[error("Request deadline of {deadline:?} exceeded")]
+    DeadlineExceeded { deadline: std::time::Duration },
 
     #[error("Network error: {0}")]
     Network(#[from] reqwest::Error),
@@ -26,36 +178,105 @@ pub enum LinktorError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("Webhook verification failed: {message}")]
     WebhookVerification { message: String },
 
+    #[error("Configuration error: {message}")]
+    Configuration { message: String },
+
     #[error("WebSocket error: {message}")]
     WebSocket { message: String },
 
+    #[error("Token store error: {message}")]
+    TokenStore { message: String },
+
+    #[error("Credentials error: {message}")]
+    Credentials { message: String },
+
+    #[error("Invalid token: {message}")]
+    InvalidToken { message: String },
+
     #[error("Unknown error: {message}")]
-    Unknown { message: String, status_code: Option<u16> },
+    Unknown {
+        message: String,
+        status_code: Option<u16>,
+        code: Option<ErrorCode>,
+        details: Option<Box<ErrorDetails>>,
+    },
 }
 
 impl LinktorError {
     pub fn from_status(status: reqwest::StatusCode, message: String, request_id: Option<String>) -> Self {
+        Self::build(status, message, None, None, request_id)
+    }
+
+    /// Build an error from a failed response body, keeping the API's `code`
+    /// and `details` when the body is a (possibly enveloped) `ApiError`.
+    pub fn from_response(status: reqwest::StatusCode, body: &str, request_id: Option<String>) -> Self {
+        let api_error = serde_json::from_str::<ApiResponse<serde_json::Value>>(body)
+            .ok()
+            .and_then(|r| r.error)
+            .or_else(|| serde_json::from_str::<ApiError>(body).ok());
+
+        match api_error {
+            Some(e) => Self::from_api_error(status, e, request_id),
+            None => Self::from_status(status, body.to_string(), request_id),
+        }
+    }
+
+    pub fn from_api_error(status: reqwest::StatusCode, error: ApiError, request_id: Option<String>) -> Self {
+        let code = Some(ErrorCode::from(error.code.as_str()));
+        Self::build(status, error.message, code, error.details.map(Box::new), request_id)
+    }
+
+    fn build(
+        status: reqwest::StatusCode,
+        message: String,
+        code: Option<ErrorCode>,
+        details: Option<Box<ErrorDetails>>,
+        request_id: Option<String>,
+    ) -> Self {
         match status.as_u16() {
-            400 => LinktorError::Validation { message, request_id },
-            401 => LinktorError::Authentication { message, request_id },
-            403 => LinktorError::Authorization { message, request_id },
-            404 => LinktorError::NotFound { message, request_id },
+            400 => LinktorError::Validation { message, code, details, request_id },
+            401 => LinktorError::Authentication { message, code, details, request_id },
+            403 => LinktorError::Authorization { message, code, details, request_id },
+            404 => LinktorError::NotFound { message, code, details, request_id },
             429 => LinktorError::RateLimit {
-                retry_after: 60,
+                retry_after: DEFAULT_RETRY_AFTER_SECS,
                 message,
+                code,
+                details,
                 request_id,
             },
-            500..=599 => LinktorError::Server { message, request_id },
+            500..=599 => LinktorError::Server { message, code, details, request_id },
             _ => LinktorError::Unknown {
                 message,
                 status_code: Some(status.as_u16()),
+                code,
+                details,
             },
         }
     }
 
+    /// Replace the retry delay of a `RateLimit` error; other variants are returned unchanged.
+    pub(crate) fn with_retry_after(mut self, secs: u64) -> Self {
+        if let LinktorError::RateLimit { ref mut retry_after, .. } = self {
+            *retry_after = secs;
+        }
+        self
+    }
+
+    /// Seconds to wait before retrying, for rate-limit errors.
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            LinktorError::RateLimit { retry_after, .. } => Some(*retry_after),
+            _ => None,
+        }
+    }
+
     pub fn request_id(&self) -> Option<&str> {
         match self {
             LinktorError::Authentication { request_id, .. } => request_id.as_deref(),
@@ -67,6 +288,64 @@ impl LinktorError {
             _ => None,
         }
     }
+
+    /// The API error code, when the server returned one.
+    pub fn code(&self) -> Option<&ErrorCode> {
+        match self {
+            LinktorError::Authentication { code, .. }
+            | LinktorError::Authorization { code, .. }
+            | LinktorError::NotFound { code, .. }
+            | LinktorError::Validation { code, .. }
+            | LinktorError::RateLimit { code, .. }
+            | LinktorError::Server { code, .. }
+            | LinktorError::Unknown { code, .. } => code.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Structured details attached to the API error, e.g. per-field validation messages.
+    pub fn details(&self) -> Option<&ErrorDetails> {
+        match self {
+            LinktorError::Authentication { details, .. }
+            | LinktorError::Authorization { details, .. }
+            | LinktorError::NotFound { details, .. }
+            | LinktorError::Validation { details, .. }
+            | LinktorError::RateLimit { details, .. }
+            | LinktorError::Server { details, .. }
+            | LinktorError::Unknown { details, .. } => details.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, LinktorError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_response_keeps_code_and_details() {
+        let body = r#"{"success":false,"error":{"code":"CONVERSATION_CLOSED","message":"closed","details":{"conversationId":"c1"}}}"#;
+        let err = LinktorError::from_response(reqwest::StatusCode::BAD_REQUEST, body, None);
+
+        assert!(matches!(err, LinktorError::Validation { .. }));
+        assert_eq!(err.code(), Some(&ErrorCode::ConversationClosed));
+        assert_eq!(err.details().unwrap()["conversationId"], "c1");
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(120));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(0));
+        let later = (chrono::Utc::now() + chrono::Duration::seconds(90)).to_rfc2822();
+        assert!(matches!(parse_retry_after(&later), Some(85..=90)));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn test_unknown_code_is_preserved() {
+        assert_eq!(ErrorCode::from("SOMETHING_NEW"), ErrorCode::Other("SOMETHING_NEW".to_string()));
+        assert_eq!(ErrorCode::from("SOMETHING_NEW").as_str(), "SOMETHING_NEW");
+    }
+}
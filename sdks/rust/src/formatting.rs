@@ -0,0 +1,321 @@
+//! Locale-aware money and date formatting for captions and rendered images,
+//! so values read `R$ 1.234,50` for a Brazilian tenant and `$1,234.50` for
+//! an American one.
+//!
+//! ```rust
+//! use linktor::formatting::Formatter;
+//!
+//! let brl = Formatter::new("pt-BR", "BRL");
+//! assert_eq!(brl.money(1234.5), "R$ 1.234,50");
+//!
+//! let usd = Formatter::new("en-US", "USD");
+//! assert_eq!(usd.money(-1234.5), "-$1,234.50");
+//! ```
+//!
+//! [`LinktorClient::formatter`](crate::LinktorClient::formatter) builds one
+//! from the tenant's settings and caches it; the VRE convenience renderers use
+//! it to add formatted amounts next to the raw numbers they send.
+//!
+//! Dates are formatted in the timezone of the value passed in; convert to the
+//! tenant's timezone first when it differs from the one you hold.
+
+use crate::types::auth::TenantSettings;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, TimeZone};
+use std::fmt;
+
+/// Moment/day.js tokens and their `strftime` equivalents, longest first so
+/// `MMMM` wins over `MM`.
+const MOMENT_TOKENS: &[(&str, &str)] = &[
+    ("YYYY", "%Y"),
+    ("YY", "%y"),
+    ("MMMM", "%B"),
+    ("MMM", "%b"),
+    ("MM", "%m"),
+    ("M", "%-m"),
+    ("DD", "%d"),
+    ("D", "%-d"),
+    ("HH", "%H"),
+    ("H", "%-H"),
+    ("hh", "%I"),
+    ("h", "%-I"),
+    ("mm", "%M"),
+    ("ss", "%S"),
+    ("A", "%p"),
+    ("a", "%P"),
+];
+
+struct LocaleRules {
+    tag: &'static str,
+    decimal: char,
+    group: char,
+    date: &'static str,
+    time: &'static str,
+    symbol_after: bool,
+    /// Between the symbol and the digits.
+    symbol_space: &'static str,
+}
+
+const LOCALES: &[LocaleRules] = &[
+    LocaleRules { tag: "pt-BR", decimal: ',', group: '.', date: "%d/%m/%Y", time: "%H:%M", symbol_after: false, symbol_space: " " },
+    LocaleRules { tag: "pt-PT", decimal: ',', group: '\u{a0}', date: "%d/%m/%Y", time: "%H:%M", symbol_after: true, symbol_space: "\u{a0}" },
+    LocaleRules { tag: "en-US", decimal: '.', group: ',', date: "%m/%d/%Y", time: "%-I:%M %p", symbol_after: false, symbol_space: "" },
+    LocaleRules { tag: "en-GB", decimal: '.', group: ',', date: "%d/%m/%Y", time: "%H:%M", symbol_after: false, symbol_space: "" },
+    LocaleRules { tag: "es-ES", decimal: ',', group: '.', date: "%d/%m/%Y", time: "%H:%M", symbol_after: true, symbol_space: "\u{a0}" },
+    LocaleRules { tag: "es-MX", decimal: '.', group: ',', date: "%d/%m/%Y", time: "%H:%M", symbol_after: false, symbol_space: "" },
+    LocaleRules { tag: "es-AR", decimal: ',', group: '.', date: "%d/%m/%Y", time: "%H:%M", symbol_after: false, symbol_space: " " },
+    LocaleRules { tag: "fr-FR", decimal: ',', group: '\u{202f}', date: "%d/%m/%Y", time: "%H:%M", symbol_after: true, symbol_space: "\u{a0}" },
+    LocaleRules { tag: "de-DE", decimal: ',', group: '.', date: "%d.%m.%Y", time: "%H:%M", symbol_after: true, symbol_space: "\u{a0}" },
+];
+
+/// Locale used for a bare language such as `pt` or `en`.
+const LANGUAGE_DEFAULTS: &[(&str, &str)] = &[("pt", "pt-BR"), ("en", "en-US"), ("es", "es-ES"), ("fr", "fr-FR"), ("de", "de-DE")];
+
+/// Symbol and minor units by ISO 4217 code; other codes are shown as the code.
+const CURRENCIES: &[(&str, &str, usize)] = &[
+    ("BRL", "R$", 2),
+    ("USD", "US$", 2),
+    ("EUR", "€", 2),
+    ("GBP", "£", 2),
+    ("MXN", "$", 2),
+    ("ARS", "$", 2),
+    ("COP", "$", 2),
+    ("CLP", "$", 0),
+    ("JPY", "¥", 0),
+];
+
+fn rules_for(locale: &str) -> &'static LocaleRules {
+    let find = |tag: &str| LOCALES.iter().find(|rules| rules.tag.eq_ignore_ascii_case(tag));
+    let normalized = locale.replace('_', "-");
+    let language = normalized.split('-').next().unwrap_or_default();
+    find(&normalized)
+        .or_else(|| {
+            LANGUAGE_DEFAULTS
+                .iter()
+                .find(|(lang, _)| lang.eq_ignore_ascii_case(language))
+                .and_then(|(_, tag)| find(tag))
+        })
+        .unwrap_or_else(|| find("en-US").unwrap())
+}
+
+/// Money, number and date formatting for one locale and currency.
+#[derive(Clone)]
+pub struct Formatter {
+    rules: &'static LocaleRules,
+    currency: String,
+    symbol: String,
+    minor_units: usize,
+    date_pattern: String,
+}
+
+impl Default for Formatter {
+    /// `pt-BR` with `BRL`, the platform default for tenants without settings.
+    fn default() -> Self {
+        Self::new("pt-BR", "BRL")
+    }
+}
+
+impl fmt::Debug for Formatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Formatter")
+            .field("locale", &self.rules.tag)
+            .field("currency", &self.currency)
+            .field("date_pattern", &self.date_pattern)
+            .finish()
+    }
+}
+
+impl Formatter {
+    /// `locale` is a BCP 47 tag such as `pt-BR`; unknown regions fall back to
+    /// the language's main locale and unknown languages to `en-US`.
+    /// `currency` is an ISO 4217 code.
+    pub fn new(locale: &str, currency: &str) -> Self {
+        let rules = rules_for(locale);
+        let currency = currency.to_ascii_uppercase();
+        let (symbol, minor_units) = match CURRENCIES.iter().find(|(code, _, _)| *code == currency) {
+            Some(("USD", _, units)) if rules.tag == "en-US" => ("$".to_string(), *units),
+            Some((_, symbol, units)) => (symbol.to_string(), *units),
+            None => (currency.clone(), 2),
+        };
+        Self {
+            rules,
+            currency,
+            symbol,
+            minor_units,
+            date_pattern: rules.date.to_string(),
+        }
+    }
+
+    /// The tenant's `language`, `currency` and `dateFormat` settings, with
+    /// [`Formatter::default`] filling in what is missing.
+    pub fn from_settings(settings: &TenantSettings) -> Self {
+        let locale = settings.language.as_deref().unwrap_or("pt-BR");
+        let currency = settings.currency.as_deref().unwrap_or("BRL");
+        let formatter = Self::new(locale, currency);
+        match settings.date_format {
+            Some(ref pattern) => formatter.date_pattern(pattern),
+            None => formatter,
+        }
+    }
+
+    /// Override the date pattern, either in `strftime` syntax (`%d/%m/%Y`) or
+    /// with moment-style tokens (`DD/MM/YYYY HH:mm`; text in `[brackets]` is
+    /// kept as is). Patterns chrono can't format, such as `%Q`, are ignored
+    /// and the locale's default is kept, since they usually come from tenant
+    /// settings rather than code.
+    pub fn date_pattern(mut self, pattern: &str) -> Self {
+        let pattern = if pattern.contains('%') {
+            pattern.to_string()
+        } else {
+            moment_to_strftime(pattern)
+        };
+        if !pattern.is_empty() && StrftimeItems::new(&pattern).all(|item| !matches!(item, Item::Error)) {
+            self.date_pattern = pattern;
+        }
+        self
+    }
+
+    /// The resolved locale, e.g. `pt-BR`.
+    pub fn locale(&self) -> &str {
+        self.rules.tag
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    /// `value` with `decimals` fraction digits and locale separators.
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let fixed = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = match fixed.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (fixed.as_str(), None),
+        };
+
+        let mut out = String::new();
+        if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            out.push('-');
+        }
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                out.push(self.rules.group);
+            }
+            out.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            out.push(self.rules.decimal);
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    /// An amount in the formatter's currency, e.g. `R$ 1.234,50`.
+    pub fn money(&self, amount: f64) -> String {
+        let number = self.number(amount, self.minor_units);
+        let (sign, digits) = match number.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", number.as_str()),
+        };
+        let space = self.rules.symbol_space;
+        if self.rules.symbol_after {
+            format!("{}{}{}{}", sign, digits, space, self.symbol)
+        } else {
+            format!("{}{}{}{}", sign, self.symbol, space, digits)
+        }
+    }
+
+    pub fn date<Tz: TimeZone>(&self, date: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        date.format(&self.date_pattern).to_string()
+    }
+
+    pub fn time<Tz: TimeZone>(&self, date: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        date.format(self.rules.time).to_string()
+    }
+
+    /// Date and time, e.g. `05/03/2024 14:30`.
+    pub fn date_time<Tz: TimeZone>(&self, date: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        format!("{} {}", self.date(date), self.time(date))
+    }
+}
+
+fn moment_to_strftime(pattern: &str) -> String {
+    let mut out = String::new();
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        if c == '[' {
+            if let Some(end) = rest.find(']') {
+                out.push_str(&rest[1..end].replace('%', "%%"));
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        match MOMENT_TOKENS.iter().find(|(token, _)| rest.starts_with(token)) {
+            Some((token, spec)) => {
+                out.push_str(spec);
+                rest = &rest[token.len()..];
+            }
+            None => {
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_money_by_locale() {
+        assert_eq!(Formatter::new("pt-BR", "BRL").money(1234567.891), "R$ 1.234.567,89");
+        assert_eq!(Formatter::new("pt", "brl").money(-0.5), "-R$ 0,50");
+        assert_eq!(Formatter::new("en-US", "USD").money(1234.5), "$1,234.50");
+        assert_eq!(Formatter::new("en-GB", "USD").money(12.0), "US$12.00");
+        assert_eq!(Formatter::new("de-DE", "EUR").money(1234.5), "1.234,50\u{a0}€");
+        assert_eq!(Formatter::new("es-CL", "CLP").money(15990.0), "15.990\u{a0}$");
+        assert_eq!(Formatter::new("xx", "XYZ").money(-0.001), "XYZ0.00");
+    }
+
+    #[test]
+    fn test_dates_follow_settings() {
+        let date = Utc.with_ymd_and_hms(2024, 3, 5, 14, 30, 0).unwrap();
+        assert_eq!(Formatter::default().date_time(&date), "05/03/2024 14:30");
+        assert_eq!(Formatter::new("en-US", "USD").date_time(&date), "03/05/2024 2:30 PM");
+
+        let settings: TenantSettings = serde_json::from_value(serde_json::json!({
+            "language": "en-GB",
+            "currency": "GBP",
+            "dateFormat": "YYYY-MM-DD"
+        }))
+        .unwrap();
+        let formatter = Formatter::from_settings(&settings);
+        assert_eq!(formatter.date(&date), "2024-03-05");
+        assert_eq!(formatter.money(9.99), "£9.99");
+    }
+
+    #[test]
+    fn test_moment_tokens_and_invalid_patterns() {
+        let date = Utc.with_ymd_and_hms(2024, 3, 5, 14, 7, 0).unwrap();
+        let pattern = |p: &str| Formatter::new("pt-BR", "BRL").date_pattern(p).date(&date);
+
+        assert_eq!(pattern("DD/MM/YYYY HH:mm"), "05/03/2024 14:07");
+        assert_eq!(pattern("D MMM YY, h:mm A"), "5 Mar 24, 2:07 PM");
+        assert_eq!(pattern("[Day] D"), "Day 5");
+        assert_eq!(pattern("%Y.%m.%d"), "2024.03.05");
+        for invalid in ["%Q", "%d/%m/%", ""] {
+            assert_eq!(pattern(invalid), "05/03/2024", "{:?}", invalid);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryReason {
+    /// The API answered 429.
+    RateLimited,
+    /// The API answered 5xx.
+    ServerError,
+    /// The region was unreachable or failing and the request moved to the next one.
+    Failover,
+}
+
+/// Emitted before the client retries a request.
+#[derive(Debug, Clone)]
+pub struct RetryEvent {
+    pub method: String,
+    pub path: String,
+    /// The attempt that failed, starting at 1.
+    pub attempt: u32,
+    pub reason: RetryReason,
+    pub status: Option<u16>,
+    /// Delay before the next attempt.
+    pub delay: Duration,
+    pub region: String,
+}
+
+/// Emitted before the client sleeps on a 429 response.
+#[derive(Debug, Clone)]
+pub struct RateLimitedEvent {
+    pub method: String,
+    pub path: String,
+    pub retry_after: Duration,
+    pub request_id: Option<String>,
+}
+
+/// Emitted after a new access token has been stored on the client.
+#[derive(Debug, Clone)]
+pub struct AuthRefreshEvent {
+    /// Lifetime of the new token in seconds.
+    pub expires_in: i64,
+}
+
+type Hook<E> = Arc<dyn Fn(&E) + Send + Sync>;
+
+/// Callbacks for notable client events. Hooks run inline on the request task,
+/// so they should return quickly.
+#[derive(Clone, Default)]
+pub(crate) struct ClientHooks {
+    pub on_retry: Option<Hook<RetryEvent>>,
+    pub on_rate_limited: Option<Hook<RateLimitedEvent>>,
+    pub on_auth_refresh: Option<Hook<AuthRefreshEvent>>,
+}
+
+impl ClientHooks {
+    pub fn retry(&self, event: impl FnOnce() -> RetryEvent) {
+        if let Some(ref hook) = self.on_retry {
+            hook(&event());
+        }
+    }
+
+    pub fn rate_limited(&self, event: impl FnOnce() -> RateLimitedEvent) {
+        if let Some(ref hook) = self.on_rate_limited {
+            hook(&event());
+        }
+    }
+
+    pub fn auth_refresh(&self, event: AuthRefreshEvent) {
+        if let Some(ref hook) = self.on_auth_refresh {
+            hook(&event);
+        }
+    }
+}
+
+impl fmt::Debug for ClientHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientHooks")
+            .field("on_retry", &self.on_retry.is_some())
+            .field("on_rate_limited", &self.on_rate_limited.is_some())
+            .field("on_auth_refresh", &self.on_auth_refresh.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,364 @@
+//! Local access to the claims of Linktor access tokens, so applications can
+//! check the tenant, user, role or expiry without calling `/auth/me`.
+//!
+//! ```rust
+//! use linktor::jwt::decode_claims;
+//!
+//! # fn check(token: &str) -> linktor::Result<()> {
+//! let claims = decode_claims(token)?;
+//! if claims.is_expired() || claims.role != "admin" {
+//!     // refresh, or refuse the admin-only action
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`decode_claims`] does not check the signature: use it only on tokens
+//! received from Linktor itself. To trust a token handed in by someone else,
+//! verify it with the tenant's signing secret ([`verify_hs256`]) or, with the
+//! `jwks` feature, against a published key set (`verify_with_jwks`).
+
+use crate::error::{LinktorError, Result};
+use crate::types::ids::{TenantId, UserId};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+
+/// The claims Linktor puts in access and refresh tokens.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Claims {
+    pub tenant_id: TenantId,
+    pub user_id: UserId,
+    #[serde(default)]
+    pub email: String,
+    /// `owner`, `admin`, `supervisor` or `agent`.
+    #[serde(default)]
+    pub role: String,
+    /// Expiry as a Unix timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    /// Any other claims, e.g. `aud` or `jti`.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl Claims {
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.exp.and_then(|exp| Utc.timestamp_opt(exp, 0).single())
+    }
+
+    pub fn issued_at(&self) -> Option<DateTime<Utc>> {
+        self.iat.and_then(|iat| Utc.timestamp_opt(iat, 0).single())
+    }
+
+    /// True once `exp` has passed; tokens without `exp` never expire.
+    pub fn is_expired(&self) -> bool {
+        self.exp.is_some_and(|exp| exp <= Utc::now().timestamp())
+    }
+
+    /// Seconds until the token expires, zero once it has.
+    pub fn expires_in(&self) -> Option<i64> {
+        self.exp.map(|exp| (exp - Utc::now().timestamp()).max(0))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Header {
+    alg: String,
+    #[serde(default)]
+    #[cfg_attr(not(feature = "jwks"), allow(dead_code))]
+    kid: Option<String>,
+}
+
+struct Parts<'a> {
+    header: Header,
+    payload: &'a str,
+    signing_input: &'a str,
+    signature: Vec<u8>,
+}
+
+fn invalid(message: impl Into<String>) -> LinktorError {
+    LinktorError::InvalidToken { message: message.into() }
+}
+
+fn decode_segment<T: DeserializeOwned>(segment: &str, name: &str) -> Result<T> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|e| invalid(format!("malformed {}: {}", name, e)))?;
+    serde_json::from_slice(&bytes).map_err(|e| invalid(format!("malformed {}: {}", name, e)))
+}
+
+fn split(token: &str) -> Result<Parts<'_>> {
+    let token = token.trim();
+    let token = token.strip_prefix("Bearer ").unwrap_or(token);
+    let (signing_input, signature) = token
+        .rsplit_once('.')
+        .ok_or_else(|| invalid("expected three dot-separated segments"))?;
+    let (header, payload) = signing_input
+        .split_once('.')
+        .filter(|(_, payload)| !payload.contains('.'))
+        .ok_or_else(|| invalid("expected three dot-separated segments"))?;
+    Ok(Parts {
+        header: decode_segment(header, "header")?,
+        payload,
+        signing_input,
+        signature: URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|e| invalid(format!("malformed signature: {}", e)))?,
+    })
+}
+
+fn checked(claims: Claims) -> Result<Claims> {
+    let now = Utc::now().timestamp();
+    if claims.is_expired() {
+        return Err(invalid("token has expired"));
+    }
+    if claims.nbf.is_some_and(|nbf| nbf > now) {
+        return Err(invalid("token is not valid yet"));
+    }
+    Ok(claims)
+}
+
+/// Read the claims of a token without verifying its signature or expiry.
+/// A `Bearer ` prefix is ignored.
+pub fn decode_claims(token: &str) -> Result<Claims> {
+    decode_segment(split(token)?.payload, "claims")
+}
+
+/// Verify an HS256 token against the signing secret it was issued with, as
+/// self-hosted deployments can, and check `exp` and `nbf`.
+pub fn verify_hs256(token: &str, secret: &[u8]) -> Result<Claims> {
+    let parts = split(token)?;
+    if parts.header.alg != "HS256" {
+        return Err(invalid(format!("expected an HS256 token, got {}", parts.header.alg)));
+    }
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(parts.signing_input.as_bytes());
+    mac.verify_slice(&parts.signature)
+        .map_err(|_| invalid("signature does not match"))?;
+    checked(decode_segment(parts.payload, "claims")?)
+}
+
+#[cfg(feature = "jwks")]
+pub use self::jwks::{verify_with_jwks, Jwk, Jwks};
+
+#[cfg(feature = "jwks")]
+mod jwks {
+    use super::*;
+    use ring::signature::{RsaPublicKeyComponents, UnparsedPublicKey, ECDSA_P256_SHA256_FIXED, RSA_PKCS1_2048_8192_SHA256};
+
+    /// A JSON Web Key Set, as served from a `jwks.json` endpoint.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Jwks {
+        pub keys: Vec<Jwk>,
+    }
+
+    /// An RSA (`n`, `e`) or P-256 (`x`, `y`) public key.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct Jwk {
+        pub kty: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub kid: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub alg: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub n: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub e: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub crv: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub x: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub y: Option<String>,
+    }
+
+    impl Jwks {
+        /// Download a key set, e.g. `https://auth.example.com/.well-known/jwks.json`,
+        /// giving up after the client's default timeout of 30 seconds.
+        pub async fn fetch(url: &str) -> Result<Self> {
+            Self::fetch_with_timeout(url, std::time::Duration::from_secs(crate::client::DEFAULT_TIMEOUT_SECS)).await
+        }
+
+        /// Like [`fetch`](Self::fetch), with a timeout for the whole download.
+        pub async fn fetch_with_timeout(url: &str, timeout: std::time::Duration) -> Result<Self> {
+            let http = reqwest::Client::builder().timeout(timeout).build()?;
+            let response = http.get(url).send().await?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(LinktorError::from_status(status, response.text().await?, None));
+            }
+            Ok(response.json().await?)
+        }
+
+        /// The key with `kid`, or the only key when the token names none.
+        pub fn find(&self, kid: Option<&str>) -> Option<&Jwk> {
+            match kid {
+                Some(kid) => self.keys.iter().find(|key| key.kid.as_deref() == Some(kid)),
+                None if self.keys.len() == 1 => self.keys.first(),
+                None => None,
+            }
+        }
+    }
+
+    fn field(value: &Option<String>, name: &str) -> Result<Vec<u8>> {
+        let value = value
+            .as_deref()
+            .ok_or_else(|| invalid(format!("key is missing '{}'", name)))?;
+        URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|e| invalid(format!("malformed key '{}': {}", name, e)))
+    }
+
+    /// Verify an RS256 or ES256 token against the matching key in `jwks` and
+    /// check `exp` and `nbf`.
+    pub fn verify_with_jwks(token: &str, jwks: &Jwks) -> Result<Claims> {
+        let parts = split(token)?;
+        let kid = parts.header.kid.as_deref();
+        let key = jwks
+            .find(kid)
+            .ok_or_else(|| invalid(format!("no key in the set matches kid {:?}", kid)))?;
+        let message = parts.signing_input.as_bytes();
+        let verified = match (parts.header.alg.as_str(), key.kty.as_str()) {
+            ("RS256", "RSA") => RsaPublicKeyComponents {
+                n: field(&key.n, "n")?,
+                e: field(&key.e, "e")?,
+            }
+            .verify(&RSA_PKCS1_2048_8192_SHA256, message, &parts.signature),
+            ("ES256", "EC") if key.crv.as_deref() == Some("P-256") => {
+                let mut point = vec![0x04];
+                point.extend(field(&key.x, "x")?);
+                point.extend(field(&key.y, "y")?);
+                UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point).verify(message, &parts.signature)
+            }
+            (alg, kty) => {
+                return Err(invalid(format!("unsupported algorithm {} for a {} key", alg, kty)));
+            }
+        };
+        verified.map_err(|_| invalid("signature does not match"))?;
+        checked(decode_segment(parts.payload, "claims")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(value: serde_json::Value) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(&value).unwrap())
+    }
+
+    fn claims(exp: i64) -> serde_json::Value {
+        serde_json::json!({
+            "tenant_id": "tenant-1",
+            "user_id": "user-1",
+            "email": "agent@example.com",
+            "role": "supervisor",
+            "exp": exp,
+            "iat": 1_700_000_000,
+            "iss": "linktor",
+            "sub": "user-1",
+            "jti": "abc"
+        })
+    }
+
+    fn hs256(payload: serde_json::Value, secret: &[u8]) -> String {
+        let input = format!("{}.{}", encode(serde_json::json!({ "alg": "HS256", "typ": "JWT" })), encode(payload));
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+        mac.update(input.as_bytes());
+        format!("{}.{}", input, URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn test_decode_claims() {
+        let exp = Utc::now().timestamp() + 900;
+        let token = hs256(claims(exp), b"secret");
+        let decoded = decode_claims(&format!("Bearer {}", token)).unwrap();
+        assert_eq!(decoded.tenant_id.as_str(), "tenant-1");
+        assert_eq!(decoded.user_id.as_str(), "user-1");
+        assert_eq!(decoded.role, "supervisor");
+        assert_eq!(decoded.expires_at().unwrap().timestamp(), exp);
+        assert!(!decoded.is_expired());
+        assert_eq!(decoded.extra["jti"], "abc");
+
+        assert!(decode_claims(&hs256(claims(1_700_000_900), b"secret")).unwrap().is_expired());
+        assert!(matches!(decode_claims("not-a-token"), Err(LinktorError::InvalidToken { .. })));
+    }
+
+    #[test]
+    fn test_verify_hs256() {
+        let token = hs256(claims(Utc::now().timestamp() + 900), b"secret");
+        assert_eq!(verify_hs256(&token, b"secret").unwrap().user_id.as_str(), "user-1");
+        assert!(verify_hs256(&token, b"other").is_err());
+
+        let expired = hs256(claims(1_700_000_900), b"secret");
+        let err = verify_hs256(&expired, b"secret").unwrap_err();
+        assert!(err.to_string().contains("expired"), "{}", err);
+    }
+
+    #[cfg(feature = "jwks")]
+    #[test]
+    fn test_verify_with_jwks() {
+        use ring::rand::SystemRandom;
+        use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+        let point = pair.public_key().as_ref();
+        let jwks = Jwks {
+            keys: vec![Jwk {
+                kty: "EC".to_string(),
+                kid: Some("k1".to_string()),
+                crv: Some("P-256".to_string()),
+                x: Some(URL_SAFE_NO_PAD.encode(&point[1..33])),
+                y: Some(URL_SAFE_NO_PAD.encode(&point[33..])),
+                ..Default::default()
+            }],
+        };
+
+        let input = format!(
+            "{}.{}",
+            encode(serde_json::json!({ "alg": "ES256", "kid": "k1" })),
+            encode(claims(Utc::now().timestamp() + 900))
+        );
+        let signature = pair.sign(&rng, input.as_bytes()).unwrap();
+        let token = format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature.as_ref()));
+        assert_eq!(verify_with_jwks(&token, &jwks).unwrap().tenant_id.as_str(), "tenant-1");
+
+        let tampered = format!("{}.{}", encode(serde_json::json!({ "alg": "ES256", "kid": "k1" })), encode(claims(0)));
+        let forged = format!("{}.{}", tampered, URL_SAFE_NO_PAD.encode(signature.as_ref()));
+        assert!(verify_with_jwks(&forged, &jwks).is_err());
+        assert!(verify_with_jwks(&hs256(claims(0), b"secret"), &Jwks::default()).is_err());
+    }
+
+    #[cfg(feature = "jwks")]
+    #[tokio::test]
+    async fn test_jwks_fetch_times_out() {
+        // Accepts the connection but never answers.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/.well-known/jwks.json", listener.local_addr().unwrap());
+        let _server = tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+        });
+
+        let started = std::time::Instant::now();
+        let result = Jwks::fetch_with_timeout(&url, std::time::Duration::from_millis(100)).await;
+        assert!(matches!(result, Err(LinktorError::Network(ref e)) if e.is_timeout()));
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+}
@@ -75,19 +75,58 @@
 //! }
 //! ```
 
+pub mod bulk;
+mod cache;
+pub mod capabilities;
+pub mod chunking;
 pub mod client;
+pub mod commands;
+#[cfg(feature = "credentials")]
+pub mod credentials;
 pub mod error;
+pub mod formatting;
+pub mod hooks;
+pub mod jwt;
+pub mod logging;
+pub mod metrics;
+pub mod oauth;
+pub mod pipeline;
+mod query;
+pub mod region;
+pub mod response;
+pub mod sandbox;
+pub mod signing;
+pub mod sms;
+mod sse;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod token_refresh;
+pub mod token_store;
+#[cfg(feature = "trace-context")]
+pub mod trace_context;
 pub mod types;
 pub mod webhook;
 
 pub use client::{
     LinktorClient, LinktorClientBuilder,
-    AuthResource, ConversationsResource, ContactsResource,
-    ChannelsResource, BotsResource, AIResource,
+    AuthResource, UsersResource, ConversationsResource, ContactsResource,
+    ChannelsResource, BotsResource, BotIntentsResource, AIResource,
     KnowledgeBasesResource, FlowsResource,
     CompletionsResource, EmbeddingsResource, AgentsResource,
+    RetentionResource, SmsResource, WebhooksResource, MediaResource, MediaStream, BroadcastsResource, CampaignsResource,
+    BatchBuilder, BatchHandle, BatchResults,
 };
-pub use error::{LinktorError, Result};
+pub use bulk::{BulkExecutor, BulkFailure, BulkReport, BulkSuccess};
+pub use capabilities::{ApiResource, Capabilities};
+pub use error::{ErrorCode, ErrorDetails, LinktorError, Result};
+pub use hooks::{AuthRefreshEvent, RateLimitedEvent, RetryEvent, RetryReason};
+pub use logging::DebugLogger;
+pub use region::Region;
+pub use response::Response;
+pub use token_refresh::{TokenRefreshHandle, TokenRefreshOptions};
+pub use token_store::{FileTokenStore, MemoryTokenStore, StoredTokens, TokenStore};
+#[cfg(feature = "trace-context")]
+pub use trace_context::TraceContext;
 pub use types::*;
 
 /// Type alias for the main error type
@@ -0,0 +1,195 @@
+use std::fmt;
+use std::sync::Arc;
+
+const REDACTED: &str = "[REDACTED]";
+const DEFAULT_MAX_BODY_LEN: usize = 2048;
+
+/// Headers whose values must never be written to debug output.
+const SENSITIVE_HEADERS: &[&str] = &["x-api-key", "authorization", "cookie", "set-cookie"];
+
+/// Suffixes of JSON body keys whose values must never be written to debug
+/// output, compared after lowercasing and dropping `_` and `-`, so `password`
+/// covers `newPassword` and `secret` covers `client_secret`. The list is
+/// deliberate rather than a substring match, which would also hide error
+/// `code`s, `countryCode` or `promptTokens`. The `otpauth://` enrollment URI
+/// embeds the TOTP secret.
+const SENSITIVE_FIELDS: &[&str] = &[
+    "password",
+    "secret",
+    "apikey",
+    "accesstoken",
+    "refreshtoken",
+    "idtoken",
+    "challengetoken",
+    "resettoken",
+    "verifytoken",
+    "codeverifier",
+    "recoverycodes",
+    "otpauthurl",
+];
+
+/// Keys redacted only when they match exactly, such as the bare `token` of a
+/// password reset.
+const SENSITIVE_KEYS: &[&str] = &["token"];
+
+/// Keys redacted in request bodies only. A request `code` is an MFA or OAuth
+/// authorization code; a response `code` is an error code worth seeing.
+const SENSITIVE_REQUEST_KEYS: &[&str] = &["code"];
+
+/// Opt-in request/response logger used by the client when debugging is enabled.
+#[derive(Clone)]
+pub struct DebugLogger {
+    sink: Arc<dyn Fn(&str) + Send + Sync>,
+    max_body_len: usize,
+}
+
+impl DebugLogger {
+    /// Logger that writes every line to stderr.
+    pub fn stderr() -> Self {
+        Self::new(|line| eprintln!("{}", line))
+    }
+
+    /// Logger that forwards every line to the given sink.
+    pub fn new(sink: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            max_body_len: DEFAULT_MAX_BODY_LEN,
+        }
+    }
+
+    /// Maximum number of body characters printed before truncating.
+    pub fn max_body_len(mut self, len: usize) -> Self {
+        self.max_body_len = len;
+        self
+    }
+
+    pub(crate) fn log_request(&self, request: &reqwest::Request) {
+        let mut line = format!("[linktor] --> {} {}", request.method(), request.url());
+        for (name, value) in request.headers() {
+            let value = value.to_str().unwrap_or("<binary>");
+            line.push_str(&format!("\n    {}: {}", name, redact_header(name.as_str(), value)));
+        }
+        if let Some(body) = request.body().and_then(|b| b.as_bytes()) {
+            line.push_str(&format!("\n    {}", self.format_body(body, true)));
+        }
+        (self.sink)(&line);
+    }
+
+    pub(crate) fn log_response(&self, status: reqwest::StatusCode, url: &str, request_id: Option<&str>, body: Option<&str>) {
+        let mut line = format!("[linktor] <-- {} {}", status, url);
+        if let Some(id) = request_id {
+            line.push_str(&format!(" (request id {})", id));
+        }
+        if let Some(body) = body {
+            line.push_str(&format!("\n    {}", self.format_body(body.as_bytes(), false)));
+        }
+        (self.sink)(&line);
+    }
+
+    fn format_body(&self, body: &[u8], is_request: bool) -> String {
+        let text = match serde_json::from_slice::<serde_json::Value>(body) {
+            Ok(mut value) => {
+                redact_json(&mut value, is_request);
+                value.to_string()
+            }
+            Err(_) => String::from_utf8_lossy(body).into_owned(),
+        };
+        truncate(&text, self.max_body_len)
+    }
+}
+
+impl fmt::Debug for DebugLogger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugLogger")
+            .field("max_body_len", &self.max_body_len)
+            .finish_non_exhaustive()
+    }
+}
+
+pub(crate) fn redact_header<'a>(name: &str, value: &'a str) -> &'a str {
+    if SENSITIVE_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(name)) {
+        REDACTED
+    } else {
+        value
+    }
+}
+
+fn redact_json(value: &mut serde_json::Value, is_request: bool) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if is_sensitive_field(key, is_request) {
+                    *v = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(v, is_request);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| redact_json(v, is_request)),
+        _ => {}
+    }
+}
+
+fn is_sensitive_field(key: &str, is_request: bool) -> bool {
+    let key: String = key
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    SENSITIVE_FIELDS.iter().any(|f| key.ends_with(f))
+        || SENSITIVE_KEYS.contains(&key.as_str())
+        || (is_request && SENSITIVE_REQUEST_KEYS.contains(&key.as_str()))
+}
+
+fn truncate(text: &str, max_len: usize) -> String {
+    match text.char_indices().nth(max_len) {
+        Some((idx, _)) => format!("{}... ({} bytes truncated)", &text[..idx], text.len() - idx),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_header() {
+        assert_eq!(redact_header("X-API-Key", "secret"), REDACTED);
+        assert_eq!(redact_header("authorization", "Bearer abc"), REDACTED);
+        assert_eq!(redact_header("Content-Type", "application/json"), "application/json");
+    }
+
+    #[test]
+    fn test_format_body_redacts_and_truncates() {
+        let logger = DebugLogger::new(|_| {}).max_body_len(40);
+        let body = br#"{"email":"a@b.c","password":"hunter2","note":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"}"#;
+        let out = logger.format_body(body, true);
+        assert!(!out.contains("hunter2"));
+        assert!(out.contains("truncated"));
+    }
+
+    #[test]
+    fn test_redacts_credential_fields() {
+        let logger = DebugLogger::new(|_| {});
+        let body = br#"{"currentPassword":"v1","NEWPASSWORD":"v2","challengeToken":"v3","recoveryCodes":["v4"],"code_verifier":"v5","client_secret":"v6","apiKey":"v7","email":"a@b.c"}"#;
+        let out = logger.format_body(body, false);
+        for leaked in ["v1", "v2", "v3", "v4", "v5", "v6", "v7"] {
+            assert!(!out.contains(leaked), "{} leaked in {}", leaked, out);
+        }
+        assert!(out.contains("a@b.c"));
+    }
+
+    #[test]
+    fn test_keeps_codes_and_counters() {
+        let logger = DebugLogger::new(|_| {});
+        let body = br#"{"code":"RATE_LIMITED","countryCode":"BR","languageCode":"pt","usage":{"promptTokens":12}}"#;
+        let out = logger.format_body(body, false);
+        for kept in ["RATE_LIMITED", "BR", "pt", "12"] {
+            assert!(out.contains(kept), "{} was redacted in {}", kept, out);
+        }
+
+        let out = logger.format_body(br#"{"code":"654321","countryCode":"BR"}"#, true);
+        assert!(!out.contains("654321"));
+        assert!(out.contains("BR"));
+    }
+}
@@ -0,0 +1,152 @@
+//! Prometheus metrics for client internals.
+//!
+//! With the `metrics` feature enabled, every client records into the registry
+//! returned by [`registry()`]; without it the recording hooks compile to nothing.
+
+#[cfg(feature = "metrics")]
+mod enabled {
+    use prometheus::{Counter, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry};
+    use std::sync::OnceLock;
+
+    pub(super) struct Metrics {
+        pub registry: Registry,
+        pub in_flight: IntGauge,
+        pub requests: IntCounterVec,
+        pub retries: IntCounterVec,
+        pub rate_limit_sleeps: IntCounter,
+        pub rate_limit_sleep_seconds: Counter,
+        pub region_healthy: IntGaugeVec,
+        pub pipeline_stages: HistogramVec,
+    }
+
+    pub(super) fn metrics() -> &'static Metrics {
+        static METRICS: OnceLock<Metrics> = OnceLock::new();
+        METRICS.get_or_init(|| {
+            let registry = Registry::new();
+            let in_flight = IntGauge::new("linktor_requests_in_flight", "Requests currently in flight").unwrap();
+            let requests = IntCounterVec::new(
+                Opts::new("linktor_requests_total", "Completed HTTP attempts by method and status"),
+                &["method", "status"],
+            )
+            .unwrap();
+            let retries = IntCounterVec::new(
+                Opts::new("linktor_retries_total", "Retried or failed-over attempts by reason"),
+                &["reason"],
+            )
+            .unwrap();
+            let rate_limit_sleeps =
+                IntCounter::new("linktor_rate_limit_sleeps_total", "Times the client slept after a 429").unwrap();
+            let rate_limit_sleep_seconds = Counter::new(
+                "linktor_rate_limit_sleep_seconds_total",
+                "Seconds spent sleeping after 429 responses",
+            )
+            .unwrap();
+            let region_healthy = IntGaugeVec::new(
+                Opts::new("linktor_region_healthy", "1 when the region is considered healthy, 0 while it is skipped"),
+                &["region"],
+            )
+            .unwrap();
+            let pipeline_stages = HistogramVec::new(
+                HistogramOpts::new("linktor_pipeline_stage_seconds", "Inbound pipeline stage durations by stage and result"),
+                &["stage", "status"],
+            )
+            .unwrap();
+
+            registry.register(Box::new(in_flight.clone())).unwrap();
+            registry.register(Box::new(requests.clone())).unwrap();
+            registry.register(Box::new(retries.clone())).unwrap();
+            registry.register(Box::new(rate_limit_sleeps.clone())).unwrap();
+            registry.register(Box::new(rate_limit_sleep_seconds.clone())).unwrap();
+            registry.register(Box::new(region_healthy.clone())).unwrap();
+            registry.register(Box::new(pipeline_stages.clone())).unwrap();
+
+            Metrics {
+                registry,
+                in_flight,
+                requests,
+                retries,
+                rate_limit_sleeps,
+                rate_limit_sleep_seconds,
+                region_healthy,
+                pipeline_stages,
+            }
+        })
+    }
+}
+
+/// The registry holding all SDK metrics, for inclusion in an application's scrape endpoint.
+#[cfg(feature = "metrics")]
+pub fn registry() -> &'static prometheus::Registry {
+    &enabled::metrics().registry
+}
+
+/// Decrements the in-flight gauge when dropped.
+pub(crate) struct InFlight(());
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        enabled::metrics().in_flight.dec();
+    }
+}
+
+pub(crate) fn request_started() -> InFlight {
+    #[cfg(feature = "metrics")]
+    enabled::metrics().in_flight.inc();
+    InFlight(())
+}
+
+pub(crate) fn request_completed(_method: &reqwest::Method, _status: reqwest::StatusCode) {
+    #[cfg(feature = "metrics")]
+    enabled::metrics()
+        .requests
+        .with_label_values(&[_method.as_str(), _status.as_str()])
+        .inc();
+}
+
+pub(crate) fn retry(_reason: &str) {
+    #[cfg(feature = "metrics")]
+    enabled::metrics().retries.with_label_values(&[_reason]).inc();
+}
+
+pub(crate) fn rate_limit_sleep(_seconds: u64) {
+    #[cfg(feature = "metrics")]
+    {
+        let metrics = enabled::metrics();
+        metrics.rate_limit_sleeps.inc();
+        metrics.rate_limit_sleep_seconds.inc_by(_seconds as f64);
+    }
+}
+
+pub(crate) fn region_health(_region: &str, _healthy: bool) {
+    #[cfg(feature = "metrics")]
+    enabled::metrics()
+        .region_healthy
+        .with_label_values(&[_region])
+        .set(_healthy as i64);
+}
+
+pub(crate) fn pipeline_stage(_stage: &str, _status: &str, _duration: std::time::Duration) {
+    #[cfg(feature = "metrics")]
+    enabled::metrics()
+        .pipeline_stages
+        .with_label_values(&[_stage, _status])
+        .observe(_duration.as_secs_f64());
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_exposes_metrics() {
+        let _guard = request_started();
+        retry("failover");
+        region_health("eu", false);
+
+        let names: Vec<String> = registry().gather().iter().map(|f| f.get_name().to_string()).collect();
+        assert!(names.contains(&"linktor_requests_in_flight".to_string()));
+        assert!(names.contains(&"linktor_retries_total".to_string()));
+        assert!(names.contains(&"linktor_region_healthy".to_string()));
+    }
+}
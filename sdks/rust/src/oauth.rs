@@ -0,0 +1,232 @@
+//! OAuth 2.0 authorization-code flow with PKCE (RFC 7636) for desktop and
+//! web apps that sign users in through the Linktor consent page.
+//!
+//! 1. [`AuthResource::authorize_url`](crate::AuthResource::authorize_url)
+//!    returns a [`PendingAuthorization`] holding the URL to open and the
+//!    secrets needed to finish the flow.
+//! 2. After consent the browser is sent to the redirect URI with `code` and
+//!    `state`; [`PendingAuthorization::code_from_redirect`] checks the state and
+//!    extracts the code.
+//! 3. [`AuthResource::exchange_code`](crate::AuthResource::exchange_code)
+//!    trades the code for tokens and signs the client in.
+//!
+//! Workers without a user use the client-credentials grant instead, set up
+//! with [`LinktorClientBuilder::client_credentials`](crate::LinktorClientBuilder::client_credentials).
+
+use crate::error::{LinktorError, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
+
+pub const AUTHORIZE_PATH: &str = "/auth/oauth/authorize";
+pub const TOKEN_PATH: &str = "/auth/oauth/token";
+/// Where service accounts obtain access tokens.
+pub const CLIENT_CREDENTIALS_PATH: &str = "/auth/token";
+/// Renew service tokens this long before they expire.
+const CLIENT_CREDENTIALS_LEAD: Duration = Duration::from_secs(60);
+
+/// The app registration and scopes to request.
+#[derive(Debug, Clone)]
+pub struct OAuthParams {
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    /// Opaque value echoed back on redirect; a random one is generated when unset.
+    pub state: Option<String>,
+}
+
+impl OAuthParams {
+    pub fn new(client_id: impl Into<String>, redirect_uri: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            redirect_uri: redirect_uri.into(),
+            scopes: Vec::new(),
+            state: None,
+        }
+    }
+
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    pub fn state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+}
+
+/// An authorization in progress. Web apps that handle the redirect in another
+/// request can keep it in the user's session; it serializes to JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingAuthorization {
+    /// Where to send the user's browser.
+    pub url: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub state: String,
+    /// PKCE secret; never sent to the browser.
+    pub code_verifier: String,
+}
+
+impl PendingAuthorization {
+    pub(crate) fn new(base_url: &str, params: OAuthParams) -> Result<Self> {
+        let code_verifier = random_token(32)?;
+        let state = match params.state {
+            Some(state) => state,
+            None => random_token(16)?,
+        };
+
+        let mut url = url::Url::parse(&format!("{}{}", base_url, AUTHORIZE_PATH)).map_err(|e| {
+            LinktorError::Configuration {
+                message: format!("Invalid base URL {}: {}", base_url, e),
+            }
+        })?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &params.client_id)
+            .append_pair("redirect_uri", &params.redirect_uri)
+            .append_pair("state", &state)
+            .append_pair("code_challenge", &code_challenge(&code_verifier))
+            .append_pair("code_challenge_method", "S256");
+        if !params.scopes.is_empty() {
+            url.query_pairs_mut().append_pair("scope", &params.scopes.join(" "));
+        }
+
+        Ok(Self {
+            url: url.into(),
+            client_id: params.client_id,
+            redirect_uri: params.redirect_uri,
+            state,
+            code_verifier,
+        })
+    }
+
+    /// The authorization code from the URL the browser was redirected to,
+    /// after checking that its `state` matches. A denied or failed
+    /// authorization is returned as an `Authentication` error.
+    pub fn code_from_redirect(&self, redirect_url: &str) -> Result<String> {
+        let url = url::Url::parse(redirect_url).map_err(|e| rejected(format!("invalid redirect URL: {}", e)))?;
+        let param = |name: &str| url.query_pairs().find(|(k, _)| k == name).map(|(_, v)| v.into_owned());
+
+        if param("state").as_deref() != Some(self.state.as_str()) {
+            return Err(rejected("state does not match the pending authorization".to_string()));
+        }
+        if let Some(error) = param("error") {
+            let description = param("error_description").unwrap_or_default();
+            return Err(rejected(format!("{} {}", error, description).trim_end().to_string()));
+        }
+        param("code").ok_or_else(|| rejected("redirect carries no authorization code".to_string()))
+    }
+}
+
+/// A service account's credentials and when its current token needs renewing.
+pub(crate) struct ClientCredentials {
+    grant: ClientCredentialsGrant,
+    renew_at: tokio::sync::Mutex<Option<Instant>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClientCredentialsGrant {
+    grant_type: &'static str,
+    client_id: String,
+    client_secret: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+}
+
+impl ClientCredentials {
+    pub fn new(client_id: String, client_secret: String, scopes: &[String]) -> Self {
+        Self {
+            grant: ClientCredentialsGrant {
+                grant_type: "client_credentials",
+                client_id,
+                client_secret,
+                scope: (!scopes.is_empty()).then(|| scopes.join(" ")),
+            },
+            renew_at: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    pub fn grant(&self) -> &impl Serialize {
+        &self.grant
+    }
+
+    /// Hold the lock while checking for and fetching a token so concurrent
+    /// requests share a single renewal.
+    pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, Option<Instant>> {
+        self.renew_at.lock().await
+    }
+
+    /// When a token valid for `expires_in` seconds should be renewed.
+    pub fn renew_at(expires_in: i64) -> Instant {
+        let lifetime = Duration::from_secs(expires_in.max(0) as u64);
+        Instant::now() + lifetime - CLIENT_CREDENTIALS_LEAD.min(lifetime / 2)
+    }
+
+    /// Force the next request to fetch a new token, e.g. after a 401.
+    pub async fn invalidate(&self) {
+        *self.renew_at.lock().await = None;
+    }
+}
+
+/// S256 challenge for a PKCE code verifier.
+pub fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+pub(crate) fn random_token(len: usize) -> Result<String> {
+    let mut bytes = vec![0u8; len];
+    getrandom::getrandom(&mut bytes).map_err(|e| LinktorError::Unknown {
+        message: format!("no secure random source available: {}", e),
+        status_code: None,
+        code: None,
+        details: None,
+    })?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+fn rejected(message: String) -> LinktorError {
+    LinktorError::Authentication {
+        message: format!("OAuth authorization failed: {}", message),
+        code: None,
+        details: None,
+        request_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_challenge_matches_rfc7636() {
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_code_from_redirect_checks_state() {
+        let pending = PendingAuthorization::new(
+            "https://api.linktor.io",
+            OAuthParams::new("app", "http://127.0.0.1:8765/callback").state("xyz"),
+        )
+        .unwrap();
+        assert_eq!(pending.code_verifier.len(), 43);
+
+        let code = pending.code_from_redirect("http://127.0.0.1:8765/callback?code=abc&state=xyz");
+        assert_eq!(code.unwrap(), "abc");
+        assert!(pending
+            .code_from_redirect("http://127.0.0.1:8765/callback?code=abc&state=other")
+            .is_err());
+        assert!(pending
+            .code_from_redirect("http://127.0.0.1:8765/callback?error=access_denied&state=xyz")
+            .is_err());
+    }
+}
@@ -0,0 +1,324 @@
+//! Ordered pre-processing of inbound messages before business handlers run:
+//! language detection, profanity filtering, attachment scanning and the like.
+//!
+//! ```rust
+//! use linktor::pipeline::{Pipeline, Step};
+//!
+//! let mut pipeline = Pipeline::new();
+//! pipeline
+//!     .stage("language", |mut inbound| async move {
+//!         inbound.annotate("language", "pt-BR");
+//!         Ok(Step::Continue(inbound))
+//!     })
+//!     .stage("profanity", |inbound| async move {
+//!         if inbound.text().unwrap_or_default().contains("spam") {
+//!             return Ok(Step::stop("blocked word"));
+//!         }
+//!         Ok(Step::Continue(inbound))
+//!     });
+//!
+//! # async fn handle(pipeline: &Pipeline, event: linktor::types::WebhookEvent) {
+//! if let Ok(linktor::pipeline::PipelineOutcome::Passed { message, .. }) = pipeline.run(event).await {
+//!     // business handlers see the message and the stage annotations
+//!     println!("{:?}", message.annotation("language"));
+//! }
+//! # }
+//! ```
+//!
+//! Stages run in registration order; the first one returning [`Step::Stop`]
+//! ends the run. With the `metrics` feature each stage records its duration
+//! and result in `linktor_pipeline_stage_seconds`.
+
+use crate::metrics;
+use crate::types::conversation::Message;
+use crate::types::webhook::{EventType, WebhookEvent};
+use futures_util::future::BoxFuture;
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Error returned by a stage; any error type converts into it with `?`.
+pub type StageError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Error, Debug)]
+pub enum PipelineError {
+    #[error("Invalid message.received event: {reason}")]
+    InvalidEvent { reason: String },
+
+    #[error("Pipeline stage '{stage}' failed: {source}")]
+    Stage { stage: String, source: StageError },
+}
+
+/// A received message travelling through the pipeline.
+#[derive(Debug, Clone)]
+pub struct InboundMessage {
+    pub event: WebhookEvent,
+    pub message: Message,
+    /// Values attached by earlier stages, e.g. a detected language.
+    pub annotations: HashMap<String, serde_json::Value>,
+}
+
+impl InboundMessage {
+    /// Wrap a message received outside webhooks, e.g. from an event stream.
+    pub fn new(event: WebhookEvent, message: Message) -> Self {
+        Self {
+            event,
+            message,
+            annotations: HashMap::new(),
+        }
+    }
+
+    fn from_event(event: WebhookEvent) -> Result<Self, PipelineError> {
+        let data = event.data.as_ref().ok_or_else(|| PipelineError::InvalidEvent {
+            reason: "event has no data".to_string(),
+        })?;
+        let value = match data.get("message") {
+            Some(message) => message.clone(),
+            None => serde_json::Value::Object(data.clone().into_iter().collect()),
+        };
+        let message = serde_json::from_value(value).map_err(|e| PipelineError::InvalidEvent {
+            reason: e.to_string(),
+        })?;
+        Ok(Self::new(event, message))
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.message.text.as_deref()
+    }
+
+    pub fn annotate(&mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) {
+        self.annotations.insert(key.into(), value.into());
+    }
+
+    pub fn annotation(&self, key: &str) -> Option<&serde_json::Value> {
+        self.annotations.get(key)
+    }
+}
+
+/// What a stage decided.
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Step {
+    /// Hand the (possibly modified) message to the next stage.
+    Continue(InboundMessage),
+    /// Drop the message; later stages and business handlers do not run.
+    Stop { reason: String },
+}
+
+impl Step {
+    pub fn stop(reason: impl Into<String>) -> Self {
+        Step::Stop { reason: reason.into() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageStatus {
+    Continued,
+    Stopped,
+    Failed,
+}
+
+impl StageStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StageStatus::Continued => "continued",
+            StageStatus::Stopped => "stopped",
+            StageStatus::Failed => "failed",
+        }
+    }
+}
+
+/// How one stage went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageReport {
+    pub stage: String,
+    pub status: StageStatus,
+    pub duration: Duration,
+}
+
+#[derive(Debug)]
+pub enum PipelineOutcome {
+    /// Every stage passed; hand the message to business handlers.
+    Passed {
+        message: Box<InboundMessage>,
+        stages: Vec<StageReport>,
+    },
+    /// A stage short-circuited the run.
+    Stopped {
+        stage: String,
+        reason: String,
+        stages: Vec<StageReport>,
+    },
+    /// Not a `message.received` event; no stage ran.
+    Skipped(Box<WebhookEvent>),
+}
+
+impl PipelineOutcome {
+    pub fn is_passed(&self) -> bool {
+        matches!(self, PipelineOutcome::Passed { .. })
+    }
+
+    /// Reports of the stages that ran, in order.
+    pub fn stages(&self) -> &[StageReport] {
+        match self {
+            PipelineOutcome::Passed { stages, .. } | PipelineOutcome::Stopped { stages, .. } => stages,
+            PipelineOutcome::Skipped(_) => &[],
+        }
+    }
+}
+
+type Processor = Box<dyn Fn(InboundMessage) -> BoxFuture<'static, Result<Step, StageError>> + Send + Sync>;
+
+/// Registered stages in the order they run.
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<(String, Processor)>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a stage. `name` labels its reports and metrics.
+    pub fn stage<F, Fut>(&mut self, name: &str, processor: F) -> &mut Self
+    where
+        F: Fn(InboundMessage) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Step, StageError>> + Send + 'static,
+    {
+        self.stages
+            .push((name.to_string(), Box::new(move |inbound| Box::pin(processor(inbound)))));
+        self
+    }
+
+    /// Stage names in run order.
+    pub fn stage_names(&self) -> Vec<&str> {
+        self.stages.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Run a webhook event through the pipeline. Events other than
+    /// `message.received` are returned untouched as [`PipelineOutcome::Skipped`].
+    pub async fn run(&self, event: WebhookEvent) -> Result<PipelineOutcome, PipelineError> {
+        if event.get_event_type() != Some(EventType::MessageReceived) {
+            return Ok(PipelineOutcome::Skipped(Box::new(event)));
+        }
+        self.run_message(InboundMessage::from_event(event)?).await
+    }
+
+    /// Run an already extracted message through the pipeline.
+    pub async fn run_message(&self, mut inbound: InboundMessage) -> Result<PipelineOutcome, PipelineError> {
+        let mut reports = Vec::with_capacity(self.stages.len());
+        for (name, processor) in &self.stages {
+            let started = Instant::now();
+            let result = processor(inbound).await;
+            let status = match result {
+                Ok(Step::Continue(_)) => StageStatus::Continued,
+                Ok(Step::Stop { .. }) => StageStatus::Stopped,
+                Err(_) => StageStatus::Failed,
+            };
+            let duration = started.elapsed();
+            metrics::pipeline_stage(name, status.as_str(), duration);
+            reports.push(StageReport {
+                stage: name.clone(),
+                status,
+                duration,
+            });
+
+            inbound = match result {
+                Ok(Step::Continue(next)) => next,
+                Ok(Step::Stop { reason }) => {
+                    return Ok(PipelineOutcome::Stopped {
+                        stage: name.clone(),
+                        reason,
+                        stages: reports,
+                    })
+                }
+                Err(source) => {
+                    return Err(PipelineError::Stage {
+                        stage: name.clone(),
+                        source,
+                    })
+                }
+            };
+        }
+        Ok(PipelineOutcome::Passed {
+            message: Box::new(inbound),
+            stages: reports,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: &str) -> WebhookEvent {
+        serde_json::from_value(serde_json::json!({
+            "id": "evt-1",
+            "type": event_type,
+            "timestamp": "2024-01-01T00:00:00Z",
+            "tenantId": "tenant-1",
+            "data": {
+                "message": {
+                    "id": "msg-1",
+                    "conversationId": "conv-1",
+                    "type": "text",
+                    "direction": "inbound",
+                    "status": "delivered",
+                    "text": "buy cheap spam",
+                    "createdAt": "2024-01-01T00:00:00Z",
+                    "updatedAt": "2024-01-01T00:00:00Z"
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn pipeline() -> Pipeline {
+        let mut pipeline = Pipeline::new();
+        pipeline
+            .stage("language", |mut inbound| async move {
+                inbound.annotate("language", "en");
+                Ok(Step::Continue(inbound))
+            })
+            .stage("profanity", |inbound| async move {
+                if inbound.annotation("language").is_some() && inbound.text().unwrap_or_default().contains("spam") {
+                    return Ok(Step::stop("blocked word"));
+                }
+                Ok(Step::Continue(inbound))
+            })
+            .stage("never", |_| async move { Err("unreachable".into()) });
+        pipeline
+    }
+
+    #[tokio::test]
+    async fn test_stop_short_circuits() {
+        match pipeline().run(event("message.received")).await.unwrap() {
+            PipelineOutcome::Stopped { stage, reason, stages } => {
+                assert_eq!(stage, "profanity");
+                assert_eq!(reason, "blocked word");
+                let statuses: Vec<_> = stages.iter().map(|s| s.status).collect();
+                assert_eq!(statuses, vec![StageStatus::Continued, StageStatus::Stopped]);
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+
+        let skipped = pipeline().run(event("contact.created")).await.unwrap();
+        assert!(matches!(skipped, PipelineOutcome::Skipped(_)));
+        assert!(skipped.stages().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_stage_is_reported() {
+        let mut pipeline = Pipeline::new();
+        pipeline.stage("scan", |_| async move { Err("scanner offline".into()) });
+        match pipeline.run(event("message.received")).await {
+            Err(PipelineError::Stage { stage, source }) => {
+                assert_eq!(stage, "scan");
+                assert_eq!(source.to_string(), "scanner offline");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
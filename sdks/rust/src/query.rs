@@ -0,0 +1,159 @@
+//! Query-string encoding for list parameters, and percent-encoding of the
+//! IDs and names placed in request paths.
+//!
+//! Scalars become `key=value`, sequences repeat the key (`tag=a&tag=b`) and
+//! maps use bracket notation (`metadata[plan]=pro`). `None` fields are omitted
+//! and keys are emitted in alphabetical order.
+
+use crate::error::{LinktorError, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+use url::form_urlencoded;
+
+/// `format!` for API paths: every argument is percent-encoded as a single
+/// path segment, so an ID can't add segments or a query string.
+macro_rules! api_path {
+    ($fmt:literal $(, $arg:expr)* $(,)?) => {
+        format!($fmt $(, $crate::query::PathSegment(&$arg))*)
+    };
+}
+pub(crate) use api_path;
+
+/// Displays the wrapped value with everything but RFC 3986 unreserved
+/// characters percent-encoded.
+pub(crate) struct PathSegment<'a, T: fmt::Display + ?Sized>(pub &'a T);
+
+impl<T: fmt::Display + ?Sized> fmt::Display for PathSegment<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.to_string().bytes() {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+                write!(f, "{}", byte as char)?;
+            } else {
+                write!(f, "%{:02X}", byte)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Append the encoded `params` to `path`, or return `path` unchanged if there are none.
+pub(crate) fn with_query<T: Serialize>(path: &str, params: &T) -> Result<String> {
+    let query = to_query_string(params)?;
+    if query.is_empty() {
+        Ok(path.to_string())
+    } else {
+        Ok(format!("{}?{}", path, query))
+    }
+}
+
+pub(crate) fn to_query_string<T: Serialize>(params: &T) -> Result<String> {
+    let fields = match serde_json::to_value(params)? {
+        Value::Object(fields) => fields,
+        Value::Null => return Ok(String::new()),
+        other => {
+            return Err(query_error(format!(
+                "query parameters must serialize to a map, got {}",
+                kind(&other)
+            )))
+        }
+    };
+
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    for (key, value) in &fields {
+        append(&mut query, key, value)?;
+    }
+    Ok(query.finish())
+}
+
+fn append(query: &mut form_urlencoded::Serializer<'_, String>, key: &str, value: &Value) -> Result<()> {
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => {
+            query.append_pair(key, if *b { "true" } else { "false" });
+        }
+        Value::Number(n) => {
+            query.append_pair(key, &n.to_string());
+        }
+        Value::String(s) => {
+            query.append_pair(key, s);
+        }
+        Value::Array(items) => {
+            for item in items {
+                match item {
+                    Value::Array(_) => {
+                        return Err(query_error(format!("nested lists are not supported in query parameter '{}'", key)))
+                    }
+                    Value::Object(_) => {
+                        return Err(query_error(format!("lists of maps are not supported in query parameter '{}'", key)))
+                    }
+                    scalar => append(query, key, scalar)?,
+                }
+            }
+        }
+        Value::Object(fields) => {
+            for (field, value) in fields {
+                append(query, &format!("{}[{}]", key, field), value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "a map",
+    }
+}
+
+fn query_error(message: String) -> LinktorError {
+    LinktorError::Serialization(serde::ser::Error::custom(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Params {
+        search: Option<String>,
+        tags: Vec<String>,
+        metadata: BTreeMap<String, String>,
+        limit: Option<i32>,
+    }
+
+    #[test]
+    fn test_lists_and_maps() {
+        let params = Params {
+            search: Some("ana maria".into()),
+            tags: vec!["vip".into(), "b2b".into()],
+            metadata: BTreeMap::from([("plan".to_string(), "pro".to_string())]),
+            limit: None,
+        };
+        assert_eq!(
+            to_query_string(&params).unwrap(),
+            "metadata%5Bplan%5D=pro&search=ana+maria&tags=vip&tags=b2b"
+        );
+        assert_eq!(with_query("/contacts", &()).unwrap(), "/contacts");
+    }
+
+    #[test]
+    fn test_path_segments_are_encoded() {
+        assert_eq!(api_path!("/contacts/{}", "c-1"), "/contacts/c-1");
+        assert_eq!(api_path!("/contacts/{}", "../users/u 1?x#y"), "/contacts/..%2Fusers%2Fu%201%3Fx%23y");
+        assert_eq!(api_path!("/intents/{}", "pedido_é"), "/intents/pedido_%C3%A9");
+    }
+
+    #[test]
+    fn test_unsupported_shapes_are_errors() {
+        assert!(to_query_string(&"text").is_err());
+        assert!(to_query_string(&serde_json::json!({ "ids": [[1, 2]] })).is_err());
+    }
+}
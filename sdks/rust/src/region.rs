@@ -0,0 +1,105 @@
+use crate::metrics;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A named API endpoint, e.g. `Region::new("eu", "https://eu.api.linktor.io")`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub name: String,
+    pub base_url: String,
+}
+
+impl Region {
+    pub fn new(name: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RegionState {
+    region: Region,
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+/// Ordered regions with passive health tracking. A region that fails is
+/// skipped for `cooldown` and then tried again.
+#[derive(Debug, Clone)]
+pub(crate) struct RegionPool {
+    regions: Arc<Vec<RegionState>>,
+    cooldown: Duration,
+}
+
+impl RegionPool {
+    pub fn new(regions: Vec<Region>, cooldown: Duration) -> Self {
+        Self {
+            regions: Arc::new(
+                regions
+                    .into_iter()
+                    .map(|region| RegionState {
+                        region,
+                        unhealthy_until: Mutex::new(None),
+                    })
+                    .collect(),
+            ),
+            cooldown,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn get(&self, index: usize) -> &Region {
+        &self.regions[index].region
+    }
+
+    pub fn is_healthy(&self, index: usize) -> bool {
+        match *self.regions[index].unhealthy_until.lock().unwrap() {
+            Some(until) => Instant::now() >= until,
+            None => true,
+        }
+    }
+
+    /// Region indices in the order they should be tried: healthy regions in
+    /// configured order, then unhealthy ones as a last resort.
+    pub fn candidates(&self) -> Vec<usize> {
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            (0..self.len()).partition(|&i| self.is_healthy(i));
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    pub fn mark_unhealthy(&self, index: usize) {
+        *self.regions[index].unhealthy_until.lock().unwrap() = Some(Instant::now() + self.cooldown);
+        metrics::region_health(&self.regions[index].region.name, false);
+    }
+
+    pub fn mark_healthy(&self, index: usize) {
+        *self.regions[index].unhealthy_until.lock().unwrap() = None;
+        metrics::region_health(&self.regions[index].region.name, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unhealthy_regions_are_tried_last() {
+        let pool = RegionPool::new(
+            vec![Region::new("eu", "https://eu.example/"), Region::new("us", "https://us.example")],
+            Duration::from_secs(60),
+        );
+        assert_eq!(pool.get(0).base_url, "https://eu.example");
+        assert_eq!(pool.candidates(), vec![0, 1]);
+
+        pool.mark_unhealthy(0);
+        assert_eq!(pool.candidates(), vec![1, 0]);
+
+        pool.mark_healthy(0);
+        assert_eq!(pool.candidates(), vec![0, 1]);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::region::Region;
+use crate::types::common::RateLimitStatus;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::ops::Deref;
+
+/// A deserialized response body together with the HTTP metadata it arrived with.
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    data: T,
+    status: StatusCode,
+    headers: HeaderMap,
+    region: Region,
+}
+
+impl<T> Response<T> {
+    pub(crate) fn new(data: T, status: StatusCode, headers: HeaderMap, region: Region) -> Self {
+        Self {
+            data,
+            status,
+            headers,
+            region,
+        }
+    }
+
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    /// The `X-Request-ID` assigned by the API, useful for support tickets.
+    pub fn request_id(&self) -> Option<&str> {
+        self.header("X-Request-ID")
+    }
+
+    /// The region that served this response.
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    /// Rate-limit state reported with this response.
+    pub fn rate_limit(&self) -> Option<RateLimitStatus> {
+        crate::client::parse_rate_limit(&self.headers)
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Response<U> {
+        Response {
+            data: f(self.data),
+            status: self.status,
+            headers: self.headers,
+            region: self.region,
+        }
+    }
+}
+
+impl<T> Deref for Response<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
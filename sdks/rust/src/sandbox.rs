@@ -0,0 +1,299 @@
+//! Policy layer for executing model-requested tools locally.
+//!
+//! The SDK does not execute tools itself; applications that run tool calls
+//! locally wrap each invocation with a [`SandboxRun`] so malformed or runaway
+//! model output cannot trigger unbounded side effects.
+
+use crate::types::ai::Tool;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SandboxViolation {
+    #[error("Tool '{tool}' is not allowed by the sandbox policy")]
+    ToolNotAllowed { tool: String },
+
+    #[error("Tool '{tool}' is not registered with the sandbox")]
+    UnknownTool { tool: String },
+
+    #[error("Run exceeded the limit of {limit} tool invocations")]
+    InvocationLimitExceeded { limit: u32 },
+
+    #[error("Tool '{tool}' timed out after {timeout:?}")]
+    Timeout { tool: String, timeout: Duration },
+
+    #[error("Invalid arguments for tool '{tool}': {reason}")]
+    InvalidArguments { tool: String, reason: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct SandboxPolicy {
+    /// Maximum wall-clock time for a single tool invocation.
+    pub timeout: Duration,
+    /// Maximum number of tool invocations in a single run. Attempts the policy
+    /// rejects count too, so a model cannot probe it without limit.
+    pub max_invocations: u32,
+    /// Tool names that may be executed. `None` allows every registered tool.
+    pub allowed_tools: Option<HashSet<String>>,
+    /// Validate arguments against the tool's JSON schema before executing.
+    pub validate_arguments: bool,
+}
+
+impl Default for SandboxPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            max_invocations: 16,
+            allowed_tools: None,
+            validate_arguments: true,
+        }
+    }
+}
+
+impl SandboxPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn max_invocations(mut self, max: u32) -> Self {
+        self.max_invocations = max;
+        self
+    }
+
+    pub fn allow_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_tools = Some(tools.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn validate_arguments(mut self, validate: bool) -> Self {
+        self.validate_arguments = validate;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ToolSandbox {
+    policy: SandboxPolicy,
+    tools: HashMap<String, Tool>,
+}
+
+impl ToolSandbox {
+    pub fn new(policy: SandboxPolicy, tools: Vec<Tool>) -> Self {
+        Self {
+            policy,
+            tools: tools.into_iter().map(|t| (t.name.clone(), t)).collect(),
+        }
+    }
+
+    pub fn policy(&self) -> &SandboxPolicy {
+        &self.policy
+    }
+
+    /// Start a run; invocation limits are counted per run.
+    pub fn start_run(&self) -> SandboxRun<'_> {
+        SandboxRun {
+            sandbox: self,
+            invocations: 0,
+        }
+    }
+
+    /// Check that `tool` may be called with `arguments`, without executing it.
+    pub fn check(&self, tool: &str, arguments: &serde_json::Value) -> Result<(), SandboxViolation> {
+        if let Some(ref allowed) = self.policy.allowed_tools {
+            if !allowed.contains(tool) {
+                return Err(SandboxViolation::ToolNotAllowed { tool: tool.to_string() });
+            }
+        }
+
+        let definition = self.tools.get(tool).ok_or_else(|| SandboxViolation::UnknownTool {
+            tool: tool.to_string(),
+        })?;
+
+        if self.policy.validate_arguments {
+            if let Some(ref schema) = definition.parameters {
+                let schema = serde_json::Value::Object(schema.clone().into_iter().collect());
+                validate(&schema, arguments, "arguments").map_err(|reason| SandboxViolation::InvalidArguments {
+                    tool: tool.to_string(),
+                    reason,
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct SandboxRun<'a> {
+    sandbox: &'a ToolSandbox,
+    invocations: u32,
+}
+
+impl SandboxRun<'_> {
+    pub fn invocations(&self) -> u32 {
+        self.invocations
+    }
+
+    /// Execute a tool through the sandbox. `execute` is only called when the
+    /// invocation passes the policy, and is cancelled when it exceeds the timeout.
+    pub async fn invoke<F, Fut, T>(
+        &mut self,
+        tool: &str,
+        arguments: &serde_json::Value,
+        execute: F,
+    ) -> Result<T, SandboxViolation>
+    where
+        F: FnOnce(serde_json::Value) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let policy = &self.sandbox.policy;
+        if self.invocations >= policy.max_invocations {
+            return Err(SandboxViolation::InvocationLimitExceeded {
+                limit: policy.max_invocations,
+            });
+        }
+        self.invocations += 1;
+        self.sandbox.check(tool, arguments)?;
+
+        tokio::time::timeout(policy.timeout, execute(arguments.clone()))
+            .await
+            .map_err(|_| SandboxViolation::Timeout {
+                tool: tool.to_string(),
+                timeout: policy.timeout,
+            })
+    }
+}
+
+/// Minimal JSON schema check covering `type`, `required`, `properties`, `items` and `enum`.
+fn validate(schema: &serde_json::Value, value: &serde_json::Value, path: &str) -> Result<(), String> {
+    if let Some(expected) = schema.get("type").and_then(|t| t.as_str()) {
+        let matches = match expected {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => true,
+        };
+        if !matches {
+            return Err(format!("{} must be of type {}", path, expected));
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !options.contains(value) {
+            return Err(format!("{} must be one of {}", path, serde_json::Value::from(options.clone())));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+            for field in required.iter().filter_map(|f| f.as_str()) {
+                if !object.contains_key(field) {
+                    return Err(format!("{}.{} is required", path, field));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (key, property_schema) in properties {
+                if let Some(property) = object.get(key) {
+                    validate(property_schema, property, &format!("{}.{}", path, key))?;
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (i, item) in array.iter().enumerate() {
+            validate(items, item, &format!("{}[{}]", path, i))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn lookup_tool() -> Tool {
+        Tool {
+            name: "lookup_order".to_string(),
+            description: None,
+            parameters: Some(
+                json!({
+                    "type": "object",
+                    "required": ["orderId"],
+                    "properties": { "orderId": { "type": "string" } }
+                })
+                .as_object()
+                .unwrap()
+                .clone()
+                .into_iter()
+                .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_check_policy() {
+        let sandbox = ToolSandbox::new(SandboxPolicy::new().allow_tools(["lookup_order"]), vec![lookup_tool()]);
+
+        assert!(sandbox.check("lookup_order", &json!({"orderId": "42"})).is_ok());
+        assert!(matches!(
+            sandbox.check("delete_everything", &json!({})),
+            Err(SandboxViolation::ToolNotAllowed { .. })
+        ));
+        assert!(matches!(
+            sandbox.check("lookup_order", &json!({"orderId": 42})),
+            Err(SandboxViolation::InvalidArguments { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invocation_limit_and_timeout() {
+        let policy = SandboxPolicy::new().max_invocations(1).timeout(Duration::from_millis(10));
+        let sandbox = ToolSandbox::new(policy, vec![lookup_tool()]);
+        let mut run = sandbox.start_run();
+        let args = json!({"orderId": "42"});
+
+        let slow = run
+            .invoke("lookup_order", &args, |_| tokio::time::sleep(Duration::from_secs(1)))
+            .await;
+        assert!(matches!(slow, Err(SandboxViolation::Timeout { .. })));
+
+        let over = run.invoke("lookup_order", &args, |_| async {}).await;
+        assert_eq!(over, Err(SandboxViolation::InvocationLimitExceeded { limit: 1 }));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_attempts_count_toward_limit() {
+        let sandbox = ToolSandbox::new(
+            SandboxPolicy::new().allow_tools(["lookup_order"]).max_invocations(2),
+            vec![lookup_tool()],
+        );
+        let mut run = sandbox.start_run();
+
+        for _ in 0..2 {
+            let denied = run.invoke("delete_everything", &json!({}), |_| async {}).await;
+            assert!(matches!(denied, Err(SandboxViolation::ToolNotAllowed { .. })));
+        }
+        assert_eq!(run.invocations(), 2);
+
+        let valid = run.invoke("lookup_order", &json!({"orderId": "42"}), |_| async {}).await;
+        assert_eq!(valid, Err(SandboxViolation::InvocationLimitExceeded { limit: 2 }));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::types::conversation::TextEncoding;
+
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+const GSM7_EXTENDED: &str = "\u{000C}^{}\\[~]|€";
+
+const GSM7_SINGLE_SEGMENT: usize = 160;
+const GSM7_MULTI_SEGMENT: usize = 153;
+const UCS2_SINGLE_SEGMENT: usize = 70;
+const UCS2_MULTI_SEGMENT: usize = 67;
+
+/// Pick the encoding a carrier will use for `text`: GSM-7 when every character
+/// is in the GSM 03.38 alphabet, UCS-2 otherwise.
+pub fn detect_encoding(text: &str) -> TextEncoding {
+    if text.chars().all(|c| GSM7_BASIC.contains(c) || GSM7_EXTENDED.contains(c)) {
+        TextEncoding::Gsm7
+    } else {
+        TextEncoding::Ucs2
+    }
+}
+
+/// Number of SMS segments needed to deliver `text` with the given encoding.
+///
+/// GSM-7 extension characters take two septets and surrogate pairs take two
+/// UCS-2 units; neither is split across segments.
+pub fn segment_count(text: &str, encoding: TextEncoding) -> usize {
+    let (single, multi) = match encoding {
+        TextEncoding::Gsm7 => (GSM7_SINGLE_SEGMENT, GSM7_MULTI_SEGMENT),
+        TextEncoding::Ucs2 => (UCS2_SINGLE_SEGMENT, UCS2_MULTI_SEGMENT),
+    };
+    let widths: Vec<usize> = text.chars().map(|c| char_width(c, encoding)).collect();
+
+    let total: usize = widths.iter().sum();
+    if total == 0 {
+        return 0;
+    }
+    if total <= single {
+        return 1;
+    }
+
+    let mut segments = 1;
+    let mut used = 0;
+    for width in widths {
+        if used + width > multi {
+            segments += 1;
+            used = 0;
+        }
+        used += width;
+    }
+    segments
+}
+
+fn char_width(c: char, encoding: TextEncoding) -> usize {
+    match encoding {
+        TextEncoding::Gsm7 if GSM7_EXTENDED.contains(c) => 2,
+        TextEncoding::Gsm7 => 1,
+        TextEncoding::Ucs2 => c.len_utf16(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_encoding() {
+        assert_eq!(detect_encoding("Hello {world}"), TextEncoding::Gsm7);
+        assert_eq!(detect_encoding("Olá, tudo bem?"), TextEncoding::Ucs2);
+        assert_eq!(detect_encoding("👍"), TextEncoding::Ucs2);
+    }
+
+    #[test]
+    fn test_segment_count() {
+        assert_eq!(segment_count("", TextEncoding::Gsm7), 0);
+        assert_eq!(segment_count(&"a".repeat(160), TextEncoding::Gsm7), 1);
+        assert_eq!(segment_count(&"a".repeat(161), TextEncoding::Gsm7), 2);
+        assert_eq!(segment_count(&"a".repeat(306), TextEncoding::Gsm7), 2);
+        assert_eq!(segment_count(&"€".repeat(80), TextEncoding::Gsm7), 1);
+        assert_eq!(segment_count(&"ç".repeat(70), TextEncoding::Ucs2), 1);
+        assert_eq!(segment_count(&"ç".repeat(71), TextEncoding::Ucs2), 2);
+    }
+}
@@ -0,0 +1,212 @@
+//! Server-sent event streams: an incremental `text/event-stream` parser and
+//! a stream that resumes dropped connections with `Last-Event-ID`.
+
+use crate::client::LinktorClient;
+use crate::error::{LinktorError, Result};
+use futures_util::stream::{self, Stream};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SseEvent {
+    pub id: Option<String>,
+    /// Event type; `message` when the server sent none.
+    pub event: String,
+    pub data: String,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SseParser {
+    /// Raw bytes, so a multi-byte character split between chunks is only
+    /// decoded once its line is complete.
+    buffer: Vec<u8>,
+    id: Option<String>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed a chunk of the body and return every event it completed. Chunks may
+    /// split lines anywhere; partial lines are kept until the next call.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let raw: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&raw);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(SseEvent {
+                        id: self.id.clone(),
+                        event: self.event.take().unwrap_or_else(|| "message".to_string()),
+                        data: std::mem::take(&mut self.data).join("\n"),
+                    });
+                }
+                self.event = None;
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "data" => self.data.push(value.to_string()),
+                "event" => self.event = Some(value.to_string()),
+                // The last event ID persists across events until changed.
+                "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        events
+    }
+
+    /// The most recent event ID, for `Last-Event-ID` on reconnect.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Drop a half-received event after the connection broke, keeping the last event ID.
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.event = None;
+        self.data.clear();
+    }
+}
+
+struct StreamState {
+    client: LinktorClient,
+    path: String,
+    is_last: fn(&SseEvent) -> bool,
+    response: Option<reqwest::Response>,
+    parser: SseParser,
+    pending: VecDeque<SseEvent>,
+    failures: u32,
+    done: bool,
+}
+
+/// Events from `path` up to and including the first one `is_last` accepts.
+///
+/// A connection that closes or fails before then is reopened after a short
+/// delay; the stream gives up with an error after more consecutive failures
+/// than the client's `max_retries`. Errors other than network errors when
+/// opening the stream (auth, 404, ...) end it immediately.
+pub(crate) fn event_stream(
+    client: LinktorClient,
+    path: String,
+    is_last: fn(&SseEvent) -> bool,
+) -> impl Stream<Item = Result<SseEvent>> + Send + 'static {
+    let state = StreamState {
+        client,
+        path,
+        is_last,
+        response: None,
+        parser: SseParser::default(),
+        pending: VecDeque::new(),
+        failures: 0,
+        done: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.pending.pop_front() {
+                state.failures = 0;
+                if (state.is_last)(&event) {
+                    state.done = true;
+                    state.pending.clear();
+                }
+                return Some((Ok(event), state));
+            }
+            if state.done {
+                return None;
+            }
+
+            let error = match state.response {
+                None => match state.client.open_event_stream(&state.path, state.parser.last_event_id()).await {
+                    Ok(response) => {
+                        state.response = Some(response);
+                        continue;
+                    }
+                    Err(e @ LinktorError::Network(_)) => e,
+                    Err(e) => {
+                        state.done = true;
+                        return Some((Err(e), state));
+                    }
+                },
+                Some(ref mut response) => match response.chunk().await {
+                    Ok(Some(bytes)) => {
+                        let events = state.parser.push(&bytes);
+                        state.pending.extend(events);
+                        continue;
+                    }
+                    Ok(None) => LinktorError::Unknown {
+                        message: format!("event stream {} closed before it finished", state.path),
+                        status_code: None,
+                        code: None,
+                        details: None,
+                    },
+                    Err(e) => e.into(),
+                },
+            };
+
+            state.response = None;
+            state.parser.reset();
+            state.failures += 1;
+            if state.failures > state.client.max_retries() {
+                state.done = true;
+                return Some((Err(error), state));
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_split_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b": keep-alive\n\nid: 1\nevent: st").is_empty());
+        let events = parser.push(b"ep\ndata: {\"a\":\ndata: 1}\r\n\r\ndata: plain\n\n");
+
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    id: Some("1".to_string()),
+                    event: "step".to_string(),
+                    data: "{\"a\":\n1}".to_string(),
+                },
+                SseEvent {
+                    id: Some("1".to_string()),
+                    event: "message".to_string(),
+                    data: "plain".to_string(),
+                },
+            ]
+        );
+        assert_eq!(parser.last_event_id(), Some("1"));
+    }
+
+    #[test]
+    fn test_multibyte_characters_split_across_chunks() {
+        let mut parser = SseParser::default();
+        let body = "data: café 🎉\n\n".as_bytes();
+        let split = body.iter().position(|b| *b == 0xC3).unwrap() + 1;
+        let emoji = body.iter().position(|b| *b == 0xF0).unwrap() + 2;
+
+        assert!(parser.push(&body[..split]).is_empty());
+        assert!(parser.push(&body[split..emoji]).is_empty());
+        let events = parser.push(&body[emoji..]);
+        assert_eq!(events[0].data, "café 🎉");
+    }
+}
@@ -0,0 +1,1325 @@
+//! In-process mock of the Linktor API for integration tests.
+//!
+//! Enabled with the `testing` feature. [`MockLinktor`] serves canned fixtures
+//! for every resource, lets tests override individual routes, and records each
+//! request so it can be asserted on afterwards.
+//!
+//! ```rust,no_run
+//! # async fn run() -> linktor::Result<()> {
+//! use linktor::testing::MockLinktor;
+//!
+//! let mock = MockLinktor::start().await;
+//! mock.on("POST", "/conversations/*/messages", 201, linktor::testing::fixtures::message());
+//!
+//! let client = mock.client();
+//! client.conversations().send_text("conv-1", "Hello").await?;
+//!
+//! assert_eq!(mock.requests_to("POST", "/conversations/conv-1/messages").len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::LinktorClient;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// A request received by the mock server.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// Header value, looked up case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    pub fn json(&self) -> Option<serde_json::Value> {
+        serde_json::from_slice(&self.body).ok()
+    }
+}
+
+/// A canned response served for matching requests.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    pub fn json(status: u16, body: impl Serialize) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: serde_json::to_string(&body).expect("mock body must serialize"),
+        }
+    }
+
+    pub fn empty(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    /// A `text/event-stream` body, written verbatim; the connection closes
+    /// after it, so omitting a final event exercises reconnects.
+    pub fn event_stream(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "text/event-stream".to_string())],
+            body: body.into(),
+        }
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Route {
+    method: String,
+    pattern: String,
+    response: MockResponse,
+}
+
+#[derive(Default)]
+struct State {
+    routes: Vec<Route>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// Mock Linktor API server bound to a random local port.
+pub struct MockLinktor {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    task: JoinHandle<()>,
+}
+
+impl MockLinktor {
+    /// Start a server preloaded with fixtures for every resource.
+    pub async fn start() -> Self {
+        let mock = Self::start_empty().await;
+        mock.install_default_fixtures();
+        mock
+    }
+
+    /// Start a server with no routes; unmatched requests receive 404.
+    pub async fn start_empty() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind mock server");
+        let addr = listener.local_addr().expect("mock server has no address");
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let accept_state = state.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = accept_state.clone();
+                tokio::spawn(async move {
+                    let _ = handle_connection(stream, state).await;
+                });
+            }
+        });
+
+        Self { addr, state, task }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Client pointed at the mock with a test API key and retries disabled.
+    pub fn client(&self) -> LinktorClient {
+        LinktorClient::builder()
+            .base_url(self.url())
+            .api_key("test-api-key")
+            .max_retries(1)
+            .build()
+            .expect("failed to build mock client")
+    }
+
+    /// Serve `body` as JSON for requests matching `method` and `pattern`.
+    /// `*` in the pattern matches a single path segment. Routes registered
+    /// later take precedence over earlier ones and over the default fixtures.
+    pub fn on(&self, method: &str, pattern: &str, status: u16, body: impl Serialize) {
+        self.on_response(method, pattern, MockResponse::json(status, body));
+    }
+
+    pub fn on_response(&self, method: &str, pattern: &str, response: MockResponse) {
+        self.state.lock().unwrap().routes.push(Route {
+            method: method.to_ascii_uppercase(),
+            pattern: pattern.to_string(),
+            response,
+        });
+    }
+
+    /// Every request received so far, in arrival order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Requests received for an exact method and path (query string excluded).
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method.eq_ignore_ascii_case(method) && r.path == path)
+            .collect()
+    }
+
+    pub fn reset_requests(&self) {
+        self.state.lock().unwrap().requests.clear();
+    }
+
+    fn install_default_fixtures(&self) {
+        use fixtures::*;
+
+        self.on("POST", "/auth/login", 200, login_response());
+        self.on("POST", "/auth/refresh", 200, refresh_token_response());
+        self.on("POST", "/auth/logout", 200, serde_json::json!({}));
+        self.on("GET", "/auth/me", 200, user());
+        self.on("GET", "/auth/tenants", 200, vec![tenant_membership()]);
+        self.on("POST", "/auth/oauth/token", 200, oauth_token_response());
+        self.on("POST", "/auth/token", 200, client_credentials_token());
+        self.on("POST", "/auth/mfa/challenge", 200, login_response());
+        self.on("POST", "/auth/mfa/enroll", 200, mfa_enrollment());
+        self.on("POST", "/auth/mfa/verify", 200, mfa_verification());
+        self.on("POST", "/auth/mfa/disable", 200, serde_json::json!({}));
+        self.on("POST", "/auth/password/reset-request", 202, serde_json::json!({}));
+        self.on("POST", "/auth/password/reset", 200, serde_json::json!({}));
+        self.on("POST", "/auth/password/change", 200, serde_json::json!({}));
+        self.on("GET", "/auth/tenant", 200, tenant());
+
+        self.crud("/conversations", conversation());
+        self.on("GET", "/conversations/*/messages", 200, page(message()));
+        self.on("POST", "/conversations/*/messages", 201, message());
+        self.on("POST", "/conversations/*/resolve", 200, conversation());
+        self.on("POST", "/conversations/*/reopen", 200, conversation());
+        self.on("GET", "/conversations/*/history", 200, Vec::<serde_json::Value>::new());
+        self.on(
+            "POST",
+            "/conversations/*/close",
+            200,
+            crate::types::Conversation { status: crate::types::ConversationStatus::Closed, ..conversation() },
+        );
+        self.on("POST", "/conversations/*/assign", 200, conversation());
+        self.on("POST", "/conversations/*/claim", 200, conversation());
+        self.on("POST", "/conversations/*/bot/pause", 200, conversation());
+        self.on("POST", "/conversations/*/bot/resume", 200, conversation());
+        self.on("POST", "/conversations/*/compliance-notice", 200, compliance_notice());
+        self.on("GET", "/conversations/*/state", 200, conversation_snapshot());
+        self.on("POST", "/conversations/statuses/reconcile", 200, reconciliation_report());
+        self.on("GET", "/conversations/*/compliance-message", 200, compliance_message_config());
+        self.on("PUT", "/conversations/*/compliance-message", 200, compliance_message_config());
+        self.on_response("DELETE", "/conversations/*/compliance-message", MockResponse::empty(204));
+
+        self.crud("/users", user());
+        self.on("POST", "/users/invite", 201, user());
+        self.on("POST", "/users/*/invite/resend", 200, user());
+        self.on("POST", "/users/*/deactivate", 200, user());
+        self.on("POST", "/users/*/reactivate", 200, user());
+        self.on("PUT", "/users/*/skills", 200, user());
+
+        self.crud("/contacts", contact());
+        self.on("PUT", "/contacts/*/avatar", 200, contact_avatar());
+        self.on("POST", "/contacts/merge", 200, contact_merge_result());
+        self.on("POST", "/contacts/*/erase", 202, contact_erasure());
+        self.on("GET", "/contacts/*/erasures/*", 200, completed_contact_erasure());
+        self.on("POST", "/contacts/*/avatar/refresh", 200, contact_avatar());
+        self.crud("/channels", channel());
+        self.on("POST", "/channels/*/connect", 200, channel());
+        self.on("POST", "/channels/*/disconnect", 200, channel());
+        self.on("GET", "/channels/*/status", 200, channel_status());
+        self.on("GET", "/channels/*/limits", 200, channel_limits());
+        self.on("POST", "/channels/*/test", 200, connection_test());
+        self.on("GET", "/channels/*/analytics", 200, channel_analytics());
+        self.on("POST", "/channels/*/test-message", 200, test_message_result());
+        self.on("POST", "/channels/*/clone", 201, cloned_channel());
+        self.on("GET", "/channels/*/webchat", 200, webchat_settings());
+        self.on("PUT", "/channels/*/webchat", 200, webchat_settings());
+        self.on("POST", "/channels/*/verification", 200, verification_request());
+        self.on("POST", "/channels/*/reauthorize", 201, reauthorization_session());
+        self.on(
+            "GET",
+            "/channels/*/reauthorize/*",
+            200,
+            crate::types::ReauthorizationSession {
+                status: crate::types::ReauthorizationStatus::Completed,
+                ..reauthorization_session()
+            },
+        );
+        self.on("POST", "/channels/*/verification/verify", 200, channel());
+        self.on("GET", "/channels/*/webhook", 200, webhook_config());
+        self.on("PUT", "/channels/*/webhook", 200, webhook_config());
+        self.on("GET", "/channels/*/compliance-message", 200, compliance_message_config());
+        self.on("PUT", "/channels/*/compliance-message", 200, compliance_message_config());
+        self.on_response("DELETE", "/channels/*/compliance-message", MockResponse::empty(204));
+        self.crud("/bots", bot());
+        self.on("POST", "/bots/*/activate", 200, serde_json::json!({ "message": "Bot activated" }));
+        self.on("POST", "/bots/*/deactivate", 200, serde_json::json!({ "message": "Bot deactivated" }));
+        self.on("POST", "/bots/*/pause", 200, serde_json::json!({ "message": "Bot paused" }));
+        self.on("POST", "/bots/*/simulate", 200, bot_simulation());
+        self.on("GET", "/bots/*/intents", 200, vec![intent()]);
+        self.on("POST", "/bots/*/intents", 201, intent());
+        self.on("PUT", "/bots/*/intents", 200, vec![intent()]);
+        self.on("GET", "/bots/*/intents/*", 200, intent());
+        self.on("PUT", "/bots/*/intents/*", 200, intent());
+        self.on_response("DELETE", "/bots/*/intents/*", MockResponse::empty(204));
+        self.on("POST", "/bots/*/detect-intent", 200, intent_detection());
+        self.on("GET", "/bots/*/schedule", 200, bot_schedule());
+        self.on("PUT", "/bots/*/schedule", 200, bot_schedule());
+        self.on("POST", "/bots/*/channels", 200, serde_json::json!({ "message": "Channel assigned to bot" }));
+        self.on("DELETE", "/bots/*/channels/*", 200, serde_json::json!({ "message": "Channel unassigned from bot" }));
+        self.on(
+            "POST",
+            "/bots/*/duplicate",
+            201,
+            crate::types::Bot {
+                id: "bot-2".into(),
+                name: "Test Bot (copy)".to_string(),
+                status: crate::types::BotStatus::Draft,
+                channel_ids: Vec::new(),
+                ..bot()
+            },
+        );
+
+        self.crud("/ai/agents", agent());
+        self.on("POST", "/ai/completions", 200, completion());
+        self.on("POST", "/ai/embeddings", 200, embedding());
+        self.on("POST", "/ai/conversations/*/score", 200, conversation_score());
+
+        self.crud("/knowledge-bases", knowledge_base());
+        self.on("POST", "/knowledge-bases/*/documents", 201, document());
+        self.on("POST", "/knowledge-bases/*/query", 200, query_result());
+
+        self.on("POST", "/vre/render", 200, vre_render_response());
+        self.on("POST", "/media", 201, uploaded_media());
+        self.on("GET", "/broadcasts", 200, page(broadcast()));
+        self.on("POST", "/broadcasts", 201, broadcast());
+        self.on("GET", "/broadcasts/*", 200, broadcast());
+        self.on(
+            "POST",
+            "/broadcasts/*/cancel",
+            200,
+            crate::types::Broadcast { status: crate::types::BroadcastStatus::Cancelled, ..broadcast() },
+        );
+        self.on("GET", "/broadcasts/*/recipients", 200, page(broadcast_recipient()));
+        self.on("GET", "/campaigns/*/contacts/*/preview", 200, campaign_preview());
+        self.on("POST", "/conversations/*/scheduled-messages", 201, scheduled_message());
+        self.on("GET", "/conversations/*/scheduled-messages", 200, page(scheduled_message()));
+        self.on(
+            "DELETE",
+            "/conversations/*/scheduled-messages/*",
+            200,
+            crate::types::ScheduledMessage { status: crate::types::ScheduledMessageStatus::Cancelled, ..scheduled_message() },
+        );
+        self.on("PUT", "/conversations/*/autopilot", 200, autopilot_state());
+        self.on("GET", "/conversations/*/messages/*", 200, message());
+        self.on("POST", "/conversations/*/messages/*/forward", 201, forwarded_message());
+        self.on_response("DELETE", "/conversations/*/messages/*", MockResponse::empty(204));
+        self.on("POST", "/conversations/*/messages/*/reactions", 200, serde_json::json!({ "message": "Reaction added successfully" }));
+        self.on("POST", "/conversations/*/typing", 200, serde_json::json!({ "message": "Typing indicator sent" }));
+        self.on("POST", "/conversations/*/read", 200, conversation());
+        self.on("POST", "/conversations/*/summarize", 200, conversation_summary());
+        self.on(
+            "POST",
+            "/conversations/*/external-references",
+            200,
+            crate::types::Conversation {
+                external_references: vec![crate::types::ExternalReference::new("jira", "SUP-1234")],
+                ..conversation()
+            },
+        );
+        self.on("DELETE", "/conversations/*/external-references", 200, conversation());
+        self.on("POST", "/conversations/*/messages/read", 200, serde_json::json!({ "message": "Messages marked as read" }));
+        self.on("DELETE", "/conversations/*/autopilot", 200, crate::types::AutopilotState { overridden: false, ..autopilot_state() });
+        self.on("GET", "/webhooks/*/missed-events", 200, missed_events_page());
+        self.on("POST", "/vre/transcripts/pdf", 200, serde_json::json!({ "pdf_base64": "JVBERi0xLjcK" }));
+
+        self.crud("/flows", flow());
+        self.on("POST", "/flows/*/execute", 200, flow_execution());
+        self.on_response(
+            "GET",
+            "/flows/executions/*/events",
+            MockResponse::event_stream(format!(
+                "id: 1\nevent: step\ndata: {}\n\nid: 2\nevent: end\ndata: {{}}\n\n",
+                serde_json::to_string(&flow_execution_step()).expect("fixture must serialize")
+            )),
+        );
+    }
+
+    fn crud(&self, base: &str, item: impl Serialize + Clone) {
+        let item_path = format!("{}/*", base);
+        self.on("GET", base, 200, fixtures::page(item.clone()));
+        self.on("POST", base, 201, item.clone());
+        self.on("GET", &item_path, 200, item.clone());
+        self.on("PATCH", &item_path, 200, item);
+        self.on_response("DELETE", &item_path, MockResponse::empty(204));
+    }
+}
+
+impl Drop for MockLinktor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.trim_matches('/').split('/').collect();
+    let path: Vec<&str> = path.trim_matches('/').split('/').collect();
+    pattern.len() == path.len() && pattern.iter().zip(&path).all(|(p, s)| *p == "*" || p == s)
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("Unknown")
+}
+
+async fn handle_connection(mut stream: TcpStream, state: Arc<Mutex<State>>) -> std::io::Result<()> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or("/").to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .get("content-length")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = buffer[header_end..].to_vec();
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+
+    let (path, query) = match target.split_once('?') {
+        Some((p, q)) => (p.to_string(), Some(q.to_string())),
+        None => (target, None),
+    };
+
+    let response = {
+        let mut state = state.lock().unwrap();
+        let response = state
+            .routes
+            .iter()
+            .rev()
+            .find(|r| r.method == method && matches(&r.pattern, &path))
+            .map(|r| r.response.clone())
+            .unwrap_or_else(|| {
+                MockResponse::json(
+                    404,
+                    serde_json::json!({"code": "NOT_FOUND", "message": format!("No mock route for {} {}", method, path)}),
+                )
+            });
+        state.requests.push(RecordedRequest {
+            method,
+            path,
+            query,
+            headers,
+            body,
+        });
+        response
+    };
+
+    let mut out = format!("HTTP/1.1 {} {}\r\n", response.status, reason_phrase(response.status));
+    for (name, value) in &response.headers {
+        out.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    out.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", response.body.len()));
+    out.push_str(&response.body);
+    stream.write_all(out.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Canned, fully populated values for every resource type.
+pub mod fixtures {
+    use crate::types::*;
+    use chrono::{DateTime, Utc};
+
+    fn timestamp() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).expect("valid fixture timestamp")
+    }
+
+    pub fn page<T>(item: T) -> PaginatedResponse<T> {
+        PaginatedResponse {
+            data: vec![item],
+            pagination: PaginationMeta {
+                total: 1,
+                page: 1,
+                limit: 20,
+                total_pages: 1,
+                has_more: false,
+                next_cursor: None,
+                prev_cursor: None,
+            },
+        }
+    }
+
+    pub fn user() -> User {
+        User {
+            id: "user-1".into(),
+            tenant_id: "tenant-1".into(),
+            email: "agent@example.com".to_string(),
+            name: "Test Agent".to_string(),
+            avatar: None,
+            role: UserRole::Agent,
+            status: UserStatus::Active,
+            preferences: None,
+            metadata: None,
+            skills: Vec::new(),
+            max_concurrent_conversations: None,
+            last_login_at: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn tenant() -> Tenant {
+        Tenant {
+            id: "tenant-1".into(),
+            name: "Test Tenant".to_string(),
+            slug: "test-tenant".to_string(),
+            plan: None,
+            settings: None,
+            metadata: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn client_credentials_token() -> ClientCredentialsToken {
+        ClientCredentialsToken {
+            access_token: "service-access-token".to_string(),
+            expires_in: 3600,
+            scope: None,
+        }
+    }
+
+    pub fn vre_render_response() -> VRERenderResponse {
+        VRERenderResponse {
+            image_base64: "iVBORw0KGgo=".to_string(),
+            caption: "Rendered".to_string(),
+            width: 800,
+            height: 600,
+            format: VREOutputFormat::Png,
+            render_time_ms: 12,
+            size_bytes: None,
+            cache_hit: None,
+        }
+    }
+
+    pub fn campaign_preview() -> CampaignPreview {
+        CampaignPreview {
+            campaign_id: "campaign-1".into(),
+            contact_id: "contact-1".into(),
+            channel_type: ChannelType::Whatsapp,
+            message_type: MessageType::Text,
+            rendered_text: Some("Hi Ana, your Gold discount ends Friday.".to_string()),
+            variables: std::collections::HashMap::from([
+                ("first_name".to_string(), "Ana".to_string()),
+                ("tier".to_string(), "Gold".to_string()),
+            ]),
+            missing_variables: vec!["city".to_string()],
+        }
+    }
+
+    pub fn broadcast() -> Broadcast {
+        Broadcast {
+            id: "broadcast-1".into(),
+            tenant_id: "tenant-1".into(),
+            name: "Black Friday".to_string(),
+            channel_id: "channel-1".into(),
+            status: BroadcastStatus::Sending,
+            message: SendMessageInput::text("20% off everything today"),
+            send_at: None,
+            throttle_per_minute: Some(600),
+            stats: BroadcastStats {
+                total: 3,
+                pending: 1,
+                sent: 1,
+                delivered: 1,
+                ..Default::default()
+            },
+            started_at: Some(timestamp()),
+            completed_at: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn broadcast_recipient() -> BroadcastRecipient {
+        BroadcastRecipient {
+            contact_id: "contact-1".into(),
+            status: MessageStatus::Delivered,
+            message_id: Some("msg-1".into()),
+            error: None,
+            sent_at: Some(timestamp()),
+        }
+    }
+
+    pub fn uploaded_media() -> UploadedMedia {
+        UploadedMedia {
+            id: "media-1".into(),
+            url: "https://files.linktor.io/media/media-1".to_string(),
+            mime_type: "image/png".to_string(),
+            filename: Some("image.png".to_string()),
+            size: 8,
+            expires_at: None,
+        }
+    }
+
+    pub fn missed_event(id: &str) -> MissedEvent {
+        MissedEvent {
+            event: WebhookEvent {
+                id: id.to_string(),
+                event_type: "message.received".to_string(),
+                timestamp: timestamp(),
+                tenant_id: "tenant-1".into(),
+                data: Some(std::collections::HashMap::from([("message".to_string(), serde_json::to_value(message()).unwrap())])),
+            },
+            status: DeliveryStatus::Failed,
+            attempts: 5,
+            last_error: Some("connection refused".to_string()),
+            last_attempt_at: Some(timestamp()),
+            cursor: format!("cursor-{}", id),
+        }
+    }
+
+    pub fn missed_events_page() -> MissedEventsPage {
+        MissedEventsPage {
+            data: vec![missed_event("evt-1"), missed_event("evt-2")],
+            next_cursor: None,
+            has_more: false,
+        }
+    }
+
+    pub fn scheduled_message() -> ScheduledMessage {
+        ScheduledMessage {
+            id: "sched-1".into(),
+            conversation_id: "conv-1".into(),
+            message: SendMessageInput::text("Reminder: your appointment is tomorrow"),
+            send_at: timestamp() + chrono::Duration::days(1),
+            status: ScheduledMessageStatus::Pending,
+            sent_message_id: None,
+            error: None,
+            created_at: timestamp(),
+        }
+    }
+
+    pub fn autopilot_state() -> AutopilotState {
+        AutopilotState {
+            config: AutopilotConfig::enabled(0.85).escalation_tag("needs-human"),
+            overridden: true,
+        }
+    }
+
+    pub fn tenant_membership() -> TenantMembership {
+        TenantMembership {
+            tenant: tenant(),
+            role: UserRole::Admin,
+            is_default: true,
+        }
+    }
+
+    pub fn login_response() -> LoginResponse {
+        LoginResponse {
+            user: user(),
+            tenant: tenant(),
+            access_token: "test-access-token".to_string(),
+            refresh_token: "test-refresh-token".to_string(),
+            expires_in: 3600,
+        }
+    }
+
+    pub fn refresh_token_response() -> RefreshTokenResponse {
+        RefreshTokenResponse {
+            access_token: "test-access-token-2".to_string(),
+            refresh_token: "test-refresh-token-2".to_string(),
+            expires_in: 3600,
+        }
+    }
+
+    pub fn conversation() -> Conversation {
+        Conversation {
+            id: "conv-1".into(),
+            tenant_id: "tenant-1".into(),
+            channel_id: "channel-1".into(),
+            contact_id: "contact-1".into(),
+            assigned_agent_id: None,
+            bot_id: None,
+            status: ConversationStatus::Open,
+            priority: None,
+            subject: None,
+            last_message: None,
+            unread_count: 0,
+            tags: Vec::new(),
+            metadata: None,
+            first_message_at: None,
+            last_message_at: None,
+            resolved_at: None,
+            bot_paused: false,
+            bot_paused_until: None,
+            autopilot: None,
+            external_references: Vec::new(),
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn conversation_snapshot() -> ConversationSnapshot {
+        ConversationSnapshot {
+            conversation_id: "conv-1".into(),
+            at: timestamp(),
+            status: ConversationStatus::Pending,
+            assigned_agent_id: Some("agent-1".into()),
+            priority: Some(ConversationPriority::High),
+            tags: vec!["billing".to_string()],
+            bot_paused: false,
+            last_changed_at: Some(timestamp() - chrono::Duration::minutes(5)),
+        }
+    }
+
+    pub fn reconciliation_report() -> ReconciliationReport {
+        ReconciliationReport {
+            checked: 10,
+            corrected: vec![StatusCorrection {
+                message_id: "msg-1".into(),
+                conversation_id: "conv-1".into(),
+                previous_status: MessageStatus::Sent,
+                status: MessageStatus::Delivered,
+                provider_updated_at: Some(timestamp()),
+            }],
+            failed: vec![ReconcileFailure {
+                message_id: "msg-2".into(),
+                reason: "message expired at provider".to_string(),
+            }],
+            started_at: timestamp(),
+            completed_at: timestamp(),
+        }
+    }
+
+    pub fn message() -> Message {
+        Message {
+            id: "msg-1".into(),
+            conversation_id: "conv-1".into(),
+            message_type: MessageType::Text,
+            direction: MessageDirection::Outbound,
+            status: MessageStatus::Sent,
+            text: Some("Hello!".to_string()),
+            media: None,
+            location: None,
+            contact: None,
+            template: None,
+            interactive: None,
+            sender_id: None,
+            sender_type: None,
+            external_id: None,
+            metadata: None,
+            reactions: Vec::new(),
+            receipts: Vec::new(),
+            translation: None,
+            forwarded_from: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn forwarded_message() -> Message {
+        Message {
+            id: "msg-2".into(),
+            conversation_id: "conv-2".into(),
+            sender_id: Some("user-1".to_string()),
+            sender_type: Some("agent".to_string()),
+            forwarded_from: Some(ForwardedFrom {
+                conversation_id: "conv-1".into(),
+                message_id: "msg-1".into(),
+                sender_id: Some("contact-1".to_string()),
+                sender_type: Some("contact".to_string()),
+                sender_name: Some("Test Contact".to_string()),
+                sent_at: timestamp(),
+                forwarded_by: Some("user-1".into()),
+            }),
+            ..message()
+        }
+    }
+
+    pub fn contact_erasure() -> ContactErasure {
+        ContactErasure {
+            id: "erasure-1".into(),
+            contact_id: "contact-1".into(),
+            status: ErasureStatus::Pending,
+            regulation: None,
+            reason: None,
+            messages_redacted: 0,
+            identifiers_removed: 0,
+            error: None,
+            requested_at: timestamp(),
+            completed_at: None,
+        }
+    }
+
+    pub fn completed_contact_erasure() -> ContactErasure {
+        ContactErasure {
+            status: ErasureStatus::Completed,
+            messages_redacted: 42,
+            identifiers_removed: 3,
+            completed_at: Some(timestamp()),
+            ..contact_erasure()
+        }
+    }
+
+    pub fn contact_merge_result() -> ContactMergeResult {
+        ContactMergeResult {
+            contact: contact(),
+            merged_contact_ids: vec!["contact-2".into()],
+            moved_conversation_ids: vec!["conv-2".into()],
+            moved_identifiers: std::collections::HashMap::from([("telegram".to_string(), "123456789".to_string())]),
+        }
+    }
+
+    pub fn contact() -> Contact {
+        Contact {
+            id: "contact-1".into(),
+            tenant_id: "tenant-1".into(),
+            name: Some("Test Contact".to_string()),
+            email: Some("contact@example.com".to_string()),
+            phone: Some("+5511999999999".to_string()),
+            avatar: None,
+            identifiers: None,
+            custom_fields: None,
+            tags: Vec::new(),
+            metadata: None,
+            last_seen_at: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn contact_avatar() -> ContactAvatar {
+        ContactAvatar {
+            url: "https://cdn.linktor.io/avatars/contact-1.png".to_string(),
+            channel_id: None,
+            updated_at: Some(timestamp()),
+        }
+    }
+
+    pub fn webhook_config() -> WebhookConfig {
+        WebhookConfig::new("https://example.com/hooks/linktor", "whsec_test").events(["message.received"])
+    }
+
+    pub fn reauthorization_session() -> ReauthorizationSession {
+        ReauthorizationSession {
+            id: "reauth-1".into(),
+            channel_id: "channel-1".into(),
+            auth_url: "https://www.facebook.com/v19.0/dialog/oauth?state=reauth-1".to_string(),
+            status: ReauthorizationStatus::Pending,
+            expires_at: timestamp(),
+            error: None,
+            completed_at: None,
+        }
+    }
+
+    pub fn verification_request() -> VerificationRequest {
+        VerificationRequest {
+            channel_id: "channel-1".into(),
+            method: VerificationMethod::Sms,
+            phone_number: Some("+55 11 9****-9999".to_string()),
+            expires_at: Some(timestamp()),
+            retry_after: Some(timestamp()),
+        }
+    }
+
+    pub fn webchat_settings() -> WebchatSettings {
+        WebchatSettings::new()
+            .title("Chat with us")
+            .colors("#6366f1", "#ffffff")
+            .position(WidgetPosition::BottomRight)
+            .welcome_message("Hi! How can we help?")
+            .pre_chat_field(PreChatField::new("email", "Email", PreChatFieldType::Email).required())
+    }
+
+    pub fn cloned_channel() -> ClonedChannel {
+        ClonedChannel {
+            channel: Channel {
+                id: "channel-2".into(),
+                tenant_id: "tenant-2".into(),
+                status: ChannelStatus::Disconnected,
+                ..channel()
+            },
+            reset_credentials: vec!["accessToken".to_string()],
+        }
+    }
+
+    pub fn test_message_result() -> TestMessageResult {
+        TestMessageResult {
+            channel_id: "channel-1".into(),
+            recipient: "+5511999999999".to_string(),
+            status: MessageStatus::Sent,
+            external_id: Some("wamid.test".to_string()),
+            error: None,
+            sent_at: timestamp(),
+        }
+    }
+
+    pub fn channel_analytics() -> ChannelAnalytics {
+        let date = timestamp().date_naive();
+        let counts = MessageCounts {
+            sent: 200,
+            received: 150,
+            delivered: 190,
+            read: 120,
+            failed: 10,
+        };
+        ChannelAnalytics {
+            channel_id: "channel-1".into(),
+            from: date,
+            to: date,
+            totals: counts.clone(),
+            days: vec![ChannelAnalyticsDay {
+                date,
+                counts,
+                errors: std::collections::HashMap::from([("131026".to_string(), 10)]),
+            }],
+        }
+    }
+
+    pub fn connection_test() -> ConnectionTest {
+        let check = |name: &str, latency_ms: u64| ConnectionCheck {
+            name: name.to_string(),
+            success: true,
+            message: None,
+            latency_ms: Some(latency_ms),
+        };
+        ConnectionTest {
+            success: true,
+            message: None,
+            checks: vec![check("smtp", 120), check("imap", 95), check("round_trip", 2400)],
+        }
+    }
+
+    pub fn channel_limits() -> ChannelLimits {
+        ChannelLimits {
+            channel_id: "channel-1".into(),
+            tier: Some("TIER_1K".to_string()),
+            daily_limit: Some(1000),
+            used_last_24h: 940,
+            remaining: Some(60),
+            quality_rating: Some(QualityRating::Yellow),
+            resets_at: Some(timestamp()),
+        }
+    }
+
+    pub fn channel_status() -> ChannelStatusResponse {
+        ChannelStatusResponse {
+            status: ChannelStatus::Connected,
+            error_message: None,
+            connected_at: Some(timestamp()),
+            last_activity_at: Some(timestamp()),
+        }
+    }
+
+    pub fn channel() -> Channel {
+        Channel {
+            id: "channel-1".into(),
+            tenant_id: "tenant-1".into(),
+            name: "Test WhatsApp".to_string(),
+            channel_type: ChannelType::Whatsapp,
+            status: ChannelStatus::Connected,
+            config: None,
+            metadata: None,
+            error_message: None,
+            connected_at: Some(timestamp()),
+            last_activity_at: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn bot() -> Bot {
+        Bot {
+            id: "bot-1".into(),
+            tenant_id: "tenant-1".into(),
+            name: "Test Bot".to_string(),
+            description: None,
+            status: BotStatus::Active,
+            bot_type: BotType::Ai,
+            config: None,
+            channel_ids: vec!["channel-1".into()],
+            flow_id: None,
+            agent_id: Some("agent-1".into()),
+            knowledge_base_ids: Vec::new(),
+            metadata: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn bot_simulation() -> BotSimulation {
+        BotSimulation {
+            bot_id: "bot-1".into(),
+            turns: vec![SimulationTurn {
+                input: "Where is my order 1234?".to_string(),
+                replies: vec!["Order 1234 ships tomorrow.".to_string()],
+                steps: vec![
+                    SimulationStep::FlowNode {
+                        flow_id: "flow-1".into(),
+                        node_id: "node-1".to_string(),
+                        node_type: "message".to_string(),
+                    },
+                    SimulationStep::AiCall {
+                        model: "gpt-4".to_string(),
+                        tokens_used: 120,
+                        latency_ms: 850,
+                    },
+                    SimulationStep::ToolCall {
+                        name: "lookup_order".to_string(),
+                        arguments: serde_json::json!({ "orderId": "1234" }),
+                        result: Some(serde_json::json!({ "status": "packed" })),
+                        error: None,
+                    },
+                ],
+                intent: Some("order_status".to_string()),
+                escalated: false,
+            }],
+            tokens_used: 120,
+            duration_ms: 900,
+        }
+    }
+
+    pub fn bot_schedule() -> BotSchedule {
+        BotSchedule::new("America/Sao_Paulo")
+            .day(chrono::Weekday::Mon, "09:00", "18:00")
+            .day(chrono::Weekday::Tue, "09:00", "18:00")
+            .off_hours_reply("We're closed, an agent will reply in the morning.")
+            .handoff_to(["user-1"])
+    }
+
+    pub fn intent() -> Intent {
+        Intent {
+            updated_at: Some(timestamp()),
+            ..Intent::new("order_status")
+                .phrase("Where is my order?")
+                .phrase("Has order 1234 shipped?")
+                .entity(IntentEntity::new("order_number", EntityKind::Number).required())
+        }
+    }
+
+    pub fn intent_detection() -> IntentDetection {
+        IntentDetection {
+            intent: Some(IntentMatch {
+                name: "order_status".to_string(),
+                confidence: 0.93,
+            }),
+            entities: std::collections::HashMap::from([("order_number".to_string(), "1234".to_string())]),
+            alternatives: vec![IntentMatch {
+                name: "cancel_order".to_string(),
+                confidence: 0.41,
+            }],
+        }
+    }
+
+    pub fn agent() -> Agent {
+        Agent {
+            id: "agent-1".into(),
+            tenant_id: "tenant-1".into(),
+            name: "Test Agent".to_string(),
+            description: None,
+            status: AgentStatus::Active,
+            model: "gpt-4".to_string(),
+            system_prompt: None,
+            temperature: 0.7,
+            max_tokens: 1024,
+            knowledge_base_ids: Vec::new(),
+            tools: Vec::new(),
+            metadata: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn completion() -> CompletionResponse {
+        CompletionResponse {
+            id: "cmpl-1".to_string(),
+            object: "chat.completion".to_string(),
+            created: timestamp().timestamp(),
+            model: "gpt-4".to_string(),
+            choices: vec![Choice {
+                index: 0,
+                message: Some(ChatMessage::assistant("4")),
+                finish_reason: Some("stop".to_string()),
+            }],
+            usage: None,
+            provider: None,
+            latency_ms: None,
+            cached: None,
+            safety: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn conversation_summary() -> ConversationSummary {
+        ConversationSummary {
+            conversation_id: "conv-1".into(),
+            summary: "Customer asked for a refund on a late order; agent issued it.".to_string(),
+            key_points: vec!["Order arrived 5 days late".to_string(), "Refund issued".to_string()],
+            sentiment: Sentiment::Positive,
+            sentiment_score: Some(0.6),
+            suggested_tags: vec!["refund".to_string(), "shipping".to_string()],
+            model: None,
+            generated_at: timestamp(),
+        }
+    }
+
+    pub fn conversation_score() -> ConversationScore {
+        let criterion = |name: &str, score: f64, quote: &str| CriterionScore {
+            name: name.to_string(),
+            score,
+            rationale: None,
+            evidence: vec![ScoreEvidence {
+                quote: quote.to_string(),
+                message_id: Some("msg-1".into()),
+            }],
+        };
+        ConversationScore {
+            conversation_id: "conv-1".into(),
+            overall: 4.0,
+            scale: 5,
+            criteria: vec![
+                criterion("empathy", 4.0, "I understand how frustrating that is"),
+                criterion("resolution", 5.0, "Your refund has been issued"),
+                criterion("policyAdherence", 3.0, "I can waive the fee this time"),
+            ],
+            summary: None,
+            model: None,
+            scored_at: Some(timestamp()),
+        }
+    }
+
+    pub fn embedding() -> EmbeddingResponse {
+        EmbeddingResponse {
+            object: "list".to_string(),
+            data: vec![EmbeddingData {
+                object: "embedding".to_string(),
+                index: 0,
+                embedding: vec![0.1, 0.2, 0.3],
+            }],
+            model: "text-embedding-3-small".to_string(),
+            usage: None,
+        }
+    }
+
+    pub fn knowledge_base() -> KnowledgeBase {
+        KnowledgeBase {
+            id: "kb-1".into(),
+            tenant_id: "tenant-1".into(),
+            name: "Test KB".to_string(),
+            description: None,
+            status: KnowledgeBaseStatus::Active,
+            embedding_model: "text-embedding-3-small".to_string(),
+            chunk_size: 512,
+            chunk_overlap: 64,
+            document_count: 1,
+            total_chunks: 1,
+            metadata: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn document() -> Document {
+        Document {
+            id: "doc-1".to_string(),
+            knowledge_base_id: "kb-1".into(),
+            name: "FAQ".to_string(),
+            doc_type: "text".to_string(),
+            source_url: None,
+            status: DocumentStatus::Completed,
+            size: 42,
+            chunk_count: 1,
+            namespace: None,
+            metadata: None,
+            error: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn query_result() -> QueryResult {
+        QueryResult {
+            chunks: vec![ScoredChunk {
+                id: "chunk-1".to_string(),
+                document_id: "doc-1".to_string(),
+                content: "Answer: reset it from the settings page.".to_string(),
+                chunk_index: 0,
+                token_count: 9,
+                score: 0.92,
+                metadata: None,
+                document: None,
+            }],
+            query: "How to reset password?".to_string(),
+            model: "text-embedding-3-small".to_string(),
+        }
+    }
+
+    pub fn flow() -> Flow {
+        Flow {
+            id: "flow-1".into(),
+            tenant_id: "tenant-1".into(),
+            name: "Test Flow".to_string(),
+            description: None,
+            status: FlowStatus::Active,
+            version: 1,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            variables: Vec::new(),
+            metadata: None,
+            created_at: timestamp(),
+            updated_at: timestamp(),
+        }
+    }
+
+    pub fn flow_execution() -> FlowExecution {
+        FlowExecution {
+            id: "exec-1".to_string(),
+            flow_id: "flow-1".into(),
+            conversation_id: "conv-1".into(),
+            status: FlowExecutionStatus::Running,
+            current_node_id: None,
+            variables: None,
+            history: Vec::new(),
+            started_at: timestamp(),
+            completed_at: None,
+            error: None,
+        }
+    }
+
+    pub fn mfa_enrollment() -> MfaEnrollment {
+        MfaEnrollment {
+            secret: "JBSWY3DPEHPK3PXP".to_string(),
+            otpauth_url: "otpauth://totp/Linktor:user%40example.com?secret=JBSWY3DPEHPK3PXP&issuer=Linktor".to_string(),
+        }
+    }
+
+    pub fn mfa_verification() -> MfaVerification {
+        MfaVerification {
+            enabled: true,
+            recovery_codes: vec!["a1b2-c3d4".to_string(), "e5f6-g7h8".to_string()],
+        }
+    }
+
+    pub fn oauth_token_response() -> OAuthTokenResponse {
+        OAuthTokenResponse {
+            access_token: "oauth-access-token".to_string(),
+            refresh_token: "oauth-refresh-token".to_string(),
+            expires_in: 3600,
+            scope: Some("conversations:read".to_string()),
+        }
+    }
+
+    pub fn compliance_message_config() -> ComplianceMessageConfig {
+        ComplianceMessageConfig::new("We process your data under our privacy policy.")
+            .regulation(ComplianceRegulation::Lgpd)
+            .translation("pt-BR", "Tratamos seus dados conforme nossa política de privacidade.")
+            .privacy_policy_url("https://example.com/privacy")
+            .version("2024-01")
+    }
+
+    pub fn compliance_notice() -> ComplianceNotice {
+        ComplianceNotice {
+            contact_id: "contact-1".into(),
+            sent: true,
+            version: Some("2024-01".to_string()),
+            message: Some(message()),
+            notified_at: Some(timestamp()),
+        }
+    }
+
+    pub fn flow_execution_step() -> FlowExecutionStep {
+        FlowExecutionStep {
+            node_id: "node-1".to_string(),
+            node_type: "message".to_string(),
+            started_at: timestamp(),
+            completed_at: Some(timestamp()),
+            input: None,
+            output: None,
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("/conversations/*", "/conversations/abc"));
+        assert!(matches("/conversations/*/messages", "/conversations/abc/messages"));
+        assert!(!matches("/conversations/*", "/conversations/abc/messages"));
+    }
+
+    #[tokio::test]
+    async fn test_default_fixtures_and_recording() {
+        let mock = MockLinktor::start().await;
+        let client = mock.client();
+
+        let conv = client.conversations().get("conv-9").await.unwrap();
+        assert_eq!(conv.id, "conv-1");
+        client.conversations().send_text("conv-9", "Hi").await.unwrap();
+
+        let sent = mock.requests_to("POST", "/conversations/conv-9/messages");
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].header("x-api-key"), Some("test-api-key"));
+        assert_eq!(sent[0].json().unwrap()["text"], "Hi");
+    }
+
+    #[tokio::test]
+    async fn test_override_route() {
+        let mock = MockLinktor::start().await;
+        mock.on(
+            "GET",
+            "/contacts/*",
+            404,
+            serde_json::json!({"code": "NOT_FOUND", "message": "gone"}),
+        );
+
+        let err = mock.client().contacts().get("contact-1").await.unwrap_err();
+        assert!(matches!(err, crate::LinktorError::NotFound { .. }));
+    }
+}
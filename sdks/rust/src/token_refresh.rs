@@ -0,0 +1,175 @@
+//! Opt-in background refresh of access tokens before they expire.
+//!
+//! The client records the refresh token and lifetime from every
+//! `auth().login()` and `auth().refresh_token()` call. A task started with
+//! [`AuthResource::start_token_refresh`](crate::AuthResource::start_token_refresh)
+//! waits until shortly before the current token expires and refreshes it,
+//! picking up any session change made elsewhere in the meantime.
+
+use crate::client::LinktorClient;
+use crate::error::{LinktorError, Result};
+use std::time::Duration;
+use tokio::sync::{oneshot, watch};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Refresh token and lifetime of the current access token.
+#[derive(Debug, Clone)]
+pub(crate) struct TokenSession {
+    pub refresh_token: String,
+    pub issued_at: Instant,
+    pub expires_in: Duration,
+}
+
+impl TokenSession {
+    pub fn new(refresh_token: String, expires_in: i64) -> Self {
+        Self {
+            refresh_token,
+            issued_at: Instant::now(),
+            expires_in: Duration::from_secs(expires_in.max(0) as u64),
+        }
+    }
+
+    /// When to refresh: `lead` before expiry, but never earlier than halfway
+    /// through the token's lifetime (nor within a second of issue) so
+    /// short-lived tokens are not refreshed in a loop.
+    fn refresh_at(&self, lead: Duration) -> Instant {
+        let at = self.issued_at + self.expires_in - lead.min(self.expires_in / 2);
+        at.max(self.issued_at + MIN_REFRESH_INTERVAL)
+    }
+}
+
+pub(crate) type SessionSender = watch::Sender<Option<TokenSession>>;
+
+#[derive(Debug, Clone)]
+pub struct TokenRefreshOptions {
+    refresh_before: Duration,
+    retry_delay: Duration,
+}
+
+impl Default for TokenRefreshOptions {
+    fn default() -> Self {
+        Self {
+            refresh_before: Duration::from_secs(60),
+            retry_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl TokenRefreshOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long before expiry to refresh (default 60s).
+    pub fn refresh_before(mut self, lead: Duration) -> Self {
+        self.refresh_before = lead;
+        self
+    }
+
+    /// Delay before retrying a refresh that failed with a network, 429 or 5xx error (default 10s).
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+}
+
+/// Handle to a running refresh task. Dropping it stops the task as well.
+#[derive(Debug)]
+pub struct TokenRefreshHandle {
+    shutdown: Option<oneshot::Sender<()>>,
+    task: JoinHandle<Result<()>>,
+}
+
+impl TokenRefreshHandle {
+    pub(crate) fn spawn(client: LinktorClient, sessions: watch::Receiver<Option<TokenSession>>, options: TokenRefreshOptions) -> Self {
+        let (shutdown, stop) = oneshot::channel();
+        let task = tokio::spawn(run(client, sessions, options, stop));
+        Self {
+            shutdown: Some(shutdown),
+            task,
+        }
+    }
+
+    /// Whether the task has stopped, either after `shutdown` or because a
+    /// refresh was rejected.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Stop the task, letting an in-flight refresh complete, and return the
+    /// error that ended it early, if any.
+    pub async fn shutdown(mut self) -> Result<()> {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        match (&mut self.task).await {
+            Ok(result) => result,
+            Err(e) => Err(LinktorError::Unknown {
+                message: format!("token refresh task failed: {}", e),
+                status_code: None,
+                code: None,
+                details: None,
+            }),
+        }
+    }
+}
+
+async fn run(
+    client: LinktorClient,
+    mut sessions: watch::Receiver<Option<TokenSession>>,
+    options: TokenRefreshOptions,
+    mut stop: oneshot::Receiver<()>,
+) -> Result<()> {
+    let mut retry_at: Option<Instant> = None;
+    loop {
+        let session = sessions.borrow_and_update().clone();
+        let due = match (&session, retry_at) {
+            (Some(_), Some(at)) => Some(at),
+            (Some(session), None) => Some(session.refresh_at(options.refresh_before)),
+            (None, _) => None,
+        };
+
+        tokio::select! {
+            _ = &mut stop => return Ok(()),
+            changed = sessions.changed() => {
+                if changed.is_err() {
+                    return Ok(());
+                }
+                retry_at = None;
+            }
+            _ = tokio::time::sleep_until(due.unwrap_or_else(Instant::now)), if due.is_some() => {
+                let refresh_token = session.map(|s| s.refresh_token).unwrap_or_default();
+                match client.auth().refresh_token(&refresh_token).await {
+                    Ok(_) => retry_at = None,
+                    Err(LinktorError::Network(_) | LinktorError::RateLimit { .. } | LinktorError::Server { .. }) => {
+                        retry_at = Some(Instant::now() + options.retry_delay);
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_at_respects_short_lifetimes() {
+        let session = TokenSession::new("r".to_string(), 3600);
+        assert_eq!(
+            session.refresh_at(Duration::from_secs(60)),
+            session.issued_at + Duration::from_secs(3540)
+        );
+
+        let short = TokenSession::new("r".to_string(), 30);
+        assert_eq!(short.refresh_at(Duration::from_secs(60)), short.issued_at + Duration::from_secs(15));
+
+        let expired = TokenSession::new("r".to_string(), 0);
+        assert_eq!(expired.refresh_at(Duration::from_secs(60)), expired.issued_at + MIN_REFRESH_INTERVAL);
+    }
+}
@@ -0,0 +1,175 @@
+//! Persisted sessions, so a process can pick up where the previous one left
+//! off instead of logging in again.
+//!
+//! A store configured with
+//! [`LinktorClientBuilder::token_store`](crate::LinktorClientBuilder::token_store)
+//! seeds the client at build time and is updated by every `login`,
+//! `refresh_token` and `logout` call, including refreshes made by the
+//! background task.
+
+use crate::error::{LinktorError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The tokens of a logged-in session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl StoredTokens {
+    pub fn new(access_token: impl Into<String>, refresh_token: impl Into<String>, expires_in: i64) -> Self {
+        Self {
+            access_token: access_token.into(),
+            refresh_token: refresh_token.into(),
+            expires_at: Utc::now() + chrono::Duration::seconds(expires_in.max(0)),
+        }
+    }
+
+    /// Seconds until the access token expires, zero once it has.
+    pub fn expires_in(&self) -> i64 {
+        (self.expires_at - Utc::now()).num_seconds().max(0)
+    }
+}
+
+/// Where a client loads and saves its session.
+pub trait TokenStore: Send + Sync {
+    fn load(&self) -> Result<Option<StoredTokens>>;
+    fn save(&self, tokens: &StoredTokens) -> Result<()>;
+    fn clear(&self) -> Result<()>;
+}
+
+impl<T: TokenStore + ?Sized> TokenStore for Arc<T> {
+    fn load(&self) -> Result<Option<StoredTokens>> {
+        (**self).load()
+    }
+
+    fn save(&self, tokens: &StoredTokens) -> Result<()> {
+        (**self).save(tokens)
+    }
+
+    fn clear(&self) -> Result<()> {
+        (**self).clear()
+    }
+}
+
+/// Keeps the session for the life of the process, e.g. to share it between
+/// clients or inspect it in tests.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    tokens: Mutex<Option<StoredTokens>>,
+}
+
+impl MemoryTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> Result<Option<StoredTokens>> {
+        Ok(self.tokens.lock().unwrap().clone())
+    }
+
+    fn save(&self, tokens: &StoredTokens) -> Result<()> {
+        *self.tokens.lock().unwrap() = Some(tokens.clone());
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<()> {
+        *self.tokens.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+/// Keeps the session in a JSON file. Writes replace the file atomically and,
+/// on Unix, make it readable by the owner only.
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn error(&self, action: &str, e: impl std::fmt::Display) -> LinktorError {
+        LinktorError::TokenStore {
+            message: format!("failed to {} {}: {}", action, self.path.display(), e),
+        }
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> Result<Option<StoredTokens>> {
+        let contents = match std::fs::read(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(self.error("read", e)),
+        };
+        serde_json::from_slice(&contents)
+            .map(Some)
+            .map_err(|e| self.error("parse", e))
+    }
+
+    fn save(&self, tokens: &StoredTokens) -> Result<()> {
+        let contents = serde_json::to_vec_pretty(tokens)?;
+        write_private(&self.path, &contents).map_err(|e| self.error("write", e))
+    }
+
+    fn clear(&self) -> Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(self.error("remove", e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Replace `path` atomically with a file only its owner can read (on Unix),
+/// creating parent directories as needed.
+pub(crate) fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut tmp = path.to_path_buf().into_os_string();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    std::io::Write::write_all(&mut options.open(&tmp)?, contents)?;
+    std::fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("linktor-token-store-{}", std::process::id()));
+        let store = FileTokenStore::new(dir.join("session.json"));
+        assert_eq!(store.load().unwrap(), None);
+
+        let tokens = StoredTokens::new("access", "refresh", 3600);
+        store.save(&tokens).unwrap();
+        assert_eq!(store.load().unwrap(), Some(tokens));
+
+        store.clear().unwrap();
+        store.clear().unwrap();
+        assert_eq!(store.load().unwrap(), None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+//! W3C Trace Context propagation (`traceparent` / `tracestate` headers).
+//!
+//! With the `trace-context` feature enabled, every outgoing request carries the
+//! active trace context so Linktor-side logs can be joined with your traces.
+//! The context is taken from the provider set with
+//! [`LinktorClientBuilder::trace_context_provider`](crate::LinktorClientBuilder::trace_context_provider),
+//! falling back to the context bound to the current task with [`TraceContext::scope`].
+//!
+//! The SDK does not depend on a particular OpenTelemetry release; a provider
+//! bridges whichever version the application uses:
+//!
+//! ```rust,ignore
+//! use opentelemetry::trace::TraceContextExt;
+//! use tracing_opentelemetry::OpenTelemetrySpanExt;
+//!
+//! let client = LinktorClient::builder()
+//!     .api_key("key")
+//!     .trace_context_provider(|| {
+//!         let cx = tracing::Span::current().context();
+//!         let span = cx.span();
+//!         let sc = span.span_context();
+//!         TraceContext::new(&sc.trace_id().to_string(), &sc.span_id().to_string(), sc.is_sampled())
+//!             .map(|tc| tc.tracestate(sc.trace_state().header()))
+//!     })
+//!     .build()?;
+//! ```
+
+use std::future::Future;
+use std::sync::Arc;
+
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+tokio::task_local! {
+    static CURRENT: TraceContext;
+}
+
+/// A W3C trace context: trace ID, parent span ID, sampling flag and vendor state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: String,
+    parent_id: String,
+    sampled: bool,
+    tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Build a context from hex IDs (32 and 16 characters). Returns `None` if
+    /// either ID is malformed or all zeros, as the spec forbids both.
+    pub fn new(trace_id: &str, parent_id: &str, sampled: bool) -> Option<Self> {
+        if !is_valid_id(trace_id, 32) || !is_valid_id(parent_id, 16) {
+            return None;
+        }
+        Some(Self {
+            trace_id: trace_id.to_ascii_lowercase(),
+            parent_id: parent_id.to_ascii_lowercase(),
+            sampled,
+            tracestate: None,
+        })
+    }
+
+    /// Parse a `traceparent` header value, e.g. from an inbound webhook request.
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let parent_id = parts.next()?;
+        let flags = parts.next()?;
+        // Version 00 has exactly four fields; later versions may append more.
+        if version.len() != 2 || version == "ff" || (version == "00" && parts.next().is_some()) {
+            return None;
+        }
+        let flags = u8::from_str_radix(flags, 16).ok().filter(|_| flags.len() == 2)?;
+        Self::new(trace_id, parent_id, flags & 0x01 == 1)
+    }
+
+    /// Attach a `tracestate` value; empty values are ignored.
+    pub fn tracestate(mut self, tracestate: impl Into<String>) -> Self {
+        let tracestate = tracestate.into();
+        self.tracestate = Some(tracestate).filter(|s| !s.trim().is_empty());
+        self
+    }
+
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    pub fn parent_id(&self) -> &str {
+        &self.parent_id
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        self.sampled
+    }
+
+    /// The `traceparent` header value.
+    pub fn traceparent(&self) -> String {
+        format!("00-{}-{}-{:02x}", self.trace_id, self.parent_id, self.sampled as u8)
+    }
+
+    pub fn tracestate_header(&self) -> Option<&str> {
+        self.tracestate.as_deref()
+    }
+
+    /// Run `future` with this context bound to the task, so requests made
+    /// inside it carry the context when no provider is configured.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// The context bound to the current task by [`scope`](Self::scope), if any.
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Clone::clone).ok()
+    }
+}
+
+pub(crate) type TraceContextProvider = Arc<dyn Fn() -> Option<TraceContext> + Send + Sync>;
+
+pub(crate) fn apply(provider: Option<&TraceContextProvider>, request: &mut reqwest::Request) {
+    let context = match provider {
+        Some(provider) => provider().or_else(TraceContext::current),
+        None => TraceContext::current(),
+    };
+    let Some(context) = context else {
+        return;
+    };
+
+    let headers = request.headers_mut();
+    if let Ok(value) = reqwest::header::HeaderValue::from_str(&context.traceparent()) {
+        headers.insert(TRACEPARENT_HEADER, value);
+    }
+    if let Some(value) = context
+        .tracestate_header()
+        .and_then(|s| reqwest::header::HeaderValue::from_str(s).ok())
+    {
+        headers.insert(TRACESTATE_HEADER, value);
+    }
+}
+
+fn is_valid_id(id: &str, len: usize) -> bool {
+    id.len() == len && id.bytes().all(|b| b.is_ascii_hexdigit()) && id.bytes().any(|b| b != b'0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traceparent_round_trip() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context = TraceContext::parse(header).unwrap();
+        assert!(context.is_sampled());
+        assert_eq!(context.traceparent(), header);
+
+        assert!(TraceContext::parse("00-00000000000000000000000000000000-00f067aa0ba902b7-01").is_none());
+        assert!(TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7").is_none());
+        assert!(TraceContext::parse("ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").is_none());
+        assert!(TraceContext::new("xyz", "00f067aa0ba902b7", true).is_none());
+    }
+}
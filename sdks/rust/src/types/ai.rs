@@ -1,3 +1,4 @@
+use super::ids::{AgentId, ConversationId, KnowledgeBaseId, MessageId, TenantId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -12,8 +13,8 @@ pub enum AgentStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Agent {
-    pub id: String,
-    pub tenant_id: String,
+    pub id: AgentId,
+    pub tenant_id: TenantId,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -26,7 +27,7 @@ pub struct Agent {
     #[serde(default)]
     pub max_tokens: i32,
     #[serde(default)]
-    pub knowledge_base_ids: Vec<String>,
+    pub knowledge_base_ids: Vec<KnowledgeBaseId>,
     #[serde(default)]
     pub tools: Vec<Tool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -162,6 +163,16 @@ pub struct CompletionResponse {
     pub choices: Vec<Choice>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<Usage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safety: Option<SafetyInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 impl CompletionResponse {
@@ -170,6 +181,15 @@ impl CompletionResponse {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafetyInfo {
+    #[serde(default)]
+    pub flagged: bool,
+    #[serde(default)]
+    pub categories: HashMap<String, f64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Choice {
@@ -239,3 +259,150 @@ pub struct EmbeddingData {
     pub index: i32,
     pub embedding: Vec<f64>,
 }
+
+/// A quality criterion a conversation is scored against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoringCriterion {
+    pub name: String,
+    /// What the scorer should look for; shown to the model verbatim.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Relative weight in the overall score (default 1).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+}
+
+impl ScoringCriterion {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            weight: None,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn weight(mut self, weight: f64) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoringRubric {
+    pub criteria: Vec<ScoringCriterion>,
+    /// Highest score per criterion (default 5).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl ScoringRubric {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empathy, resolution and policy adherence with equal weight.
+    pub fn standard() -> Self {
+        Self::new()
+            .criterion(ScoringCriterion::new("empathy").description("Acknowledges the customer's situation and tone"))
+            .criterion(ScoringCriterion::new("resolution").description("Resolves the customer's issue or sets a clear next step"))
+            .criterion(
+                ScoringCriterion::new("policyAdherence").description("Follows company policy and avoids unsupported promises"),
+            )
+    }
+
+    pub fn criterion(mut self, criterion: ScoringCriterion) -> Self {
+        self.criteria.push(criterion);
+        self
+    }
+
+    pub fn scale(mut self, scale: u32) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationScore {
+    pub conversation_id: ConversationId,
+    /// Weighted average of the criterion scores, on the rubric's scale.
+    pub overall: f64,
+    pub scale: u32,
+    pub criteria: Vec<CriterionScore>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scored_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ConversationScore {
+    pub fn criterion(&self, name: &str) -> Option<&CriterionScore> {
+        self.criteria.iter().find(|c| c.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sentiment {
+    Positive,
+    Neutral,
+    Negative,
+    Mixed,
+}
+
+/// A digest of a conversation, compact enough to store in a CRM in place of
+/// the full transcript.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationSummary {
+    pub conversation_id: ConversationId,
+    pub summary: String,
+    #[serde(default)]
+    pub key_points: Vec<String>,
+    pub sentiment: Sentiment,
+    /// From -1.0 (very negative) to 1.0 (very positive).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sentiment_score: Option<f64>,
+    /// Tags the model thinks fit; they are not applied to the conversation.
+    #[serde(default)]
+    pub suggested_tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriterionScore {
+    pub name: String,
+    pub score: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+    /// Quotes from the conversation supporting the score.
+    #[serde(default)]
+    pub evidence: Vec<ScoreEvidence>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreEvidence {
+    pub quote: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<MessageId>,
+}
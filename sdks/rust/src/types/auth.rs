@@ -1,3 +1,4 @@
+use super::ids::{TenantId, UserId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -22,8 +23,8 @@ pub enum UserStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct User {
-    pub id: String,
-    pub tenant_id: String,
+    pub id: UserId,
+    pub tenant_id: TenantId,
     pub email: String,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -34,16 +35,162 @@ pub struct User {
     pub preferences: Option<HashMap<String, serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Skills used by routing rules to match conversations to agents.
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_conversations: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_login_at: Option<chrono::DateTime<chrono::Utc>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl User {
+    pub fn has_skill(&self, name: &str) -> bool {
+        self.skills.iter().any(|s| s.name == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Skill {
+    pub name: String,
+    /// Proficiency from 1 (basic) to 5 (expert).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<u8>,
+}
+
+impl Skill {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            level: None,
+        }
+    }
+
+    pub fn level(mut self, level: u8) -> Self {
+        self.level = Some(level);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSkillsInput {
+    pub skills: Vec<Skill>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_conversations: Option<u32>,
+}
+
+impl SetSkillsInput {
+    pub fn new(skills: Vec<Skill>) -> Self {
+        Self {
+            skills,
+            max_concurrent_conversations: None,
+        }
+    }
+
+    pub fn max_concurrent_conversations(mut self, max: u32) -> Self {
+        self.max_concurrent_conversations = Some(max);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteUserInput {
+    pub email: String,
+    pub role: UserRole,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Personal note included in the invitation email.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl InviteUserInput {
+    pub fn new(email: impl Into<String>, role: UserRole) -> Self {
+        Self {
+            email: email.into(),
+            role,
+            name: None,
+            message: None,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateUserInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<UserRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<UserStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListUsersParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<UserRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<UserStatus>,
+    /// Free-text search over name and email.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+}
+
+impl ListUsersParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn role(mut self, role: UserRole) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    pub fn status(mut self, status: UserStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Tenant {
-    pub id: String,
+    pub id: TenantId,
     pub name: String,
     pub slug: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -56,6 +203,17 @@ pub struct Tenant {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// A tenant the current user belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantMembership {
+    pub tenant: Tenant,
+    pub role: UserRole,
+    /// The tenant requests go to when no `X-Tenant-ID` is sent.
+    #[serde(default)]
+    pub is_default: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TenantSettings {
@@ -63,6 +221,9 @@ pub struct TenantSettings {
     pub timezone: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
+    /// ISO 4217 code, e.g. `BRL`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub date_format: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -124,6 +285,91 @@ pub struct LoginResponse {
     pub expires_in: i64,
 }
 
+/// Result of [`login`](crate::AuthResource::login).
+#[derive(Debug, Clone)]
+pub enum LoginOutcome {
+    /// The client is signed in.
+    Authenticated(Box<LoginResponse>),
+    /// The password was accepted but a second factor is needed; finish with
+    /// [`complete_login`](crate::AuthResource::complete_login).
+    MfaRequired(MfaChallenge),
+}
+
+impl LoginOutcome {
+    pub fn is_authenticated(&self) -> bool {
+        matches!(self, LoginOutcome::Authenticated(_))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MfaMethod {
+    Totp,
+    RecoveryCode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaChallenge {
+    pub challenge_token: String,
+    #[serde(default)]
+    pub methods: Vec<MfaMethod>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompleteLoginInput {
+    pub challenge_token: String,
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<MfaMethod>,
+}
+
+/// A pending TOTP enrollment; confirm it with a code from the authenticator app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaEnrollment {
+    /// Base32 secret for manual entry.
+    pub secret: String,
+    /// `otpauth://` URI to render as a QR code.
+    pub otpauth_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaVerification {
+    pub enabled: bool,
+    /// One-time codes for when the authenticator is unavailable; shown only once.
+    #[serde(default)]
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MfaCodeInput {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestPasswordResetInput {
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmPasswordResetInput {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordInput {
+    pub current_password: String,
+    pub new_password: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RefreshTokenInput {
@@ -137,3 +383,49 @@ pub struct RefreshTokenResponse {
     pub refresh_token: String,
     pub expires_in: i64,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeCodeInput {
+    pub grant_type: String,
+    pub client_id: String,
+    pub code: String,
+    pub redirect_uri: String,
+    pub code_verifier: String,
+}
+
+impl ExchangeCodeInput {
+    pub fn new(
+        client_id: impl Into<String>,
+        code: impl Into<String>,
+        redirect_uri: impl Into<String>,
+        code_verifier: impl Into<String>,
+    ) -> Self {
+        Self {
+            grant_type: "authorization_code".to_string(),
+            client_id: client_id.into(),
+            code: code.into(),
+            redirect_uri: redirect_uri.into(),
+            code_verifier: code_verifier.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCredentialsToken {
+    pub access_token: String,
+    pub expires_in: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthTokenResponse {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
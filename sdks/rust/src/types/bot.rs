@@ -1,3 +1,6 @@
+use super::auth::{BusinessHours, DaySchedule};
+use super::ids::{AgentId, BotId, ChannelId, FlowId, KnowledgeBaseId, TenantId, UserId};
+use chrono::{NaiveTime, Weekday};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -20,8 +23,8 @@ pub enum BotType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Bot {
-    pub id: String,
-    pub tenant_id: String,
+    pub id: BotId,
+    pub tenant_id: TenantId,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -31,13 +34,13 @@ pub struct Bot {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<HashMap<String, serde_json::Value>>,
     #[serde(default)]
-    pub channel_ids: Vec<String>,
+    pub channel_ids: Vec<ChannelId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub flow_id: Option<String>,
+    pub flow_id: Option<FlowId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub agent_id: Option<String>,
+    pub agent_id: Option<AgentId>,
     #[serde(default)]
-    pub knowledge_base_ids: Vec<String>,
+    pub knowledge_base_ids: Vec<KnowledgeBaseId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -55,13 +58,13 @@ pub struct CreateBotInput {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<HashMap<String, serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub channel_ids: Option<Vec<String>>,
+    pub channel_ids: Option<Vec<ChannelId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub flow_id: Option<String>,
+    pub flow_id: Option<FlowId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub agent_id: Option<String>,
+    pub agent_id: Option<AgentId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub knowledge_base_ids: Option<Vec<String>>,
+    pub knowledge_base_ids: Option<Vec<KnowledgeBaseId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
@@ -104,13 +107,13 @@ pub struct UpdateBotInput {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<HashMap<String, serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub channel_ids: Option<Vec<String>>,
+    pub channel_ids: Option<Vec<ChannelId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub flow_id: Option<String>,
+    pub flow_id: Option<FlowId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub agent_id: Option<String>,
+    pub agent_id: Option<AgentId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub knowledge_base_ids: Option<Vec<String>>,
+    pub knowledge_base_ids: Option<Vec<KnowledgeBaseId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
@@ -123,7 +126,7 @@ pub struct ListBotsParams {
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub bot_type: Option<BotType>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub channel_id: Option<String>,
+    pub channel_id: Option<ChannelId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -131,3 +134,450 @@ pub struct ListBotsParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<i32>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachChannelInput {
+    pub channel_id: ChannelId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateBotInput {
+    pub name: String,
+}
+
+/// A customer message fed to the bot by `bots().simulate()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedMessage {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_name: Option<String>,
+    /// Visible to flow conditions and the AI prompt, like real message metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+impl SimulatedMessage {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            contact_name: None,
+            metadata: None,
+        }
+    }
+
+    pub fn contact_name(mut self, name: impl Into<String>) -> Self {
+        self.contact_name = Some(name.into());
+        self
+    }
+
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBotInput {
+    pub messages: Vec<SimulatedMessage>,
+}
+
+/// Trace of a simulated conversation, one turn per [`SimulatedMessage`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BotSimulation {
+    pub bot_id: BotId,
+    pub turns: Vec<SimulationTurn>,
+    #[serde(default)]
+    pub tokens_used: i64,
+    #[serde(default)]
+    pub duration_ms: i64,
+}
+
+impl BotSimulation {
+    /// Every reply the bot sent, in order.
+    pub fn replies(&self) -> Vec<&str> {
+        self.turns
+            .iter()
+            .flat_map(|t| t.replies.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// Ids of the flow nodes the bot went through, in order.
+    pub fn visited_nodes(&self) -> Vec<&str> {
+        self.steps()
+            .filter_map(|s| match s {
+                SimulationStep::FlowNode { node_id, .. } => Some(node_id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Names of the tools the bot invoked, in order.
+    pub fn tool_calls(&self) -> Vec<&str> {
+        self.steps()
+            .filter_map(|s| match s {
+                SimulationStep::ToolCall { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether any turn handed the conversation to a human.
+    pub fn escalated(&self) -> bool {
+        self.turns.iter().any(|t| t.escalated)
+    }
+
+    fn steps(&self) -> impl Iterator<Item = &SimulationStep> {
+        self.turns.iter().flat_map(|t| t.steps.iter())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationTurn {
+    pub input: String,
+    #[serde(default)]
+    pub replies: Vec<String>,
+    #[serde(default)]
+    pub steps: Vec<SimulationStep>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intent: Option<String>,
+    #[serde(default)]
+    pub escalated: bool,
+}
+
+/// One thing the bot did while handling a turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum SimulationStep {
+    FlowNode {
+        flow_id: FlowId,
+        node_id: String,
+        node_type: String,
+    },
+    AiCall {
+        model: String,
+        #[serde(default)]
+        tokens_used: i64,
+        #[serde(default)]
+        latency_ms: i64,
+    },
+    ToolCall {
+        name: String,
+        #[serde(default)]
+        arguments: serde_json::Value,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        result: Option<serde_json::Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKind {
+    Text,
+    Number,
+    Date,
+    Email,
+    Phone,
+    /// One of a fixed set of [`values`](IntentEntity::values).
+    List,
+}
+
+/// A value pulled out of messages matching an intent, e.g. an order number.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentEntity {
+    pub name: String,
+    pub kind: EntityKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+    /// Flows ask for the entity when a matching message doesn't include it.
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl IntentEntity {
+    pub fn new(name: impl Into<String>, kind: EntityKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            values: Vec::new(),
+            required: false,
+        }
+    }
+
+    pub fn values<I>(mut self, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.values = values.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+}
+
+/// An NLU intent that flow and hybrid bots route messages on. Intents are
+/// identified by name within a bot.
+///
+/// ```rust
+/// use linktor::types::{EntityKind, Intent, IntentEntity};
+///
+/// let intent = Intent::new("order_status")
+///     .phrase("Where is my order?")
+///     .phrase("Has order 1234 shipped?")
+///     .entity(IntentEntity::new("order_number", EntityKind::Number).required());
+/// assert_eq!(intent.training_phrases.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Intent {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub training_phrases: Vec<String>,
+    #[serde(default)]
+    pub entities: Vec<IntentEntity>,
+    #[serde(default, skip_serializing)]
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Intent {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            training_phrases: Vec::new(),
+            entities: Vec::new(),
+            updated_at: None,
+        }
+    }
+
+    pub fn description(mut self, desc: impl Into<String>) -> Self {
+        self.description = Some(desc.into());
+        self
+    }
+
+    pub fn phrase(mut self, text: impl Into<String>) -> Self {
+        self.training_phrases.push(text.into());
+        self
+    }
+
+    pub fn entity(mut self, entity: IntentEntity) -> Self {
+        self.entities.push(entity);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaceIntentsInput {
+    pub intents: Vec<Intent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectIntentInput {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentMatch {
+    pub name: String,
+    pub confidence: f64,
+}
+
+/// Outcome of `bots().detect_intent()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentDetection {
+    /// Best match, or `None` if nothing reached the bot's confidence threshold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intent: Option<IntentMatch>,
+    /// Entity values found in the text, by entity name.
+    #[serde(default)]
+    pub entities: HashMap<String, String>,
+    /// Runner-up intents, best first.
+    #[serde(default)]
+    pub alternatives: Vec<IntentMatch>,
+}
+
+impl IntentDetection {
+    pub fn intent_name(&self) -> Option<&str> {
+        self.intent.as_ref().map(|m| m.name.as_str())
+    }
+}
+
+/// What happens to conversations that arrive outside a bot's active hours.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum OffHoursFallback {
+    /// Leave the conversation with the bot until its hours resume.
+    #[default]
+    Wait,
+    /// Hand the conversation to humans, trying `agent_ids` in order. With no
+    /// agents it goes to the unassigned queue.
+    Handoff {
+        #[serde(default)]
+        agent_ids: Vec<UserId>,
+    },
+}
+
+/// When a bot answers, and what it does outside those hours. The weekly
+/// schedule has the same shape as [`BusinessHours`], so a bot can start from
+/// the tenant's hours with [`from_business_hours`](Self::from_business_hours).
+///
+/// ```rust
+/// use chrono::{NaiveTime, Weekday};
+/// use linktor::types::BotSchedule;
+///
+/// let schedule = BotSchedule::new("America/Sao_Paulo")
+///     .day(Weekday::Mon, "09:00", "18:00")
+///     .off_hours_reply("We're closed, an agent will reply in the morning.")
+///     .handoff_to(["on-call-agent-id"]);
+/// assert!(schedule.is_active_at(Weekday::Mon, NaiveTime::from_hms_opt(10, 0, 0).unwrap()));
+/// assert!(!schedule.is_active_at(Weekday::Tue, NaiveTime::from_hms_opt(10, 0, 0).unwrap()));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BotSchedule {
+    /// When `false` the bot answers around the clock.
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Active hours keyed by lowercase weekday name, e.g. `monday`. Missing
+    /// days are off.
+    #[serde(default)]
+    pub schedule: HashMap<String, DaySchedule>,
+    /// Auto-reply sent once per conversation outside active hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub off_hours_message: Option<String>,
+    #[serde(default)]
+    pub off_hours_fallback: OffHoursFallback,
+}
+
+impl BotSchedule {
+    pub fn new(timezone: impl Into<String>) -> Self {
+        Self {
+            enabled: true,
+            timezone: Some(timezone.into()),
+            schedule: HashMap::new(),
+            off_hours_message: None,
+            off_hours_fallback: OffHoursFallback::Wait,
+        }
+    }
+
+    /// Copy the tenant's business hours, e.g. from `TenantSettings::business_hours`.
+    pub fn from_business_hours(hours: &BusinessHours) -> Self {
+        Self {
+            enabled: hours.enabled,
+            timezone: hours.timezone.clone(),
+            schedule: hours.schedule.clone().unwrap_or_default(),
+            off_hours_message: None,
+            off_hours_fallback: OffHoursFallback::Wait,
+        }
+    }
+
+    /// Active from `start` to `end` (`HH:MM`) on `weekday`. An `end` before
+    /// `start` runs past midnight.
+    pub fn day(mut self, weekday: Weekday, start: impl Into<String>, end: impl Into<String>) -> Self {
+        self.schedule.insert(
+            weekday_key(weekday).to_string(),
+            DaySchedule {
+                enabled: true,
+                start: Some(start.into()),
+                end: Some(end.into()),
+            },
+        );
+        self
+    }
+
+    pub fn off_hours_reply(mut self, text: impl Into<String>) -> Self {
+        self.off_hours_message = Some(text.into());
+        self
+    }
+
+    pub fn handoff_to<I>(mut self, agent_ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<UserId>,
+    {
+        self.off_hours_fallback = OffHoursFallback::Handoff {
+            agent_ids: agent_ids.into_iter().map(Into::into).collect(),
+        };
+        self
+    }
+
+    /// Whether the bot answers at `time` on `weekday`, both local to the
+    /// schedule's timezone. A day without start or end is active all day;
+    /// unparseable times count as off.
+    pub fn is_active_at(&self, weekday: Weekday, time: NaiveTime) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let Some(day) = self.schedule.get(weekday_key(weekday)).filter(|d| d.enabled) else {
+            return false;
+        };
+        let parse = |t: &Option<String>| t.as_deref().map(|t| NaiveTime::parse_from_str(t, "%H:%M"));
+        match (parse(&day.start), parse(&day.end)) {
+            (None, None) => true,
+            (Some(Ok(start)), None) => time >= start,
+            (None, Some(Ok(end))) => time < end,
+            (Some(Ok(start)), Some(Ok(end))) if start <= end => time >= start && time < end,
+            (Some(Ok(start)), Some(Ok(end))) => time >= start || time < end,
+            _ => false,
+        }
+    }
+}
+
+fn weekday_key(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_active_hours() {
+        let schedule = BotSchedule::new("UTC")
+            .day(Weekday::Mon, "09:00", "18:00")
+            .day(Weekday::Fri, "22:00", "06:00");
+
+        assert!(schedule.is_active_at(Weekday::Mon, at(9, 0)));
+        assert!(!schedule.is_active_at(Weekday::Mon, at(18, 0)));
+        assert!(!schedule.is_active_at(Weekday::Sun, at(12, 0)));
+        assert!(schedule.is_active_at(Weekday::Fri, at(23, 30)));
+        assert!(schedule.is_active_at(Weekday::Fri, at(5, 59)));
+        assert!(!schedule.is_active_at(Weekday::Fri, at(12, 0)));
+
+        let always = BotSchedule { enabled: false, ..schedule };
+        assert!(always.is_active_at(Weekday::Sun, at(3, 0)));
+    }
+}
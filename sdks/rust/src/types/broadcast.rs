@@ -0,0 +1,170 @@
+use super::conversation::{MessageStatus, SendMessageInput};
+use super::filter::Filter;
+use super::ids::{BroadcastId, ChannelId, ContactId, MessageId, TenantId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BroadcastStatus {
+    Scheduled,
+    Sending,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+/// Delivery counts across all recipients of a broadcast.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastStats {
+    pub total: i64,
+    #[serde(default)]
+    pub pending: i64,
+    #[serde(default)]
+    pub sent: i64,
+    #[serde(default)]
+    pub delivered: i64,
+    #[serde(default)]
+    pub read: i64,
+    #[serde(default)]
+    pub failed: i64,
+    /// Recipients left out because they were listed twice, opted out or
+    /// have no identifier on the channel.
+    #[serde(default)]
+    pub skipped: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Broadcast {
+    pub id: BroadcastId,
+    pub tenant_id: TenantId,
+    pub name: String,
+    pub channel_id: ChannelId,
+    pub status: BroadcastStatus,
+    pub message: SendMessageInput,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub throttle_per_minute: Option<u32>,
+    #[serde(default)]
+    pub stats: BroadcastStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Who receives a broadcast. Contacts matched more than once receive a
+/// single message.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum BroadcastAudience {
+    Contacts { contact_ids: Vec<ContactId> },
+    /// Every contact matching the filter when the broadcast starts.
+    Segment { filter: Filter },
+}
+
+impl BroadcastAudience {
+    pub fn contacts<I>(ids: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ContactId>,
+    {
+        BroadcastAudience::Contacts {
+            contact_ids: ids.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn segment(filter: Filter) -> Self {
+        BroadcastAudience::Segment { filter }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBroadcastInput {
+    pub name: String,
+    pub channel_id: ChannelId,
+    /// Usually a template, since most recipients are outside the channel's
+    /// customer service window.
+    pub message: SendMessageInput,
+    pub audience: BroadcastAudience,
+    /// Start later instead of right away.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Cap on messages sent per minute, to stay within the channel's limits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub throttle_per_minute: Option<u32>,
+}
+
+impl CreateBroadcastInput {
+    pub fn new(
+        name: impl Into<String>,
+        channel_id: impl Into<ChannelId>,
+        message: SendMessageInput,
+        audience: BroadcastAudience,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            channel_id: channel_id.into(),
+            message,
+            audience,
+            send_at: None,
+            throttle_per_minute: None,
+        }
+    }
+
+    pub fn send_at(mut self, at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.send_at = Some(at);
+        self
+    }
+
+    pub fn throttle_per_minute(mut self, limit: u32) -> Self {
+        self.throttle_per_minute = Some(limit);
+        self
+    }
+}
+
+/// Delivery of a broadcast to one contact.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastRecipient {
+    pub contact_id: ContactId,
+    pub status: MessageStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<MessageId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBroadcastRecipientsParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<MessageStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+}
+
+impl ListBroadcastRecipientsParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, status: MessageStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
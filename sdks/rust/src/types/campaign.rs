@@ -0,0 +1,34 @@
+use super::channel::ChannelType;
+use super::conversation::MessageType;
+use super::ids::{CampaignId, ContactId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A campaign message rendered for one contact, exactly as it would be sent,
+/// with variables resolved from the contact's fields and custom fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CampaignPreview {
+    pub campaign_id: CampaignId,
+    pub contact_id: ContactId,
+    pub channel_type: ChannelType,
+    #[serde(rename = "type")]
+    pub message_type: MessageType,
+    /// Final text with variables resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered_text: Option<String>,
+    /// Value used for each variable, e.g. `first_name` → `Ana`.
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// Variables the contact has no value for; they render with the
+    /// template's fallback, or empty if it has none.
+    #[serde(default)]
+    pub missing_variables: Vec<String>,
+}
+
+impl CampaignPreview {
+    /// Whether every variable resolved from the contact's data.
+    pub fn is_complete(&self) -> bool {
+        self.missing_variables.is_empty()
+    }
+}
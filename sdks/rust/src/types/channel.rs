@@ -1,3 +1,6 @@
+use super::conversation::MessageStatus;
+use super::email::EmailChannelConfig;
+use super::ids::{ChannelId, ReauthorizationSessionId, TenantId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -15,6 +18,21 @@ pub enum ChannelType {
     Rcs,
 }
 
+impl ChannelType {
+    /// Longest text message the channel accepts, in characters; `None` if unlimited.
+    pub fn max_text_length(&self) -> Option<usize> {
+        match self {
+            ChannelType::Whatsapp | ChannelType::WhatsappUnofficial => Some(4096),
+            ChannelType::Telegram => Some(4096),
+            ChannelType::Facebook => Some(2000),
+            ChannelType::Instagram => Some(1000),
+            ChannelType::Sms => Some(1600),
+            ChannelType::Rcs => Some(3072),
+            ChannelType::Webchat | ChannelType::Email => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ChannelStatus {
@@ -27,8 +45,8 @@ pub enum ChannelStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Channel {
-    pub id: String,
-    pub tenant_id: String,
+    pub id: ChannelId,
+    pub tenant_id: TenantId,
     pub name: String,
     #[serde(rename = "type")]
     pub channel_type: ChannelType,
@@ -73,6 +91,13 @@ impl CreateChannelInput {
         self.config = Some(config);
         self
     }
+
+    /// An email channel, failing if `config` does not pass
+    /// [`EmailChannelConfig::validate`].
+    pub fn email(name: impl Into<String>, config: &EmailChannelConfig) -> crate::error::Result<Self> {
+        config.validate()?;
+        Ok(Self::new(name, ChannelType::Email).config(config.to_config()))
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -112,3 +137,368 @@ pub struct ChannelStatusResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_activity_at: Option<chrono::DateTime<chrono::Utc>>,
 }
+
+/// WhatsApp's rating of recent messages from a phone number. Numbers that
+/// stay `Red` get their messaging tier lowered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum QualityRating {
+    Green,
+    Yellow,
+    Red,
+    Unknown,
+}
+
+/// How much a channel may still send before the provider throttles it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelLimits {
+    pub channel_id: ChannelId,
+    /// Provider tier, e.g. `TIER_1K` for WhatsApp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    /// Conversations that may be started per rolling 24 hours; `None` when unlimited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_limit: Option<i64>,
+    #[serde(default)]
+    pub used_last_24h: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality_rating: Option<QualityRating>,
+    /// When the oldest message in the window ages out and frees quota.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resets_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ChannelLimits {
+    /// Whether `count` more conversations fit in the remaining quota.
+    pub fn can_send(&self, count: i64) -> bool {
+        self.remaining.is_none_or(|remaining| remaining >= count)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationMethod {
+    Sms,
+    Voice,
+}
+
+/// A verification code on its way to the channel's phone number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationRequest {
+    pub channel_id: ChannelId,
+    pub method: VerificationMethod,
+    /// The number the code was sent to, partially masked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Earliest time another code can be requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReauthorizationStatus {
+    Pending,
+    Completed,
+    Failed,
+    Expired,
+}
+
+/// An OAuth consent session for a channel whose token expired, e.g. on
+/// Instagram or Facebook. Send a page admin to `auth_url`; completion is also
+/// reported with a `channel.reauthorized` webhook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReauthorizationSession {
+    pub id: ReauthorizationSessionId,
+    pub channel_id: ChannelId,
+    pub auth_url: String,
+    pub status: ReauthorizationStatus,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// The period covered by an analytics query, inclusive of both ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsRange {
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+}
+
+impl AnalyticsRange {
+    pub fn new(from: chrono::NaiveDate, to: chrono::NaiveDate) -> Self {
+        Self { from, to }
+    }
+
+    /// The last `days` days up to and including today (UTC).
+    pub fn last_days(days: u32) -> Self {
+        let to = chrono::Utc::now().date_naive();
+        let from = to - chrono::Days::new(u64::from(days.max(1) - 1));
+        Self { from, to }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageCounts {
+    #[serde(default)]
+    pub sent: i64,
+    #[serde(default)]
+    pub received: i64,
+    #[serde(default)]
+    pub delivered: i64,
+    #[serde(default)]
+    pub read: i64,
+    #[serde(default)]
+    pub failed: i64,
+}
+
+impl MessageCounts {
+    /// Share of sent messages that were delivered, from 0.0 to 1.0.
+    pub fn delivery_rate(&self) -> Option<f64> {
+        rate(self.delivered, self.sent)
+    }
+
+    /// Share of sent messages that were read, from 0.0 to 1.0.
+    pub fn read_rate(&self) -> Option<f64> {
+        rate(self.read, self.sent)
+    }
+}
+
+fn rate(count: i64, total: i64) -> Option<f64> {
+    (total > 0).then(|| count as f64 / total as f64)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelAnalyticsDay {
+    pub date: chrono::NaiveDate,
+    #[serde(flatten)]
+    pub counts: MessageCounts,
+    /// Failed messages by provider error code.
+    #[serde(default)]
+    pub errors: HashMap<String, i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelAnalytics {
+    pub channel_id: ChannelId,
+    pub from: chrono::NaiveDate,
+    pub to: chrono::NaiveDate,
+    pub totals: MessageCounts,
+    /// One entry per day in the range, oldest first.
+    #[serde(default)]
+    pub days: Vec<ChannelAnalyticsDay>,
+}
+
+/// A message sent straight through a channel, outside any conversation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMessageResult {
+    pub channel_id: ChannelId,
+    pub recipient: String,
+    pub status: MessageStatus,
+    /// The provider's id for the message, when it accepted it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub sent_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// What to change when copying a channel. Anything left unset is copied from
+/// the source, except credentials, which are always cleared.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneChannelInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Create the copy in this tenant instead of the source's, e.g. to promote
+    /// a channel from a staging tenant to production.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<TenantId>,
+    /// Config keys to set on the copy, merged over the copied config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<HashMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+impl CloneChannelInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn tenant(mut self, tenant_id: impl Into<TenantId>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+
+    pub fn config(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.config.get_or_insert_with(HashMap::new).insert(key.into(), value.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClonedChannel {
+    /// The copy, disconnected until its credentials are filled in.
+    pub channel: Channel,
+    /// Config keys that were cleared and must be set with `update` before
+    /// connecting, e.g. `accessToken`.
+    #[serde(default)]
+    pub reset_credentials: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WidgetPosition {
+    BottomRight,
+    BottomLeft,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreChatFieldType {
+    Text,
+    Email,
+    Phone,
+    Select,
+}
+
+/// A question asked before the chat starts; answers are stored on the contact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreChatField {
+    /// Contact field or custom field the answer is saved to, e.g. `email`.
+    pub name: String,
+    pub label: String,
+    #[serde(rename = "type")]
+    pub field_type: PreChatFieldType,
+    #[serde(default)]
+    pub required: bool,
+    /// Choices for `Select` fields.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
+impl PreChatField {
+    pub fn new(name: impl Into<String>, label: impl Into<String>, field_type: PreChatFieldType) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            field_type,
+            required: false,
+            options: Vec::new(),
+        }
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub fn options(mut self, options: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.options = options.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Appearance and behaviour of a webchat channel's widget. Unset fields keep
+/// their current value on update.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebchatSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Hex color such as `#6366f1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<WidgetPosition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    /// Greeting shown when the widget opens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub welcome_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offline_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_open: Option<bool>,
+    /// Seconds after page load before the widget opens itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_open_delay: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_typing_indicator: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_attachments: Option<bool>,
+    /// Sites allowed to embed the widget; empty allows any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_domains: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_chat_form: Option<Vec<PreChatField>>,
+}
+
+impl WebchatSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn colors(mut self, primary: impl Into<String>, text: impl Into<String>) -> Self {
+        self.primary_color = Some(primary.into());
+        self.text_color = Some(text.into());
+        self
+    }
+
+    pub fn position(mut self, position: WidgetPosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn welcome_message(mut self, message: impl Into<String>) -> Self {
+        self.welcome_message = Some(message.into());
+        self
+    }
+
+    pub fn auto_open(mut self, delay_seconds: u32) -> Self {
+        self.auto_open = Some(true);
+        self.auto_open_delay = Some(delay_seconds);
+        self
+    }
+
+    pub fn allowed_domains(mut self, domains: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.allowed_domains = Some(domains.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn pre_chat_field(mut self, field: PreChatField) -> Self {
+        self.pre_chat_form.get_or_insert_with(Vec::new).push(field);
+        self
+    }
+}
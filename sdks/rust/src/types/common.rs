@@ -83,3 +83,25 @@ pub struct ApiError {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<HashMap<String, serde_json::Value>>,
 }
+
+/// Rate-limit state reported by the API in `X-RateLimit-*` headers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitStatus {
+    pub limit: u32,
+    pub remaining: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl RateLimitStatus {
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Time left until the window resets, zero if it already has.
+    pub fn reset_in(&self) -> Option<std::time::Duration> {
+        self.reset_at
+            .map(|at| (at - chrono::Utc::now()).to_std().unwrap_or_default())
+    }
+}
@@ -0,0 +1,145 @@
+use super::conversation::Message;
+use super::ids::{ContactId, ErasureId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComplianceRegulation {
+    Lgpd,
+    Gdpr,
+    Other,
+}
+
+/// The notice sent to a contact before anything else, configured per channel
+/// and optionally overridden per conversation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplianceMessageConfig {
+    pub enabled: bool,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regulation: Option<ComplianceRegulation>,
+    /// Notice text by locale (e.g. `pt-BR`), chosen from the contact's locale.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub translations: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub privacy_policy_url: Option<String>,
+    /// Changing the version sends the notice again to contacts who only saw an older one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl ComplianceMessageConfig {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            enabled: true,
+            text: text.into(),
+            regulation: None,
+            translations: HashMap::new(),
+            privacy_policy_url: None,
+            version: None,
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn regulation(mut self, regulation: ComplianceRegulation) -> Self {
+        self.regulation = Some(regulation);
+        self
+    }
+
+    pub fn translation(mut self, locale: impl Into<String>, text: impl Into<String>) -> Self {
+        self.translations.insert(locale.into(), text.into());
+        self
+    }
+
+    pub fn privacy_policy_url(mut self, url: impl Into<String>) -> Self {
+        self.privacy_policy_url = Some(url.into());
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+}
+
+/// Outcome of `ensure_compliance_notice`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplianceNotice {
+    pub contact_id: ContactId,
+    /// Whether this call sent the notice; `false` when the contact already had it
+    /// or no notice is configured.
+    pub sent: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The notice message, when this call sent it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<Message>,
+    /// When the contact first received the current version of the notice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notified_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErasureStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Why a contact is being erased, recorded on the job for audits.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EraseContactInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regulation: Option<ComplianceRegulation>,
+    /// Free-form reference, e.g. the data subject request ticket.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl EraseContactInput {
+    pub fn new(regulation: ComplianceRegulation) -> Self {
+        Self {
+            regulation: Some(regulation),
+            reason: None,
+        }
+    }
+
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+/// Receipt for a contact erasure. Messages are redacted and identifiers
+/// removed in the background; poll with `contacts().get_erasure()` until the
+/// status is `Completed` to keep as proof of deletion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactErasure {
+    pub id: ErasureId,
+    pub contact_id: ContactId,
+    pub status: ErasureStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regulation: Option<ComplianceRegulation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub messages_redacted: i64,
+    #[serde(default)]
+    pub identifiers_removed: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub requested_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
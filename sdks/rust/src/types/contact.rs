@@ -1,11 +1,13 @@
+use super::filter::Filter;
+use super::ids::{ChannelId, ContactId, ConversationId, TenantId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Contact {
-    pub id: String,
-    pub tenant_id: String,
+    pub id: ContactId,
+    pub tenant_id: TenantId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -28,6 +30,66 @@ pub struct Contact {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+pub const LOCALE_FIELD: &str = "locale";
+pub const TIMEZONE_FIELD: &str = "timezone";
+
+impl Contact {
+    /// Locale stored in the `locale` custom field, e.g. `pt-BR`.
+    pub fn locale(&self) -> Option<&str> {
+        self.custom_field_str(LOCALE_FIELD)
+    }
+
+    /// IANA timezone stored in the `timezone` custom field, e.g. `America/Sao_Paulo`.
+    pub fn timezone(&self) -> Option<&str> {
+        self.custom_field_str(TIMEZONE_FIELD)
+    }
+
+    fn custom_field_str(&self, key: &str) -> Option<&str> {
+        self.custom_fields.as_ref()?.get(key)?.as_str()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocaleSignal {
+    PhoneCountryCode,
+    MessageLanguage,
+    ChannelProfile,
+    Existing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleInference {
+    pub contact_id: ContactId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub confidence: f64,
+    #[serde(default)]
+    pub signals: Vec<LocaleSignal>,
+    /// Whether the inferred values were written to the contact's custom fields.
+    #[serde(default)]
+    pub applied: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferLocaleInput {
+    /// Overwrite `locale`/`timezone` custom fields that are already set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overwrite: Option<bool>,
+    /// Only return the inference without updating the contact.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateContactInput {
@@ -96,8 +158,18 @@ pub struct UpdateContactInput {
 pub struct ListContactsParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tag: Option<String>,
+    /// Contacts carrying all of these tags; sent as repeated `tags` parameters.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Exact matches on metadata fields; sent as `metadata[key]=value`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    /// Free-text search over name, email and phone.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search: Option<String>,
+    /// Structured conditions on fields, custom fields and tags.
+    #[serde(skip_serializing_if = "Option::is_none", skip_deserializing)]
+    pub filter: Option<Filter>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -118,6 +190,34 @@ impl ListContactsParams {
         self
     }
 
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Add `filter` to any filter already set, requiring both to match.
+    pub fn and_filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(match self.filter.take() {
+            Some(existing) => existing.and(filter),
+            None => filter,
+        });
+        self
+    }
+
+    pub fn last_seen_between(self, from: chrono::DateTime<chrono::Utc>, to: chrono::DateTime<chrono::Utc>) -> Self {
+        self.and_filter(Filter::last_seen_between(from, to))
+    }
+
+    pub fn tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
     pub fn limit(mut self, limit: i32) -> Self {
         self.limit = Some(limit);
         self
@@ -127,6 +227,52 @@ impl ListContactsParams {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MergeContactsInput {
-    pub primary_contact_id: String,
-    pub contact_ids_to_merge: Vec<String>,
+    pub primary_contact_id: ContactId,
+    pub contact_ids_to_merge: Vec<ContactId>,
+}
+
+impl MergeContactsInput {
+    /// Fold `duplicates` into `primary`, which survives the merge.
+    pub fn new<I>(primary: impl Into<ContactId>, duplicates: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ContactId>,
+    {
+        Self {
+            primary_contact_id: primary.into(),
+            contact_ids_to_merge: duplicates.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Outcome of a merge: the surviving contact and what was moved onto it from
+/// the duplicates, which are deleted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactMergeResult {
+    pub contact: Contact,
+    #[serde(default)]
+    pub merged_contact_ids: Vec<ContactId>,
+    #[serde(default)]
+    pub moved_conversation_ids: Vec<ConversationId>,
+    /// Channel identifiers taken over from the duplicates, keyed like
+    /// [`Contact::identifiers`].
+    #[serde(default)]
+    pub moved_identifiers: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactAvatar {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshAvatarInput {
+    pub channel_id: ChannelId,
 }
@@ -1,3 +1,6 @@
+use super::channel::ChannelType;
+use super::common::PaginationParams;
+use super::ids::{BotId, ChannelId, ContactId, ConversationId, MessageId, ScheduledMessageId, TenantId, UserId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -45,6 +48,29 @@ pub enum MessageStatus {
     Failed,
 }
 
+impl MessageStatus {
+    /// Whether a message in this status has progressed at least as far as
+    /// `target`, so a `Read` message has also been `Delivered`. `Failed` only
+    /// reaches itself.
+    pub fn has_reached(self, target: MessageStatus) -> bool {
+        match (self, target) {
+            (MessageStatus::Failed, target) => target == MessageStatus::Failed,
+            (_, MessageStatus::Failed) => false,
+            (status, target) => status.rank() >= target.rank(),
+        }
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            MessageStatus::Pending => 0,
+            MessageStatus::Sent => 1,
+            MessageStatus::Delivered => 2,
+            MessageStatus::Read => 3,
+            MessageStatus::Failed => 4,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum MessageDirection {
@@ -55,14 +81,14 @@ pub enum MessageDirection {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Conversation {
-    pub id: String,
-    pub tenant_id: String,
-    pub channel_id: String,
-    pub contact_id: String,
+    pub id: ConversationId,
+    pub tenant_id: TenantId,
+    pub channel_id: ChannelId,
+    pub contact_id: ContactId,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub assigned_agent_id: Option<String>,
+    pub assigned_agent_id: Option<UserId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub bot_id: Option<String>,
+    pub bot_id: Option<BotId>,
     pub status: ConversationStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<ConversationPriority>,
@@ -82,15 +108,124 @@ pub struct Conversation {
     pub last_message_at: Option<chrono::DateTime<chrono::Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub bot_paused: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_paused_until: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether the attached AI agent replies on its own; absent when no agent is attached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autopilot: Option<AutopilotState>,
+    /// Tickets and records in other systems that track this conversation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_references: Vec<ExternalReference>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// A link from a conversation to a record in another system, such as a Jira
+/// issue or a Zendesk ticket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalReference {
+    /// Name of the other system, e.g. `"jira"` or `"zendesk"`.
+    pub system: String,
+    /// The record's identifier in that system, e.g. `"SUP-1234"`.
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub linked_by: Option<UserId>,
+    #[serde(default, skip_serializing)]
+    pub linked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ExternalReference {
+    pub fn new(system: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            system: system.into(),
+            id: id.into(),
+            url: None,
+            linked_by: None,
+            linked_at: None,
+        }
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+}
+
+/// How the AI agent attached to a conversation handles incoming messages.
+/// When enabled it sends replies whose confidence reaches `min_confidence`;
+/// otherwise, and for replies below the threshold, it only drafts suggestions
+/// for a human agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotConfig {
+    pub enabled: bool,
+    /// Between 0 and 1.
+    pub min_confidence: f64,
+    /// Tag added to the conversation when a reply falls below the threshold.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub escalation_tag: Option<String>,
+}
+
+impl AutopilotConfig {
+    /// Reply automatically when confident enough.
+    pub fn enabled(min_confidence: f64) -> Self {
+        Self {
+            enabled: true,
+            min_confidence,
+            escalation_tag: None,
+        }
+    }
+
+    /// Only draft suggestions.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            min_confidence: 1.0,
+            escalation_tag: None,
+        }
+    }
+
+    pub fn escalation_tag(mut self, tag: impl Into<String>) -> Self {
+        self.escalation_tag = Some(tag.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutopilotState {
+    #[serde(flatten)]
+    pub config: AutopilotConfig,
+    /// `true` when set on this conversation, `false` when inherited from the agent.
+    #[serde(default)]
+    pub overridden: bool,
+}
+
+impl Conversation {
+    /// Whether the bot is currently silenced in this conversation.
+    pub fn is_bot_paused(&self) -> bool {
+        match self.bot_paused_until {
+            Some(until) => self.bot_paused && until > chrono::Utc::now(),
+            None => self.bot_paused,
+        }
+    }
+
+    /// The linked record in `system`, e.g. `"jira"`, if any.
+    pub fn external_reference(&self, system: &str) -> Option<&ExternalReference> {
+        self.external_references.iter().find(|r| r.system == system)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Message {
-    pub id: String,
-    pub conversation_id: String,
+    pub id: MessageId,
+    pub conversation_id: ConversationId,
     #[serde(rename = "type")]
     pub message_type: MessageType,
     pub direction: MessageDirection,
@@ -115,10 +250,97 @@ pub struct Message {
     pub external_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Emoji reactions, one per user, on channels that support them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<Reaction>,
+    /// Delivery progress per recipient, for outbound messages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub receipts: Vec<DeliveryReceipt>,
+    /// Machine translation of `text`, present when one was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<MessageTranslation>,
+    /// Where this message came from when it was forwarded from another conversation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarded_from: Option<ForwardedFrom>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageTranslation {
+    /// Detected language of the original text, e.g. `"es"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_language: Option<String>,
+    pub target_language: String,
+    pub text: String,
+}
+
+/// Attribution kept on a forwarded message so the receiving team can see who
+/// originally wrote it and jump back to the source conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardedFrom {
+    pub conversation_id: ConversationId,
+    pub message_id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_name: Option<String>,
+    /// When the original message was created.
+    pub sent_at: chrono::DateTime<chrono::Utc>,
+    /// The user who forwarded it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarded_by: Option<UserId>,
+}
+
+/// When a message reached each stage for one recipient, as reported by the
+/// channel. Stages the channel does not report stay `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryReceipt {
+    /// Phone number, username or other channel address of the recipient.
+    pub recipient: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_type: Option<ChannelType>,
+    pub status: MessageStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivered_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DeliveryReceipt {
+    /// Time from being sent to the channel until delivery to the device.
+    pub fn delivery_time(&self) -> Option<chrono::Duration> {
+        Some(self.delivered_at? - self.sent_at?)
+    }
+
+    /// Time from being sent until the recipient read it.
+    pub fn read_time(&self) -> Option<chrono::Duration> {
+        Some(self.read_at? - self.sent_at?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reaction {
+    /// The agent or contact who reacted.
+    pub user_id: String,
+    pub emoji: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MediaContent {
@@ -176,6 +398,12 @@ pub struct TemplateContent {
 pub struct TemplateComponent {
     #[serde(rename = "type")]
     pub component_type: String,
+    /// Button kind (`quick_reply`, `url`, `copy_code`) for `button` components.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_type: Option<String>,
+    /// Position of the button in the template, for `button` components.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
     #[serde(default)]
     pub parameters: Vec<TemplateParameter>,
 }
@@ -193,6 +421,34 @@ pub struct TemplateParameter {
     pub document: Option<MediaContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub video: Option<MediaContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<TemplateCurrency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_time: Option<TemplateDateTime>,
+    /// Quick-reply button payload echoed back when the contact taps it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<String>,
+    /// Coupon code for `copy_code` buttons.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coupon_code: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateCurrency {
+    /// Shown where the channel cannot localize the amount.
+    pub fallback_value: String,
+    /// ISO 4217 code.
+    pub code: String,
+    /// Amount multiplied by 1000, e.g. `12_500` for 12.50.
+    #[serde(rename = "amount1000")]
+    pub amount_1000: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateDateTime {
+    pub fallback_value: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -238,6 +494,9 @@ pub struct InteractiveFooter {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InteractiveAction {
+    /// Label of the button that opens a list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub button: Option<String>,
     #[serde(default)]
     pub buttons: Vec<Button>,
     #[serde(default)]
@@ -267,25 +526,347 @@ pub struct SectionRow {
     pub description: Option<String>,
 }
 
+impl SectionRow {
+    pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            description: None,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextEncoding {
+    Gsm7,
+    Ucs2,
+}
+
+/// Channel-specific rendering of a message that has not been sent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessagePreview {
+    pub conversation_id: ConversationId,
+    pub channel_type: ChannelType,
+    #[serde(rename = "type")]
+    pub message_type: MessageType,
+    /// Final text with template variables resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered_text: Option<String>,
+    #[serde(default)]
+    pub buttons: Vec<Button>,
+    #[serde(default)]
+    pub sections: Vec<Section>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<MediaContent>,
+    /// Number of SMS segments the text will be split into, for SMS channels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_segments: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<TextEncoding>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// A conversation's routing state at a past moment, reconstructed from the audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationSnapshot {
+    pub conversation_id: ConversationId,
+    /// The moment the state was reconstructed for.
+    pub at: chrono::DateTime<chrono::Utc>,
+    pub status: ConversationStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_agent_id: Option<UserId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<ConversationPriority>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub bot_paused: bool,
+    /// When the latest audit event at or before `at` was recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_changed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A message whose status was corrected from the channel provider's records.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusCorrection {
+    pub message_id: MessageId,
+    pub conversation_id: ConversationId,
+    pub previous_status: MessageStatus,
+    pub status: MessageStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileFailure {
+    pub message_id: MessageId,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationReport {
+    /// Messages whose status was checked with the provider.
+    pub checked: i64,
+    #[serde(default)]
+    pub corrected: Vec<StatusCorrection>,
+    /// Messages the provider could not report on.
+    #[serde(default)]
+    pub failed: Vec<ReconcileFailure>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub completed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ReconciliationReport {
+    /// Checked messages whose status already matched the provider.
+    pub fn unchanged(&self) -> i64 {
+        self.checked - self.corrected.len() as i64 - self.failed.len() as i64
+    }
+}
+
 // Input types
 
+/// Which messages to re-sync: explicit messages, whole conversations, or
+/// everything sent in a time range.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileStatusesInput {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub message_ids: Vec<MessageId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conversation_ids: Vec<ConversationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+}
+
+impl ReconcileStatusesInput {
+    pub fn messages(ids: impl IntoIterator<Item = impl Into<MessageId>>) -> Self {
+        Self {
+            message_ids: ids.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn conversations(ids: impl IntoIterator<Item = impl Into<ConversationId>>) -> Self {
+        Self {
+            conversation_ids: ids.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Messages sent between `from` and `to`, e.g. the span of an outage.
+    pub fn range(from: chrono::DateTime<chrono::Utc>, to: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            from: Some(from),
+            to: Some(to),
+            ..Self::default()
+        }
+    }
+
+    /// Only messages on this channel.
+    pub fn channel_id(mut self, id: impl Into<ChannelId>) -> Self {
+        self.channel_id = Some(id.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimConversationInput {
+    pub agent_id: UserId,
+    /// Only claim if the conversation is currently assigned to this agent;
+    /// `None` only claims unassigned conversations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_agent_id: Option<UserId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActorType {
+    User,
+    Bot,
+    Contact,
+    System,
+}
+
+/// Who caused a [`ConversationEvent`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventActor {
+    #[serde(rename = "type")]
+    pub actor_type: ActorType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HandoffDirection {
+    /// The bot handed the conversation to a human agent.
+    ToAgent,
+    /// The conversation was handed (back) to a bot.
+    ToBot,
+}
+
+/// What happened, with the details of each kind of change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum ConversationEventKind {
+    Created,
+    StatusChanged {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        from: Option<ConversationStatus>,
+        to: ConversationStatus,
+    },
+    /// `agent_id` is `None` when the conversation was unassigned.
+    Assigned {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        agent_id: Option<UserId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        previous_agent_id: Option<UserId>,
+    },
+    TagsChanged {
+        #[serde(default)]
+        added: Vec<String>,
+        #[serde(default)]
+        removed: Vec<String>,
+    },
+    PriorityChanged {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        from: Option<ConversationPriority>,
+        to: ConversationPriority,
+    },
+    BotHandoff {
+        direction: HandoffDirection,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        bot_id: Option<BotId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// An event type this SDK version does not know yet.
+    #[serde(other)]
+    Other,
+}
+
+/// One entry of a conversation's audit timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationEvent {
+    pub id: String,
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<EventActor>,
+    #[serde(flatten)]
+    pub kind: ConversationEventKind,
+}
+
+/// Result of an atomic [`claim`](crate::ConversationsResource::claim).
+#[derive(Debug, Clone)]
+pub enum ClaimOutcome {
+    /// The conversation is now assigned to the requesting agent.
+    Claimed(Box<Conversation>),
+    /// Another agent holds the conversation.
+    AlreadyClaimedBy(String),
+}
+
+impl ClaimOutcome {
+    pub fn is_claimed(&self) -> bool {
+        matches!(self, ClaimOutcome::Claimed(_))
+    }
+}
+
+/// Client-side behaviour for [`send_message_with`](crate::ConversationsResource::send_message_with).
+#[derive(Debug, Clone, Default)]
+pub struct SendOptions {
+    /// Split long text into several messages that fit the channel's limit.
+    /// Only plain text messages are split; media, templates, interactive and
+    /// location messages are sent as one message.
+    pub auto_chunk: bool,
+    /// Override the channel's text limit, in characters.
+    pub max_length: Option<usize>,
+}
+
+impl SendOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn auto_chunk(mut self, enabled: bool) -> Self {
+        self.auto_chunk = enabled;
+        self
+    }
+
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseBotInput {
+    /// Pause length in seconds; `None` pauses until resumed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u64>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListConversationsParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<ConversationStatus>,
+    /// Conversations in any of these statuses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<ConversationStatus>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<ConversationPriority>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub channel_id: Option<String>,
+    pub channel_id: Option<ChannelId>,
+    /// Conversations on any channel of these types.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channel_types: Vec<ChannelType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<ContactId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub contact_id: Option<String>,
+    pub assigned_agent_id: Option<UserId>,
+    /// Only conversations without an assigned agent.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub assigned_agent_id: Option<String>,
+    pub unassigned: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tag: Option<String>,
+    /// Conversations carrying all of these tags; sent as repeated `tags` parameters.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search: Option<String>,
+    /// Full-text search over message contents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_after: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_before: Option<chrono::DateTime<chrono::Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -309,11 +890,55 @@ impl ListConversationsParams {
         self
     }
 
-    pub fn channel_id(mut self, id: impl Into<String>) -> Self {
+    pub fn channel_id(mut self, id: impl Into<ChannelId>) -> Self {
         self.channel_id = Some(id.into());
         self
     }
 
+    pub fn tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn statuses(mut self, statuses: impl IntoIterator<Item = ConversationStatus>) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    pub fn channel_types(mut self, types: impl IntoIterator<Item = ChannelType>) -> Self {
+        self.channel_types = types.into_iter().collect();
+        self
+    }
+
+    pub fn unassigned(mut self) -> Self {
+        self.unassigned = Some(true);
+        self
+    }
+
+    pub fn search(mut self, query: impl Into<String>) -> Self {
+        self.search = Some(query.into());
+        self
+    }
+
+    pub fn message_search(mut self, query: impl Into<String>) -> Self {
+        self.message_search = Some(query.into());
+        self
+    }
+
+    /// Created between `from` (inclusive) and `to` (exclusive).
+    pub fn created_between(mut self, from: chrono::DateTime<chrono::Utc>, to: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_after = Some(from);
+        self.created_before = Some(to);
+        self
+    }
+
+    /// Last updated between `from` (inclusive) and `to` (exclusive).
+    pub fn updated_between(mut self, from: chrono::DateTime<chrono::Utc>, to: chrono::DateTime<chrono::Utc>) -> Self {
+        self.updated_after = Some(from);
+        self.updated_before = Some(to);
+        self
+    }
+
     pub fn limit(mut self, limit: i32) -> Self {
         self.limit = Some(limit);
         self
@@ -339,6 +964,9 @@ pub struct SendMessageInput {
     pub interactive: Option<InteractiveContent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Translate `text` into this language (e.g. `"pt-BR"`) before delivery.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translate_to: Option<String>,
 }
 
 impl SendMessageInput {
@@ -349,6 +977,91 @@ impl SendMessageInput {
             ..Default::default()
         }
     }
+
+    /// A WhatsApp template message, e.g. from a
+    /// [`TemplateMessageBuilder`](super::TemplateMessageBuilder).
+    pub fn template(template: TemplateContent) -> Self {
+        Self {
+            message_type: Some(MessageType::Template),
+            template: Some(template),
+            ..Default::default()
+        }
+    }
+
+    pub fn interactive(interactive: InteractiveContent) -> Self {
+        Self {
+            message_type: Some(MessageType::Interactive),
+            interactive: Some(interactive),
+            ..Default::default()
+        }
+    }
+
+    /// An image, video, audio, document or sticker message.
+    pub fn media(message_type: MessageType, media: MediaContent) -> Self {
+        Self {
+            message_type: Some(message_type),
+            media: Some(media),
+            ..Default::default()
+        }
+    }
+
+    /// Have the server translate the text into `language` before delivery;
+    /// the original is kept on the stored message.
+    pub fn translate_to(mut self, language: impl Into<String>) -> Self {
+        self.translate_to = Some(language.into());
+        self
+    }
+
+    /// Whether the message carries nothing but text.
+    pub(crate) fn is_text_only(&self) -> bool {
+        self.media.is_none()
+            && self.location.is_none()
+            && self.contact.is_none()
+            && self.template.is_none()
+            && self.interactive.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduledMessageStatus {
+    Pending,
+    Sent,
+    Cancelled,
+    Failed,
+}
+
+/// A message queued to be sent at a later time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledMessage {
+    pub id: ScheduledMessageId,
+    pub conversation_id: ConversationId,
+    pub message: SendMessageInput,
+    pub send_at: chrono::DateTime<chrono::Utc>,
+    pub status: ScheduledMessageStatus,
+    /// The message created once it was sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_message_id: Option<MessageId>,
+    /// Why sending failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScheduleMessageInput {
+    pub message: SendMessageInput,
+    pub send_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TranslatedMessagesQuery {
+    pub translate_to: String,
+    #[serde(flatten)]
+    pub page: Option<PaginationParams>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -359,7 +1072,7 @@ pub struct UpdateConversationInput {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<ConversationPriority>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub assigned_agent_id: Option<String>,
+    pub assigned_agent_id: Option<UserId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,295 @@
+use crate::error::{ErrorCode, LinktorError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailEncryption {
+    /// Implicit TLS, usually port 465 (SMTP) or 993 (IMAP).
+    Tls,
+    /// Upgrade a plain connection, usually port 587.
+    Starttls,
+    None,
+}
+
+impl EmailEncryption {
+    fn as_str(&self) -> &'static str {
+        match self {
+            EmailEncryption::Tls => "tls",
+            EmailEncryption::Starttls => "starttls",
+            EmailEncryption::None => "none",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmtpSettings {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub encryption: EmailEncryption,
+}
+
+impl SmtpSettings {
+    /// STARTTLS on port 587, the common submission setup.
+    pub fn new(host: impl Into<String>, username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port: 587,
+            username: username.into(),
+            password: password.into(),
+            encryption: EmailEncryption::Starttls,
+        }
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn encryption(mut self, encryption: EmailEncryption) -> Self {
+        self.encryption = encryption;
+        self
+    }
+}
+
+/// Mailbox polled for replies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImapSettings {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    /// Seconds between polls.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll_interval: Option<u32>,
+}
+
+impl ImapSettings {
+    /// Implicit TLS on port 993, reading `INBOX`.
+    pub fn new(host: impl Into<String>, username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port: 993,
+            username: username.into(),
+            password: password.into(),
+            folder: None,
+            poll_interval: None,
+        }
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    pub fn folder(mut self, folder: impl Into<String>) -> Self {
+        self.folder = Some(folder.into());
+        self
+    }
+
+    pub fn poll_interval(mut self, seconds: u32) -> Self {
+        self.poll_interval = Some(seconds);
+        self
+    }
+}
+
+/// Settings for an email channel sending over SMTP and receiving over IMAP,
+/// rendered to the channel's config keys by [`to_config`](Self::to_config).
+///
+/// ```rust
+/// use linktor::types::{EmailChannelConfig, ImapSettings, SmtpSettings};
+///
+/// let config = EmailChannelConfig::new("support@acme.com", SmtpSettings::new("smtp.acme.com", "support", "secret"))
+///     .from_name("Acme Support")
+///     .imap(ImapSettings::new("imap.acme.com", "support", "secret"));
+/// assert!(config.validate().is_ok());
+/// assert_eq!(config.to_config()["smtp_port"], "587");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailChannelConfig {
+    pub from_email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+    /// Address that other mailboxes forward to so their mail lands in this
+    /// inbox, e.g. when `help@` is hosted elsewhere.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarding_address: Option<String>,
+    pub smtp: SmtpSettings,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imap: Option<ImapSettings>,
+}
+
+impl EmailChannelConfig {
+    pub fn new(from_email: impl Into<String>, smtp: SmtpSettings) -> Self {
+        Self {
+            from_email: from_email.into(),
+            from_name: None,
+            reply_to: None,
+            forwarding_address: None,
+            smtp,
+            imap: None,
+        }
+    }
+
+    pub fn from_name(mut self, name: impl Into<String>) -> Self {
+        self.from_name = Some(name.into());
+        self
+    }
+
+    pub fn reply_to(mut self, address: impl Into<String>) -> Self {
+        self.reply_to = Some(address.into());
+        self
+    }
+
+    pub fn forwarding_address(mut self, address: impl Into<String>) -> Self {
+        self.forwarding_address = Some(address.into());
+        self
+    }
+
+    pub fn imap(mut self, imap: ImapSettings) -> Self {
+        self.imap = Some(imap);
+        self
+    }
+
+    /// Check addresses, hosts and ports before creating the channel. Problems
+    /// are reported per config key in the error's `details`.
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = HashMap::new();
+        let mut check = |ok: bool, key: &str, problem: &str| {
+            if !ok {
+                problems.insert(key.to_string(), serde_json::json!(problem));
+            }
+        };
+
+        check(is_address(&self.from_email), "from_email", "not an email address");
+        for (key, address) in [("reply_to", &self.reply_to), ("forwarding_address", &self.forwarding_address)] {
+            if let Some(address) = address {
+                check(is_address(address), key, "not an email address");
+            }
+        }
+        check(!self.smtp.host.trim().is_empty(), "smtp_host", "required");
+        check(self.smtp.port != 0, "smtp_port", "must not be 0");
+        check(!self.smtp.username.is_empty(), "smtp_username", "required");
+        if let Some(imap) = &self.imap {
+            check(!imap.host.trim().is_empty(), "imap_host", "required");
+            check(imap.port != 0, "imap_port", "must not be 0");
+            check(!imap.username.is_empty(), "imap_username", "required");
+            check(imap.poll_interval != Some(0), "imap_poll_interval", "must not be 0");
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+        let mut keys: Vec<&str> = problems.keys().map(String::as_str).collect();
+        keys.sort();
+        Err(LinktorError::Validation {
+            message: format!("Invalid email channel config: {}", keys.join(", ")),
+            code: Some(ErrorCode::Validation),
+            details: Some(Box::new(problems)),
+            request_id: None,
+        })
+    }
+
+    /// The channel `config` map, using the keys the email adapter reads.
+    pub fn to_config(&self) -> HashMap<String, serde_json::Value> {
+        let mut config = HashMap::new();
+        let mut set = |key: &str, value: String| {
+            config.insert(key.to_string(), serde_json::Value::String(value));
+        };
+
+        set("provider", "smtp".to_string());
+        set("from_email", self.from_email.clone());
+        if let Some(name) = &self.from_name {
+            set("from_name", name.clone());
+        }
+        if let Some(reply_to) = &self.reply_to {
+            set("reply_to", reply_to.clone());
+        }
+        if let Some(address) = &self.forwarding_address {
+            set("forwarding_address", address.clone());
+        }
+        set("smtp_host", self.smtp.host.clone());
+        set("smtp_port", self.smtp.port.to_string());
+        set("smtp_username", self.smtp.username.clone());
+        set("smtp_password", self.smtp.password.clone());
+        set("smtp_encryption", self.smtp.encryption.as_str().to_string());
+        if let Some(imap) = &self.imap {
+            set("imap_host", imap.host.clone());
+            set("imap_port", imap.port.to_string());
+            set("imap_username", imap.username.clone());
+            set("imap_password", imap.password.clone());
+            if let Some(folder) = &imap.folder {
+                set("imap_folder", folder.clone());
+            }
+            if let Some(interval) = imap.poll_interval {
+                set("imap_poll_interval", interval.to_string());
+            }
+        }
+        config
+    }
+}
+
+fn is_address(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((local, domain)) => !local.is_empty() && domain.contains('.') && !value.contains(char::is_whitespace),
+        None => false,
+    }
+}
+
+/// Outcome of `channels().test_connection()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionTest {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Individual steps, e.g. `smtp`, `imap` and `round_trip` for email.
+    #[serde(default)]
+    pub checks: Vec<ConnectionCheck>,
+}
+
+impl ConnectionTest {
+    pub fn check(&self, name: &str) -> Option<&ConnectionCheck> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionCheck {
+    pub name: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reports_each_key() {
+        let config = EmailChannelConfig::new("support", SmtpSettings::new("", "support", "secret").port(0))
+            .reply_to("help@acme.com")
+            .imap(ImapSettings::new("imap.acme.com", "", "secret").poll_interval(0));
+
+        let err = config.validate().unwrap_err();
+        let LinktorError::Validation { details: Some(details), .. } = err else {
+            panic!("expected validation error");
+        };
+        let mut keys: Vec<&String> = details.keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["from_email", "imap_poll_interval", "imap_username", "smtp_host", "smtp_port"]);
+    }
+}
@@ -0,0 +1,328 @@
+//! Typed filter expressions for contact queries, rendered to the API's
+//! filter syntax:
+//!
+//! ```text
+//! customFields.plan = "pro" AND (tags HAS "vip" OR NOT lastSeenAt EXISTS)
+//! ```
+
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    String(String),
+    Number(f64),
+    Integer(i64),
+    Bool(bool),
+}
+
+impl fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterValue::String(s) => {
+                f.write_str("\"")?;
+                for c in s.chars() {
+                    if c == '"' || c == '\\' {
+                        f.write_str("\\")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                f.write_str("\"")
+            }
+            FilterValue::Number(n) => write!(f, "{}", n),
+            FilterValue::Integer(n) => write!(f, "{}", n),
+            FilterValue::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(value: &str) -> Self {
+        FilterValue::String(value.to_string())
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(value: String) -> Self {
+        FilterValue::String(value)
+    }
+}
+
+impl From<bool> for FilterValue {
+    fn from(value: bool) -> Self {
+        FilterValue::Bool(value)
+    }
+}
+
+impl From<i32> for FilterValue {
+    fn from(value: i32) -> Self {
+        FilterValue::Integer(value.into())
+    }
+}
+
+impl From<i64> for FilterValue {
+    fn from(value: i64) -> Self {
+        FilterValue::Integer(value)
+    }
+}
+
+impl From<f64> for FilterValue {
+    fn from(value: f64) -> Self {
+        FilterValue::Number(value)
+    }
+}
+
+impl From<chrono::DateTime<chrono::Utc>> for FilterValue {
+    fn from(value: chrono::DateTime<chrono::Utc>) -> Self {
+        FilterValue::String(value.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+}
+
+impl FilterOp {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FilterOp::Eq => "=",
+            FilterOp::Ne => "!=",
+            FilterOp::Gt => ">",
+            FilterOp::Gte => ">=",
+            FilterOp::Lt => "<",
+            FilterOp::Lte => "<=",
+            FilterOp::Contains => "~",
+        }
+    }
+}
+
+/// A filter over contact fields, tags and custom fields. Build one from
+/// [`Filter::field`] or [`Filter::tag`] and combine with `and`, `or` and `not`.
+///
+/// ```rust
+/// use linktor::Filter;
+///
+/// let filter = Filter::field("customFields.plan").eq("pro").and(Filter::tag("vip"));
+/// assert_eq!(filter.to_string(), r#"customFields.plan = "pro" AND tags HAS "vip""#);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    Compare {
+        field: String,
+        op: FilterOp,
+        value: FilterValue,
+    },
+    In {
+        field: String,
+        values: Vec<FilterValue>,
+    },
+    Exists(String),
+    Tag(String),
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Not(Box<Filter>),
+}
+
+/// A field awaiting its condition; see [`Filter::field`].
+#[derive(Debug, Clone)]
+pub struct FieldFilter {
+    field: String,
+}
+
+impl FieldFilter {
+    fn compare(self, op: FilterOp, value: impl Into<FilterValue>) -> Filter {
+        Filter::Compare {
+            field: self.field,
+            op,
+            value: value.into(),
+        }
+    }
+
+    pub fn eq(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare(FilterOp::Eq, value)
+    }
+
+    pub fn ne(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare(FilterOp::Ne, value)
+    }
+
+    pub fn gt(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare(FilterOp::Gt, value)
+    }
+
+    pub fn gte(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare(FilterOp::Gte, value)
+    }
+
+    pub fn lt(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare(FilterOp::Lt, value)
+    }
+
+    pub fn lte(self, value: impl Into<FilterValue>) -> Filter {
+        self.compare(FilterOp::Lte, value)
+    }
+
+    /// Case-insensitive substring match.
+    pub fn contains(self, value: impl Into<String>) -> Filter {
+        self.compare(FilterOp::Contains, value.into())
+    }
+
+    pub fn one_of(self, values: impl IntoIterator<Item = impl Into<FilterValue>>) -> Filter {
+        Filter::In {
+            field: self.field,
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Inclusive range; dates compare as RFC 3339 timestamps.
+    pub fn between(self, low: impl Into<FilterValue>, high: impl Into<FilterValue>) -> Filter {
+        let upper = FieldFilter { field: self.field.clone() }.lte(high);
+        self.gte(low).and(upper)
+    }
+
+    /// The field is set, with any value.
+    pub fn exists(self) -> Filter {
+        Filter::Exists(self.field)
+    }
+}
+
+impl Filter {
+    /// A contact field such as `email`, or a custom field as `customFields.<name>`.
+    pub fn field(name: impl Into<String>) -> FieldFilter {
+        FieldFilter { field: name.into() }
+    }
+
+    /// Shorthand for `Filter::field("customFields.<name>")`.
+    pub fn custom_field(name: &str) -> FieldFilter {
+        Filter::field(format!("customFields.{}", name))
+    }
+
+    /// Contacts last seen within `from..=to`.
+    pub fn last_seen_between(from: chrono::DateTime<chrono::Utc>, to: chrono::DateTime<chrono::Utc>) -> Filter {
+        Filter::field("lastSeenAt").between(from, to)
+    }
+
+    /// Contacts carrying `tag`.
+    pub fn tag(tag: impl Into<String>) -> Filter {
+        Filter::Tag(tag.into())
+    }
+
+    pub fn and(self, other: Filter) -> Filter {
+        match self {
+            Filter::And(mut all) => {
+                all.push(other);
+                Filter::And(all)
+            }
+            first => Filter::And(vec![first, other]),
+        }
+    }
+
+    pub fn or(self, other: Filter) -> Filter {
+        match self {
+            Filter::Or(mut any) => {
+                any.push(other);
+                Filter::Or(any)
+            }
+            first => Filter::Or(vec![first, other]),
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Filter {
+        Filter::Not(Box::new(self))
+    }
+
+    fn is_compound(&self) -> bool {
+        matches!(self, Filter::And(_) | Filter::Or(_))
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_compound() {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+
+    fn fmt_joined(f: &mut fmt::Formatter<'_>, filters: &[Filter], separator: &str) -> fmt::Result {
+        for (i, filter) in filters.iter().enumerate() {
+            if i > 0 {
+                f.write_str(separator)?;
+            }
+            filter.fmt_operand(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::Compare { field, op, value } => write!(f, "{} {} {}", field, op.as_str(), value),
+            Filter::In { field, values } => {
+                write!(f, "{} IN (", field)?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str(")")
+            }
+            Filter::Exists(field) => write!(f, "{} EXISTS", field),
+            Filter::Tag(tag) => write!(f, "tags HAS {}", FilterValue::from(tag.as_str())),
+            Filter::And(all) => Filter::fmt_joined(f, all, " AND "),
+            Filter::Or(any) => Filter::fmt_joined(f, any, " OR "),
+            Filter::Not(inner) => {
+                f.write_str("NOT ")?;
+                inner.fmt_operand(f)
+            }
+        }
+    }
+}
+
+impl Serialize for Filter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_filters_are_parenthesized() {
+        let filter = Filter::field("customFields.plan")
+            .eq("pro")
+            .and(Filter::tag("vip").or(Filter::field("lastSeenAt").exists().not()))
+            .and(Filter::field("name").contains("O\"Brien"))
+            .and(Filter::field("customFields.seats").one_of([5, 10]).not());
+
+        assert_eq!(
+            filter.to_string(),
+            r#"customFields.plan = "pro" AND (tags HAS "vip" OR NOT lastSeenAt EXISTS) AND name ~ "O\"Brien" AND NOT customFields.seats IN (5, 10)"#
+        );
+    }
+
+    #[test]
+    fn test_ranges_join_the_enclosing_group() {
+        let from = "2024-01-01T00:00:00Z".parse().unwrap();
+        let to = "2024-02-01T00:00:00Z".parse().unwrap();
+        let filter = Filter::custom_field("seats")
+            .between(5, 50)
+            .or(Filter::last_seen_between(from, to).and(Filter::tag("trial")));
+
+        assert_eq!(
+            filter.to_string(),
+            r#"(customFields.seats >= 5 AND customFields.seats <= 50) OR (lastSeenAt >= "2024-01-01T00:00:00Z" AND lastSeenAt <= "2024-02-01T00:00:00Z" AND tags HAS "trial")"#
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use serde::{Deserialize, Serialize};
+use crate::error::{ErrorCode, LinktorError, Result};
+use super::ids::{ConversationId, FlowId, TenantId};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -22,8 +24,8 @@ pub enum FlowExecutionStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Flow {
-    pub id: String,
-    pub tenant_id: String,
+    pub id: FlowId,
+    pub tenant_id: TenantId,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -81,12 +83,120 @@ pub struct FlowVariable {
     pub description: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowVariableType {
+    String,
+    Number,
+    Integer,
+    Boolean,
+    Object,
+    Array,
+    Any,
+}
+
+impl FlowVariableType {
+    pub fn parse(var_type: &str) -> Self {
+        match var_type.to_ascii_lowercase().as_str() {
+            "string" | "text" => FlowVariableType::String,
+            "number" | "float" | "decimal" => FlowVariableType::Number,
+            "integer" | "int" => FlowVariableType::Integer,
+            "boolean" | "bool" => FlowVariableType::Boolean,
+            "object" | "json" => FlowVariableType::Object,
+            "array" | "list" => FlowVariableType::Array,
+            _ => FlowVariableType::Any,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlowVariableType::String => "string",
+            FlowVariableType::Number => "number",
+            FlowVariableType::Integer => "integer",
+            FlowVariableType::Boolean => "boolean",
+            FlowVariableType::Object => "object",
+            FlowVariableType::Array => "array",
+            FlowVariableType::Any => "any",
+        }
+    }
+
+    pub fn accepts(&self, value: &serde_json::Value) -> bool {
+        match self {
+            FlowVariableType::String => value.is_string(),
+            FlowVariableType::Number => value.is_number(),
+            FlowVariableType::Integer => value.is_i64() || value.is_u64(),
+            FlowVariableType::Boolean => value.is_boolean(),
+            FlowVariableType::Object => value.is_object(),
+            FlowVariableType::Array => value.is_array(),
+            FlowVariableType::Any => true,
+        }
+    }
+}
+
+/// Declared variables of a flow, used to check execution input before sending it.
+#[derive(Debug, Clone, Default)]
+pub struct FlowVariableSchema {
+    variables: HashMap<String, FlowVariableType>,
+}
+
+impl FlowVariableSchema {
+    pub fn new(variables: &[FlowVariable]) -> Self {
+        Self {
+            variables: variables
+                .iter()
+                .map(|v| (v.name.clone(), FlowVariableType::parse(&v.var_type)))
+                .collect(),
+        }
+    }
+
+    pub fn variable_type(&self, name: &str) -> Option<FlowVariableType> {
+        self.variables.get(name).copied()
+    }
+
+    /// Reject unknown variables and values of the wrong type. Problems are
+    /// reported per variable in the error's `details`.
+    pub fn validate(&self, values: &HashMap<String, serde_json::Value>) -> Result<()> {
+        let mut problems = HashMap::new();
+        for (name, value) in values {
+            match self.variables.get(name) {
+                None => {
+                    problems.insert(name.clone(), serde_json::json!("unknown variable"));
+                }
+                Some(expected) if !expected.accepts(value) => {
+                    problems.insert(name.clone(), serde_json::json!(format!("expected {}", expected.as_str())));
+                }
+                Some(_) => {}
+            }
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+        let mut names: Vec<&String> = problems.keys().collect();
+        names.sort();
+        Err(LinktorError::Validation {
+            message: format!(
+                "Invalid flow variables: {}",
+                names.iter().map(|n| n.as_str()).collect::<Vec<_>>().join(", ")
+            ),
+            code: Some(ErrorCode::Validation),
+            details: Some(Box::new(problems)),
+            request_id: None,
+        })
+    }
+}
+
+impl Flow {
+    pub fn variable_schema(&self) -> FlowVariableSchema {
+        FlowVariableSchema::new(&self.variables)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FlowExecution {
     pub id: String,
-    pub flow_id: String,
-    pub conversation_id: String,
+    pub flow_id: FlowId,
+    pub conversation_id: ConversationId,
     pub status: FlowExecutionStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub current_node_id: Option<String>,
@@ -101,6 +211,17 @@ pub struct FlowExecution {
     pub error: Option<String>,
 }
 
+impl FlowExecution {
+    /// Read an execution variable as `T`. Returns `Ok(None)` when the variable
+    /// is not set and an error when it cannot be converted to `T`.
+    pub fn variable<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>> {
+        match self.variables.as_ref().and_then(|vars| vars.get(name)) {
+            Some(value) => Ok(Some(serde_json::from_value(value.clone())?)),
+            None => Ok(None),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FlowExecutionStep {
@@ -169,13 +290,13 @@ pub struct UpdateFlowInput {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteFlowInput {
-    pub conversation_id: String,
+    pub conversation_id: ConversationId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variables: Option<HashMap<String, serde_json::Value>>,
 }
 
 impl ExecuteFlowInput {
-    pub fn new(conversation_id: impl Into<String>) -> Self {
+    pub fn new(conversation_id: impl Into<ConversationId>) -> Self {
         Self {
             conversation_id: conversation_id.into(),
             variables: None,
@@ -186,4 +307,48 @@ impl ExecuteFlowInput {
         self.variables = Some(vars);
         self
     }
+
+    pub fn variable(mut self, name: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.variables.get_or_insert_with(HashMap::new).insert(name.into(), value.into());
+        self
+    }
+
+    pub fn validate(&self, schema: &FlowVariableSchema) -> Result<()> {
+        match self.variables {
+            Some(ref vars) => schema.validate(vars),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_validation() {
+        let schema = FlowVariableSchema::new(&[
+            FlowVariable {
+                name: "orderId".to_string(),
+                var_type: "string".to_string(),
+                default_value: None,
+                description: None,
+            },
+            FlowVariable {
+                name: "attempts".to_string(),
+                var_type: "integer".to_string(),
+                default_value: None,
+                description: None,
+            },
+        ]);
+
+        let ok = ExecuteFlowInput::new("conv-1").variable("orderId", "42").variable("attempts", 3);
+        assert!(ok.validate(&schema).is_ok());
+
+        let bad = ExecuteFlowInput::new("conv-1").variable("attempts", "3").variable("typo", true);
+        let err = bad.validate(&schema).unwrap_err();
+        let details = err.details().unwrap();
+        assert!(details.contains_key("attempts"));
+        assert!(details.contains_key("typo"));
+    }
 }
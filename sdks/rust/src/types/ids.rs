@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+macro_rules! id_type {
+    ($($(#[$meta:meta])* $name:ident;)*) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(id: impl Into<String>) -> Self {
+                Self(id.into())
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self(id.to_string())
+            }
+        }
+
+        impl From<&String> for $name {
+            fn from(id: &String) -> Self {
+                Self(id.clone())
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(id: &$name) -> Self {
+                id.clone()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+    )*};
+}
+
+id_type! {
+    /// Identifies a [`Conversation`](super::Conversation).
+    ConversationId;
+    /// Identifies a [`Message`](super::Message).
+    MessageId;
+    /// Identifies a [`Contact`](super::Contact).
+    ContactId;
+    /// Identifies a [`Channel`](super::Channel).
+    ChannelId;
+    /// Identifies a [`Bot`](super::Bot).
+    BotId;
+    /// Identifies a [`User`](super::User), including agents assigned to conversations.
+    UserId;
+    /// Identifies a [`Flow`](super::Flow).
+    FlowId;
+    /// Identifies a [`KnowledgeBase`](super::KnowledgeBase).
+    KnowledgeBaseId;
+    /// Identifies an AI [`Agent`](super::Agent).
+    AgentId;
+    /// Identifies a [`Tenant`](super::Tenant).
+    TenantId;
+    /// Identifies a webhook endpoint registration.
+    WebhookId;
+    /// Identifies an [`UploadedMedia`](super::UploadedMedia) file.
+    MediaId;
+    /// Identifies a [`ScheduledMessage`](super::ScheduledMessage).
+    ScheduledMessageId;
+    /// Identifies a [`Broadcast`](super::Broadcast).
+    BroadcastId;
+    /// Identifies a marketing campaign.
+    CampaignId;
+    /// Identifies a [`ContactErasure`](super::ContactErasure) job.
+    ErasureId;
+    /// Identifies a [`ReauthorizationSession`](super::ReauthorizationSession).
+    ReauthorizationSessionId;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_are_transparent_strings() {
+        let id = ConversationId::from("conv-1");
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"conv-1\"");
+        assert_eq!(serde_json::from_str::<ConversationId>("\"conv-1\"").unwrap(), id);
+        assert_eq!(id, "conv-1");
+        assert_eq!(id.to_string(), "conv-1");
+    }
+}
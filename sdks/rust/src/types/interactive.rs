@@ -0,0 +1,274 @@
+use crate::error::{ErrorCode, LinktorError, Result};
+use super::conversation::{
+    Button, InteractiveAction, InteractiveBody, InteractiveContent, InteractiveFooter, InteractiveHeader, Section,
+    SectionRow,
+};
+use std::collections::HashMap;
+
+/// Limits enforced by WhatsApp, the strictest channel for interactive messages.
+pub const MAX_BUTTONS: usize = 3;
+pub const MAX_LIST_ROWS: usize = 10;
+pub const MAX_LIST_SECTIONS: usize = 10;
+pub const MAX_BUTTON_TITLE_LEN: usize = 20;
+pub const MAX_ROW_TITLE_LEN: usize = 24;
+pub const MAX_ROW_DESCRIPTION_LEN: usize = 72;
+pub const MAX_SECTION_TITLE_LEN: usize = 24;
+pub const MAX_BODY_LEN: usize = 1024;
+pub const MAX_HEADER_LEN: usize = 60;
+pub const MAX_FOOTER_LEN: usize = 60;
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Buttons(Vec<Button>),
+    List { button: String, sections: Vec<Section> },
+}
+
+/// Reply buttons or a list menu, checked against channel limits when built.
+///
+/// ```rust
+/// use linktor::types::{InteractiveMessage, SectionRow};
+///
+/// let confirm = InteractiveMessage::buttons("Confirm your order?")
+///     .button("yes", "Yes")
+///     .button("no", "No")
+///     .build()?;
+///
+/// let slots = InteractiveMessage::list("Pick a time", "See times")
+///     .section("Morning", [SectionRow::new("9", "09:00"), SectionRow::new("10", "10:00")])
+///     .footer("Times are in BRT")
+///     .build()?;
+/// # Ok::<(), linktor::LinktorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct InteractiveMessage {
+    body: String,
+    header: Option<String>,
+    footer: Option<String>,
+    kind: Kind,
+}
+
+impl InteractiveMessage {
+    /// Up to three reply buttons under `body`.
+    pub fn buttons(body: impl Into<String>) -> Self {
+        Self::new(body.into(), Kind::Buttons(Vec::new()))
+    }
+
+    /// A menu opened by a button labelled `button`, with up to ten rows
+    /// across its sections.
+    pub fn list(body: impl Into<String>, button: impl Into<String>) -> Self {
+        Self::new(
+            body.into(),
+            Kind::List {
+                button: button.into(),
+                sections: Vec::new(),
+            },
+        )
+    }
+
+    fn new(body: String, kind: Kind) -> Self {
+        Self {
+            body,
+            header: None,
+            footer: None,
+            kind,
+        }
+    }
+
+    pub fn header(mut self, text: impl Into<String>) -> Self {
+        self.header = Some(text.into());
+        self
+    }
+
+    pub fn footer(mut self, text: impl Into<String>) -> Self {
+        self.footer = Some(text.into());
+        self
+    }
+
+    /// Add a reply button; `id` is echoed back when the contact taps it.
+    /// Ignored for lists.
+    pub fn button(mut self, id: impl Into<String>, title: impl Into<String>) -> Self {
+        if let Kind::Buttons(ref mut buttons) = self.kind {
+            buttons.push(Button {
+                button_type: "reply".to_string(),
+                id: id.into(),
+                title: title.into(),
+            });
+        }
+        self
+    }
+
+    /// Add a list section. Ignored for button messages.
+    pub fn section(mut self, title: impl Into<String>, rows: impl IntoIterator<Item = SectionRow>) -> Self {
+        if let Kind::List { ref mut sections, .. } = self.kind {
+            sections.push(Section {
+                title: title.into(),
+                rows: rows.into_iter().collect(),
+            });
+        }
+        self
+    }
+
+    /// Check channel limits and build the message content. Every problem is
+    /// reported in the error's `details`, keyed by field.
+    pub fn build(self) -> Result<InteractiveContent> {
+        let mut problems = Problems::default();
+        problems.length("body", &self.body, MAX_BODY_LEN);
+        if self.body.trim().is_empty() {
+            problems.add("body", "must not be empty".to_string());
+        }
+        if let Some(ref header) = self.header {
+            problems.length("header", header, MAX_HEADER_LEN);
+        }
+        if let Some(ref footer) = self.footer {
+            problems.length("footer", footer, MAX_FOOTER_LEN);
+        }
+
+        let (interactive_type, action) = match self.kind {
+            Kind::Buttons(buttons) => {
+                problems.count("buttons", buttons.len(), MAX_BUTTONS);
+                for (i, button) in buttons.iter().enumerate() {
+                    problems.length(&format!("buttons[{}].title", i), &button.title, MAX_BUTTON_TITLE_LEN);
+                }
+                problems.unique_ids(buttons.iter().map(|b| b.id.as_str()));
+                let action = InteractiveAction {
+                    button: None,
+                    buttons,
+                    sections: Vec::new(),
+                };
+                ("button", action)
+            }
+            Kind::List { button, sections } => {
+                problems.length("button", &button, MAX_BUTTON_TITLE_LEN);
+                problems.count("sections", sections.len(), MAX_LIST_SECTIONS);
+                problems.count("rows", sections.iter().map(|s| s.rows.len()).sum(), MAX_LIST_ROWS);
+                for (i, section) in sections.iter().enumerate() {
+                    let field = format!("sections[{}].title", i);
+                    problems.length(&field, &section.title, MAX_SECTION_TITLE_LEN);
+                    if sections.len() > 1 && section.title.trim().is_empty() {
+                        problems.add(&field, "required when there are several sections".to_string());
+                    }
+                    for (j, row) in section.rows.iter().enumerate() {
+                        let field = format!("sections[{}].rows[{}]", i, j);
+                        problems.length(&format!("{}.title", field), &row.title, MAX_ROW_TITLE_LEN);
+                        if let Some(ref description) = row.description {
+                            problems.length(&format!("{}.description", field), description, MAX_ROW_DESCRIPTION_LEN);
+                        }
+                    }
+                }
+                problems.unique_ids(sections.iter().flat_map(|s| s.rows.iter().map(|r| r.id.as_str())));
+                let action = InteractiveAction {
+                    button: Some(button),
+                    buttons: Vec::new(),
+                    sections,
+                };
+                ("list", action)
+            }
+        };
+        problems.into_result()?;
+
+        Ok(InteractiveContent {
+            interactive_type: interactive_type.to_string(),
+            header: self.header.map(|text| InteractiveHeader {
+                header_type: "text".to_string(),
+                text: Some(text),
+                image: None,
+                video: None,
+                document: None,
+            }),
+            body: Some(InteractiveBody { text: self.body }),
+            footer: self.footer.map(|text| InteractiveFooter { text }),
+            action: Some(action),
+        })
+    }
+}
+
+#[derive(Default)]
+struct Problems(HashMap<String, serde_json::Value>);
+
+impl Problems {
+    fn add(&mut self, field: &str, problem: String) {
+        self.0.insert(field.to_string(), serde_json::json!(problem));
+    }
+
+    fn length(&mut self, field: &str, value: &str, max: usize) {
+        let len = value.chars().count();
+        if len > max {
+            self.add(field, format!("{} characters, at most {} allowed", len, max));
+        }
+    }
+
+    fn count(&mut self, field: &str, count: usize, max: usize) {
+        if count == 0 {
+            self.add(field, "at least one required".to_string());
+        } else if count > max {
+            self.add(field, format!("{} given, at most {} allowed", count, max));
+        }
+    }
+
+    fn unique_ids<'a>(&mut self, ids: impl Iterator<Item = &'a str>) {
+        let mut seen = std::collections::HashSet::new();
+        for id in ids {
+            if !seen.insert(id) {
+                self.add("ids", format!("duplicate id '{}'", id));
+            }
+        }
+    }
+
+    fn into_result(self) -> Result<()> {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        let mut fields: Vec<&str> = self.0.keys().map(String::as_str).collect();
+        fields.sort();
+        Err(LinktorError::Validation {
+            message: format!("Invalid interactive message: {}", fields.join(", ")),
+            code: Some(ErrorCode::Validation),
+            details: Some(Box::new(self.0)),
+            request_id: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buttons_within_limits() {
+        let content = InteractiveMessage::buttons("Confirm?")
+            .button("yes", "Yes")
+            .button("no", "No")
+            .footer("Reply within 24h")
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&content).unwrap();
+        assert_eq!(json["type"], "button");
+        assert_eq!(json["action"]["buttons"][1]["id"], "no");
+        assert!(json["action"].get("button").is_none());
+    }
+
+    #[test]
+    fn test_limits_are_reported_per_field() {
+        let err = InteractiveMessage::buttons("Pick one")
+            .button("a", "A")
+            .button("b", "B")
+            .button("c", "C")
+            .button("a", "This title is far too long")
+            .build()
+            .unwrap_err();
+        let details = err.details().unwrap();
+        assert!(details.contains_key("buttons"));
+        assert!(details.contains_key("buttons[3].title"));
+        assert!(details.contains_key("ids"));
+
+        let rows = (0..11).map(|i| SectionRow::new(i.to_string(), format!("Slot {}", i)));
+        let err = InteractiveMessage::list("Pick a time", "See times").section("Slots", rows).build().unwrap_err();
+        assert_eq!(err.details().unwrap()["rows"], "11 given, at most 10 allowed");
+
+        let list = InteractiveMessage::list("Pick a time", "See times")
+            .section("", [SectionRow::new("9", "09:00").description("Morning")])
+            .build()
+            .unwrap();
+        assert_eq!(list.action.unwrap().button.as_deref(), Some("See times"));
+    }
+}
@@ -1,3 +1,4 @@
+use super::ids::{KnowledgeBaseId, TenantId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -22,8 +23,8 @@ pub enum DocumentStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct KnowledgeBase {
-    pub id: String,
-    pub tenant_id: String,
+    pub id: KnowledgeBaseId,
+    pub tenant_id: TenantId,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -43,7 +44,7 @@ pub struct KnowledgeBase {
 #[serde(rename_all = "camelCase")]
 pub struct Document {
     pub id: String,
-    pub knowledge_base_id: String,
+    pub knowledge_base_id: KnowledgeBaseId,
     pub name: String,
     #[serde(rename = "type")]
     pub doc_type: String,
@@ -52,6 +53,9 @@ pub struct Document {
     pub status: DocumentStatus,
     pub size: i64,
     pub chunk_count: i32,
+    /// Partition of the knowledge base the document belongs to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -75,6 +79,17 @@ pub struct ScoredChunk {
     pub document: Option<Document>,
 }
 
+impl ScoredChunk {
+    /// The namespace of the chunk's document, from the embedded document or
+    /// the chunk's `namespace` metadata.
+    pub fn namespace(&self) -> Option<&str> {
+        self.document
+            .as_ref()
+            .and_then(|d| d.namespace.as_deref())
+            .or_else(|| self.metadata.as_ref()?.get("namespace")?.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryResult {
@@ -83,6 +98,128 @@ pub struct QueryResult {
     pub model: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CitationStyle {
+    /// `[1] FAQ (https://example.com/faq)`
+    Numbered,
+    /// `[^1]: FAQ (https://example.com/faq)`
+    Footnote,
+    /// `[1] FAQ: "first words of the cited passage..."`
+    Excerpt,
+}
+
+const EXCERPT_LEN: usize = 160;
+
+/// Consecutive chunks of one document joined into a single passage.
+#[derive(Debug, Clone)]
+pub struct MergedChunk {
+    pub document_id: String,
+    pub document: Option<Document>,
+    pub chunk_indices: Vec<i32>,
+    pub content: String,
+    pub score: f64,
+}
+
+impl MergedChunk {
+    pub fn title(&self) -> &str {
+        self.document.as_ref().map(|d| d.name.as_str()).unwrap_or(&self.document_id)
+    }
+
+    pub fn source_url(&self) -> Option<&str> {
+        self.document.as_ref().and_then(|d| d.source_url.as_deref())
+    }
+}
+
+impl QueryResult {
+    /// Keep only chunks tagged with one of `namespaces`. Untagged chunks are
+    /// dropped too, since nothing says they belong to the caller.
+    pub(crate) fn retain_namespaces(&mut self, namespaces: &[String]) {
+        self.chunks
+            .retain(|chunk| chunk.namespace().is_some_and(|ns| namespaces.iter().any(|n| n == ns)));
+    }
+
+    /// Group chunks by document, drop duplicates and join chunks with adjacent
+    /// indices. Passages are ordered by their best score, highest first.
+    pub fn merge_chunks_by_document(&self) -> Vec<MergedChunk> {
+        let mut by_document: Vec<(String, Vec<&ScoredChunk>)> = Vec::new();
+        for chunk in &self.chunks {
+            match by_document.iter_mut().find(|(id, _)| *id == chunk.document_id) {
+                Some((_, chunks)) => {
+                    if !chunks.iter().any(|c| c.chunk_index == chunk.chunk_index) {
+                        chunks.push(chunk);
+                    }
+                }
+                None => by_document.push((chunk.document_id.clone(), vec![chunk])),
+            }
+        }
+
+        let mut merged: Vec<MergedChunk> = Vec::new();
+        for (document_id, mut chunks) in by_document {
+            chunks.sort_by_key(|c| c.chunk_index);
+            let mut current: Option<MergedChunk> = None;
+            for chunk in chunks {
+                match current {
+                    Some(ref mut m) if m.chunk_indices.last() == Some(&(chunk.chunk_index - 1)) => {
+                        m.chunk_indices.push(chunk.chunk_index);
+                        m.content.push('\n');
+                        m.content.push_str(&chunk.content);
+                        m.score = m.score.max(chunk.score);
+                        if m.document.is_none() {
+                            m.document = chunk.document.clone();
+                        }
+                    }
+                    _ => {
+                        merged.extend(current.take());
+                        current = Some(MergedChunk {
+                            document_id: document_id.clone(),
+                            document: chunk.document.clone(),
+                            chunk_indices: vec![chunk.chunk_index],
+                            content: chunk.content.clone(),
+                            score: chunk.score,
+                        });
+                    }
+                }
+            }
+            merged.extend(current);
+        }
+
+        merged.sort_by(|a, b| b.score.total_cmp(&a.score));
+        merged
+    }
+
+    /// Numbered citation strings, one per cited document, in score order.
+    pub fn to_citations(&self, style: CitationStyle) -> Vec<String> {
+        let mut documents: Vec<MergedChunk> = Vec::new();
+        for passage in self.merge_chunks_by_document() {
+            if !documents.iter().any(|d| d.document_id == passage.document_id) {
+                documents.push(passage);
+            }
+        }
+
+        documents
+            .iter()
+            .enumerate()
+            .map(|(i, passage)| {
+                let n = i + 1;
+                let source = passage.source_url().map(|url| format!(" ({})", url)).unwrap_or_default();
+                match style {
+                    CitationStyle::Numbered => format!("[{}] {}{}", n, passage.title(), source),
+                    CitationStyle::Footnote => format!("[^{}]: {}{}", n, passage.title(), source),
+                    CitationStyle::Excerpt => format!("[{}] {}: \"{}\"", n, passage.title(), excerpt(&passage.content)),
+                }
+            })
+            .collect()
+    }
+}
+
+fn excerpt(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(EXCERPT_LEN) {
+        Some((idx, _)) => format!("{}...", text[..idx].trim_end()),
+        None => text,
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateKnowledgeBaseInput {
@@ -132,6 +269,10 @@ pub struct AddDocumentInput {
     pub content: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_url: Option<String>,
+    /// Partition to store the document in; queries restricted to other
+    /// namespaces never return it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
@@ -142,6 +283,7 @@ impl AddDocumentInput {
             name: name.into(),
             content: None,
             source_url: None,
+            namespace: None,
             metadata: None,
         }
     }
@@ -155,6 +297,11 @@ impl AddDocumentInput {
         self.source_url = Some(url.into());
         self
     }
+
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -167,6 +314,9 @@ pub struct QueryKnowledgeBaseInput {
     pub min_score: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filter: Option<HashMap<String, serde_json::Value>>,
+    /// Search only documents in these namespaces; empty searches all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub namespaces: Vec<String>,
 }
 
 impl QueryKnowledgeBaseInput {
@@ -181,4 +331,68 @@ impl QueryKnowledgeBaseInput {
         self.top_k = Some(k);
         self
     }
+
+    pub fn namespaces(mut self, namespaces: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.namespaces = namespaces.into_iter().map(|n| n.as_ref().to_string()).collect();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(document_id: &str, index: i32, content: &str, score: f64) -> ScoredChunk {
+        ScoredChunk {
+            id: format!("{}-{}", document_id, index),
+            document_id: document_id.to_string(),
+            content: content.to_string(),
+            chunk_index: index,
+            token_count: 1,
+            score,
+            metadata: None,
+            document: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_and_cite() {
+        let result = QueryResult {
+            chunks: vec![
+                chunk("faq", 2, "second", 0.7),
+                chunk("faq", 1, "first", 0.8),
+                chunk("guide", 0, "intro", 0.9),
+                chunk("faq", 1, "first", 0.8),
+                chunk("faq", 5, "later", 0.5),
+            ],
+            query: "q".to_string(),
+            model: "m".to_string(),
+        };
+
+        let merged = result.merge_chunks_by_document();
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].document_id, "guide");
+        assert_eq!(merged[1].chunk_indices, vec![1, 2]);
+        assert_eq!(merged[1].content, "first\nsecond");
+
+        let citations = result.to_citations(CitationStyle::Numbered);
+        assert_eq!(citations, vec!["[1] guide", "[2] faq"]);
+    }
+
+    #[test]
+    fn test_retain_namespaces() {
+        let mut tagged = chunk("faq", 0, "acme answer", 0.9);
+        tagged.metadata = Some(HashMap::from([("namespace".to_string(), serde_json::json!("acme"))]));
+        let mut other = chunk("faq", 1, "globex answer", 0.8);
+        other.metadata = Some(HashMap::from([("namespace".to_string(), serde_json::json!("globex"))]));
+        let mut result = QueryResult {
+            chunks: vec![tagged, other, chunk("guide", 0, "untagged", 0.5)],
+            query: "q".to_string(),
+            model: "m".to_string(),
+        };
+
+        result.retain_namespaces(&["acme".to_string()]);
+        let contents: Vec<&str> = result.chunks.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["acme answer"]);
+    }
 }
@@ -0,0 +1,31 @@
+use super::conversation::MediaContent;
+use super::ids::MediaId;
+use serde::{Deserialize, Serialize};
+
+/// A file hosted by Linktor, ready to be referenced from a message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadedMedia {
+    pub id: MediaId,
+    pub url: String,
+    pub mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    pub size: i64,
+    /// When the hosted copy is removed; `None` for files kept indefinitely.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl UploadedMedia {
+    /// Message content pointing at this file.
+    pub fn content(&self, caption: Option<&str>) -> MediaContent {
+        MediaContent {
+            url: self.url.clone(),
+            mime_type: Some(self.mime_type.clone()),
+            filename: self.filename.clone(),
+            size: Some(self.size),
+            caption: caption.map(String::from),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod common;
+pub mod ids;
 pub mod auth;
 pub mod conversation;
 pub mod contact;
@@ -9,8 +10,20 @@ pub mod knowledge;
 pub mod flow;
 pub mod webhook;
 pub mod vre;
+pub mod retention;
+pub mod sms;
+pub mod batch;
+pub mod compliance;
+pub mod filter;
+pub mod media;
+pub mod template;
+pub mod interactive;
+pub mod broadcast;
+pub mod campaign;
+pub mod email;
 
 pub use common::*;
+pub use ids::*;
 pub use auth::*;
 pub use conversation::*;
 pub use contact::*;
@@ -21,3 +34,14 @@ pub use knowledge::*;
 pub use flow::*;
 pub use webhook::*;
 pub use vre::*;
+pub use retention::*;
+pub use sms::*;
+pub use batch::*;
+pub use compliance::*;
+pub use filter::*;
+pub use media::*;
+pub use template::*;
+pub use interactive::*;
+pub use broadcast::*;
+pub use campaign::*;
+pub use email::*;
@@ -0,0 +1,157 @@
+use super::ids::{ChannelId, TenantId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionEntity {
+    Messages,
+    Media,
+    Conversations,
+    Contacts,
+    AuditLogs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionAction {
+    Anonymize,
+    Delete,
+    Purge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PurgeBatchStatus {
+    Scheduled,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    pub id: String,
+    pub tenant_id: TenantId,
+    pub name: String,
+    pub entity: RetentionEntity,
+    pub action: RetentionAction,
+    pub older_than_days: i32,
+    pub enabled: bool,
+    #[serde(default)]
+    pub channel_ids: Vec<ChannelId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeBatch {
+    pub id: String,
+    pub policy_id: String,
+    pub entity: RetentionEntity,
+    pub action: RetentionAction,
+    pub status: PurgeBatchStatus,
+    pub estimated_count: i64,
+    pub scheduled_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cutoff: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// Input types
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRetentionPolicyInput {
+    pub name: String,
+    pub entity: RetentionEntity,
+    pub action: RetentionAction,
+    pub older_than_days: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_ids: Option<Vec<ChannelId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+impl CreateRetentionPolicyInput {
+    pub fn new(name: impl Into<String>, entity: RetentionEntity, action: RetentionAction, older_than_days: i32) -> Self {
+        Self {
+            name: name.into(),
+            entity,
+            action,
+            older_than_days,
+            enabled: None,
+            channel_ids: None,
+            metadata: None,
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    pub fn channel_ids(mut self, ids: impl IntoIterator<Item = impl Into<ChannelId>>) -> Self {
+        self.channel_ids = Some(ids.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRetentionPolicyInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<RetentionAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub older_than_days: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_ids: Option<Vec<ChannelId>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPurgeBatchesParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity: Option<RetentionEntity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
+}
+
+impl ListPurgeBatchesParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn policy_id(mut self, id: impl Into<String>) -> Self {
+        self.policy_id = Some(id.into());
+        self
+    }
+
+    pub fn entity(mut self, entity: RetentionEntity) -> Self {
+        self.entity = Some(entity);
+        self
+    }
+
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
@@ -0,0 +1,43 @@
+use super::conversation::TextEncoding;
+use super::ids::ChannelId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateSmsCostInput {
+    pub channel_id: ChannelId,
+    pub text: String,
+    pub recipients: Vec<String>,
+}
+
+impl EstimateSmsCostInput {
+    pub fn new(channel_id: impl Into<ChannelId>, text: impl Into<String>, recipients: Vec<String>) -> Self {
+        Self {
+            channel_id: channel_id.into(),
+            text: text.into(),
+            recipients,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmsCostEstimate {
+    pub channel_id: ChannelId,
+    pub encoding: TextEncoding,
+    pub segments: i32,
+    pub recipients: i32,
+    pub total_cost: f64,
+    pub currency: String,
+    #[serde(default)]
+    pub breakdown: Vec<SmsCostBreakdown>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmsCostBreakdown {
+    pub country_code: String,
+    pub recipients: i32,
+    pub cost_per_segment: f64,
+    pub cost: f64,
+}
@@ -0,0 +1,271 @@
+use super::conversation::{MediaContent, TemplateComponent, TemplateContent, TemplateCurrency, TemplateDateTime, TemplateParameter};
+
+/// A typed value for a template placeholder.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateParam {
+    Text(String),
+    /// `amount` in major units, e.g. `12.5` for R$ 12,50.
+    Currency {
+        code: String,
+        amount: f64,
+        fallback: String,
+    },
+    DateTime(String),
+    Image(String),
+    Video(String),
+    Document {
+        url: String,
+        filename: Option<String>,
+    },
+}
+
+impl TemplateParam {
+    pub fn text(text: impl Into<String>) -> Self {
+        TemplateParam::Text(text.into())
+    }
+
+    pub fn currency(code: impl Into<String>, amount: f64, fallback: impl Into<String>) -> Self {
+        TemplateParam::Currency {
+            code: code.into(),
+            amount,
+            fallback: fallback.into(),
+        }
+    }
+
+    /// A date shown as `fallback`, e.g. `March 5, 2024`.
+    pub fn date_time(fallback: impl Into<String>) -> Self {
+        TemplateParam::DateTime(fallback.into())
+    }
+
+    pub fn image(url: impl Into<String>) -> Self {
+        TemplateParam::Image(url.into())
+    }
+
+    pub fn video(url: impl Into<String>) -> Self {
+        TemplateParam::Video(url.into())
+    }
+
+    pub fn document(url: impl Into<String>, filename: impl Into<String>) -> Self {
+        TemplateParam::Document {
+            url: url.into(),
+            filename: Some(filename.into()),
+        }
+    }
+
+    fn into_parameter(self) -> TemplateParameter {
+        let mut parameter = empty_parameter("");
+        let media = |url: String, filename: Option<String>| MediaContent {
+            url,
+            mime_type: None,
+            filename,
+            size: None,
+            caption: None,
+        };
+        match self {
+            TemplateParam::Text(text) => {
+                parameter.param_type = "text".to_string();
+                parameter.text = Some(text);
+            }
+            TemplateParam::Currency { code, amount, fallback } => {
+                parameter.param_type = "currency".to_string();
+                parameter.currency = Some(TemplateCurrency {
+                    fallback_value: fallback,
+                    code,
+                    amount_1000: (amount * 1000.0).round() as i64,
+                });
+            }
+            TemplateParam::DateTime(fallback) => {
+                parameter.param_type = "date_time".to_string();
+                parameter.date_time = Some(TemplateDateTime { fallback_value: fallback });
+            }
+            TemplateParam::Image(url) => {
+                parameter.param_type = "image".to_string();
+                parameter.image = Some(media(url, None));
+            }
+            TemplateParam::Video(url) => {
+                parameter.param_type = "video".to_string();
+                parameter.video = Some(media(url, None));
+            }
+            TemplateParam::Document { url, filename } => {
+                parameter.param_type = "document".to_string();
+                parameter.document = Some(media(url, filename));
+            }
+        }
+        parameter
+    }
+}
+
+fn empty_parameter(param_type: &str) -> TemplateParameter {
+    TemplateParameter {
+        param_type: param_type.to_string(),
+        text: None,
+        image: None,
+        document: None,
+        video: None,
+        currency: None,
+        date_time: None,
+        payload: None,
+        coupon_code: None,
+    }
+}
+
+impl From<&str> for TemplateParam {
+    fn from(text: &str) -> Self {
+        TemplateParam::Text(text.to_string())
+    }
+}
+
+impl From<String> for TemplateParam {
+    fn from(text: String) -> Self {
+        TemplateParam::Text(text)
+    }
+}
+
+/// Fluent construction of a [`TemplateContent`] for an approved WhatsApp
+/// template. Parameters fill the template's `{{1}}`, `{{2}}`, ... placeholders
+/// in the order they are added.
+///
+/// ```rust
+/// use linktor::types::{TemplateMessageBuilder, TemplateParam};
+///
+/// let template = TemplateMessageBuilder::new("order_shipped", "pt_BR")
+///     .header(TemplateParam::image("https://cdn.example.com/box.png"))
+///     .body("Maria")
+///     .body(TemplateParam::currency("BRL", 149.9, "R$ 149,90"))
+///     .url_button(0, "tracking/AB123")
+///     .build();
+/// assert_eq!(template.components.len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TemplateMessageBuilder {
+    name: String,
+    language: String,
+    header: Vec<TemplateParam>,
+    body: Vec<TemplateParam>,
+    buttons: Vec<TemplateComponent>,
+}
+
+impl TemplateMessageBuilder {
+    /// `language` is the template's language code, e.g. `pt_BR` or `en_US`.
+    pub fn new(name: impl Into<String>, language: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            language: language.into(),
+            header: Vec::new(),
+            body: Vec::new(),
+            buttons: Vec::new(),
+        }
+    }
+
+    /// Add a header parameter: text, or the image, video or document of a
+    /// media header.
+    pub fn header(mut self, param: impl Into<TemplateParam>) -> Self {
+        self.header.push(param.into());
+        self
+    }
+
+    /// Add the next body parameter.
+    pub fn body(mut self, param: impl Into<TemplateParam>) -> Self {
+        self.body.push(param.into());
+        self
+    }
+
+    /// Set the payload of the quick-reply button at `index`.
+    pub fn quick_reply_button(self, index: u32, payload: impl Into<String>) -> Self {
+        let parameter = TemplateParameter {
+            payload: Some(payload.into()),
+            ..empty_parameter("payload")
+        };
+        self.button("quick_reply", index, parameter)
+    }
+
+    /// Fill the dynamic suffix of the URL button at `index`.
+    pub fn url_button(self, index: u32, suffix: impl Into<String>) -> Self {
+        self.button("url", index, TemplateParam::text(suffix).into_parameter())
+    }
+
+    /// Set the code copied by the copy-code button at `index`.
+    pub fn copy_code_button(self, index: u32, code: impl Into<String>) -> Self {
+        let parameter = TemplateParameter {
+            coupon_code: Some(code.into()),
+            ..empty_parameter("coupon_code")
+        };
+        self.button("copy_code", index, parameter)
+    }
+
+    fn button(mut self, sub_type: &str, index: u32, parameter: TemplateParameter) -> Self {
+        self.buttons.push(TemplateComponent {
+            component_type: "button".to_string(),
+            sub_type: Some(sub_type.to_string()),
+            index: Some(index),
+            parameters: vec![parameter],
+        });
+        self
+    }
+
+    pub fn build(self) -> TemplateContent {
+        let section = |component_type: &str, params: Vec<TemplateParam>| TemplateComponent {
+            component_type: component_type.to_string(),
+            sub_type: None,
+            index: None,
+            parameters: params.into_iter().map(TemplateParam::into_parameter).collect(),
+        };
+        let mut components = Vec::new();
+        if !self.header.is_empty() {
+            components.push(section("header", self.header));
+        }
+        if !self.body.is_empty() {
+            components.push(section("body", self.body));
+        }
+        components.extend(self.buttons);
+        TemplateContent {
+            name: self.name,
+            language: self.language,
+            components,
+        }
+    }
+}
+
+impl From<TemplateMessageBuilder> for TemplateContent {
+    fn from(builder: TemplateMessageBuilder) -> Self {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_serializes_components() {
+        let template = TemplateMessageBuilder::new("appointment", "en_US")
+            .header(TemplateParam::document("https://cdn.example.com/a.pdf", "a.pdf"))
+            .body("Ana")
+            .body(TemplateParam::date_time("March 5, 2024"))
+            .body(TemplateParam::currency("USD", 12.345, "$12.35"))
+            .quick_reply_button(0, "confirm-42")
+            .copy_code_button(1, "SAVE10")
+            .build();
+
+        let json = serde_json::to_value(&template).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "name": "appointment",
+                "language": "en_US",
+                "components": [
+                    { "type": "header", "parameters": [
+                        { "type": "document", "document": { "url": "https://cdn.example.com/a.pdf", "filename": "a.pdf" } }
+                    ] },
+                    { "type": "body", "parameters": [
+                        { "type": "text", "text": "Ana" },
+                        { "type": "date_time", "dateTime": { "fallbackValue": "March 5, 2024" } },
+                        { "type": "currency", "currency": { "fallbackValue": "$12.35", "code": "USD", "amount1000": 12345 } }
+                    ] },
+                    { "type": "button", "subType": "quick_reply", "index": 0, "parameters": [{ "type": "payload", "payload": "confirm-42" }] },
+                    { "type": "button", "subType": "copy_code", "index": 1, "parameters": [{ "type": "coupon_code", "couponCode": "SAVE10" }] }
+                ]
+            })
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::ids::{ConversationId, MessageId, TenantId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -56,7 +57,7 @@ pub enum StockStatus {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct VRERenderRequest {
-    pub tenant_id: String,
+    pub tenant_id: TenantId,
     pub template_id: String,
     pub data: HashMap<String, serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -72,7 +73,7 @@ pub struct VRERenderRequest {
 }
 
 impl VRERenderRequest {
-    pub fn new(tenant_id: impl Into<String>, template_id: impl Into<String>, data: HashMap<String, serde_json::Value>) -> Self {
+    pub fn new(tenant_id: impl Into<TenantId>, template_id: impl Into<String>, data: HashMap<String, serde_json::Value>) -> Self {
         Self {
             tenant_id: tenant_id.into(),
             template_id: template_id.into(),
@@ -131,7 +132,7 @@ pub struct VRERenderResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct VRERenderAndSendRequest {
-    pub conversation_id: String,
+    pub conversation_id: ConversationId,
     pub template_id: String,
     pub data: HashMap<String, serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -141,7 +142,7 @@ pub struct VRERenderAndSendRequest {
 }
 
 impl VRERenderAndSendRequest {
-    pub fn new(conversation_id: impl Into<String>, template_id: impl Into<String>, data: HashMap<String, serde_json::Value>) -> Self {
+    pub fn new(conversation_id: impl Into<ConversationId>, template_id: impl Into<String>, data: HashMap<String, serde_json::Value>) -> Self {
         Self {
             conversation_id: conversation_id.into(),
             template_id: template_id.into(),
@@ -166,7 +167,7 @@ impl VRERenderAndSendRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct VRERenderAndSendResponse {
-    pub message_id: String,
+    pub message_id: MessageId,
     pub image_url: String,
     pub caption: String,
 }
@@ -390,3 +391,133 @@ impl CobrancaPixData {
         }
     }
 }
+
+/// How the rendered transcript is returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptDelivery {
+    /// PDF bytes in the response.
+    Inline,
+    /// A signed, expiring download URL; better for long conversations.
+    Url,
+}
+
+/// Branding applied to transcript headers and footers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TranscriptBranding {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+    /// Hex color such as `#1A73E8`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer_text: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TranscriptPdfOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery: Option<TranscriptDelivery>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branding: Option<TranscriptBranding>,
+    /// Show image and video thumbnails next to media messages (default true).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_media_thumbnails: Option<bool>,
+    /// Include internal notes and system events, not just customer-visible messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_internal_notes: Option<bool>,
+    /// IANA timezone for timestamps, e.g. `America/Sao_Paulo` (default UTC).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TranscriptPdfOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn delivery(mut self, delivery: TranscriptDelivery) -> Self {
+        self.delivery = Some(delivery);
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn branding(mut self, branding: TranscriptBranding) -> Self {
+        self.branding = Some(branding);
+        self
+    }
+
+    pub fn include_media_thumbnails(mut self, include: bool) -> Self {
+        self.include_media_thumbnails = Some(include);
+        self
+    }
+
+    pub fn include_internal_notes(mut self, include: bool) -> Self {
+        self.include_internal_notes = Some(include);
+        self
+    }
+
+    pub fn timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Only messages sent between `from` and `to`.
+    pub fn range(mut self, from: chrono::DateTime<chrono::Utc>, to: chrono::DateTime<chrono::Utc>) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct TranscriptPdfRequest {
+    pub conversation_id: ConversationId,
+    #[serde(flatten)]
+    pub options: TranscriptPdfOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct TranscriptPdfResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdf_base64: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A rendered transcript, inline or as a download link depending on
+/// [`TranscriptPdfOptions::delivery`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranscriptPdf {
+    Bytes(Vec<u8>),
+    Url {
+        url: String,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    },
+}
+
@@ -1,3 +1,5 @@
+use super::ids::TenantId;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -5,6 +7,48 @@ pub const SIGNATURE_HEADER: &str = "X-Linktor-Signature";
 pub const TIMESTAMP_HEADER: &str = "X-Linktor-Timestamp";
 pub const DEFAULT_TOLERANCE_SECONDS: i64 = 300;
 
+/// How far a webhook's timestamp may drift from the local clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tolerance {
+    /// Require the timestamp header and accept the default window.
+    Strict,
+    /// Accept timestamps within this many seconds; `Seconds(0)` allows no drift.
+    Seconds(i64),
+    /// Skip timestamp validation entirely.
+    Disabled,
+}
+
+#[derive(Debug, Clone)]
+pub struct ToleranceOptions {
+    pub tolerance: Tolerance,
+}
+
+impl Default for ToleranceOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: Tolerance::Seconds(DEFAULT_TOLERANCE_SECONDS),
+        }
+    }
+}
+
+impl ToleranceOptions {
+    pub fn new(tolerance: Tolerance) -> Self {
+        Self { tolerance }
+    }
+
+    pub fn strict() -> Self {
+        Self::new(Tolerance::Strict)
+    }
+
+    pub fn seconds(seconds: i64) -> Self {
+        Self::new(Tolerance::Seconds(seconds))
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(Tolerance::Disabled)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EventType {
     #[serde(rename = "message.received")]
@@ -40,6 +84,11 @@ pub enum EventType {
     ChannelDisconnected,
     #[serde(rename = "channel.error")]
     ChannelError,
+    /// The provider rejected the channel's OAuth token; see `channels().reauthorize()`.
+    #[serde(rename = "channel.auth_expired")]
+    ChannelAuthExpired,
+    #[serde(rename = "channel.reauthorized")]
+    ChannelReauthorized,
 
     #[serde(rename = "bot.started")]
     BotStarted,
@@ -61,7 +110,7 @@ pub struct WebhookEvent {
     #[serde(rename = "type")]
     pub event_type: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
-    pub tenant_id: String,
+    pub tenant_id: TenantId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<HashMap<String, serde_json::Value>>,
 }
@@ -84,6 +133,8 @@ impl WebhookEvent {
             "channel.connected" => Some(EventType::ChannelConnected),
             "channel.disconnected" => Some(EventType::ChannelDisconnected),
             "channel.error" => Some(EventType::ChannelError),
+            "channel.auth_expired" => Some(EventType::ChannelAuthExpired),
+            "channel.reauthorized" => Some(EventType::ChannelReauthorized),
             "bot.started" => Some(EventType::BotStarted),
             "bot.stopped" => Some(EventType::BotStopped),
             "flow.started" => Some(EventType::FlowStarted),
@@ -106,3 +157,112 @@ pub struct WebhookConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub headers: Option<HashMap<String, String>>,
 }
+
+impl WebhookConfig {
+    /// An enabled webhook receiving every event, signed with `secret`.
+    pub fn new(url: impl Into<String>, secret: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret: secret.into(),
+            events: Vec::new(),
+            enabled: true,
+            headers: None,
+        }
+    }
+
+    /// Only deliver these event types, e.g. `"message.received"`.
+    pub fn events(mut self, events: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.events = events.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Extra header sent with every delivery, e.g. for a gateway API key.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.get_or_insert_with(HashMap::new).insert(name.into(), value.into());
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    /// Not attempted yet, or waiting for its next retry.
+    Pending,
+    /// Retries exhausted.
+    Failed,
+}
+
+/// An event that never reached the webhook endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissedEvent {
+    pub event: WebhookEvent,
+    pub status: DeliveryStatus,
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// Position just after this event. Persist it once the event is handled and
+    /// pass it to [`MissedEventsParams::cursor`] to resume from there.
+    pub cursor: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissedEventsPage {
+    pub data: Vec<MissedEvent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+/// Where to start reading missed events: a time, or a cursor saved from an
+/// earlier run, which takes precedence.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissedEventsParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<DeliveryStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+}
+
+impl MissedEventsParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    pub fn status(mut self, status: DeliveryStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Events per page.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
@@ -1,5 +1,7 @@
 use crate::error::{LinktorError, Result};
-use crate::types::webhook::{WebhookEvent, SIGNATURE_HEADER, TIMESTAMP_HEADER, DEFAULT_TOLERANCE_SECONDS};
+use crate::types::webhook::{
+    Tolerance, ToleranceOptions, WebhookEvent, DEFAULT_TOLERANCE_SECONDS, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
 use chrono::Utc;
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
@@ -71,6 +73,9 @@ pub fn verify(payload: &[u8], headers: &HashMap<String, String>, secret: &str, t
 }
 
 /// Construct and verify a webhook event
+///
+/// `Some(0)` is treated as the default tolerance for compatibility; use
+/// [`construct_event_with`] for explicit tolerance handling.
 pub fn construct_event(
     payload: &[u8],
     headers: &HashMap<String, String>,
@@ -84,11 +89,68 @@ pub fn construct_event(
     };
 
     if !verify(payload, headers, secret, Some(tolerance)) {
-        return Err(LinktorError::WebhookVerification {
-            message: "Webhook signature verification failed".to_string(),
-        });
+        return Err(verification_error("Webhook signature verification failed"));
     }
 
+    parse_event(payload)
+}
+
+/// Construct and verify a webhook event with explicit timestamp tolerance.
+pub fn construct_event_with(
+    payload: &[u8],
+    headers: &HashMap<String, String>,
+    secret: &str,
+    options: ToleranceOptions,
+) -> Result<WebhookEvent> {
+    let signature = header(headers, SIGNATURE_HEADER).unwrap_or("");
+    if signature.is_empty() {
+        return Err(verification_error("Missing webhook signature"));
+    }
+
+    let window = match options.tolerance {
+        Tolerance::Strict => Some(DEFAULT_TOLERANCE_SECONDS),
+        Tolerance::Seconds(seconds) => Some(seconds),
+        Tolerance::Disabled => None,
+    };
+
+    if let Some(window) = window {
+        match header(headers, TIMESTAMP_HEADER) {
+            Some(ts_str) => {
+                let timestamp = ts_str
+                    .parse::<i64>()
+                    .map_err(|_| verification_error("Invalid webhook timestamp"))?;
+                if (Utc::now().timestamp() - timestamp).abs() > window {
+                    return Err(verification_error("Webhook timestamp outside tolerance"));
+                }
+            }
+            None if options.tolerance == Tolerance::Strict => {
+                return Err(verification_error("Missing webhook timestamp"));
+            }
+            None => {}
+        }
+    }
+
+    if !verify_signature(payload, signature, secret) {
+        return Err(verification_error("Webhook signature verification failed"));
+    }
+
+    parse_event(payload)
+}
+
+fn header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .or_else(|| headers.get(&name.to_lowercase()))
+        .map(String::as_str)
+}
+
+fn verification_error(message: &str) -> LinktorError {
+    LinktorError::WebhookVerification {
+        message: message.to_string(),
+    }
+}
+
+fn parse_event(payload: &[u8]) -> Result<WebhookEvent> {
     let event: WebhookEvent = serde_json::from_slice(payload).map_err(|e| {
         LinktorError::WebhookVerification {
             message: format!("Failed to parse webhook event: {}", e),
@@ -96,9 +158,7 @@ pub fn construct_event(
     })?;
 
     if event.id.is_empty() || event.event_type.is_empty() {
-        return Err(LinktorError::WebhookVerification {
-            message: "Invalid webhook event structure".to_string(),
-        });
+        return Err(verification_error("Invalid webhook event structure"));
     }
 
     Ok(event)
@@ -125,4 +185,31 @@ mod tests {
         assert!(verify_signature(payload, &signature, secret));
         assert!(!verify_signature(payload, "wrong-signature", secret));
     }
+
+    fn signed_headers(payload: &[u8], secret: &str, timestamp: Option<i64>) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert(SIGNATURE_HEADER.to_string(), compute_signature(payload, secret));
+        if let Some(ts) = timestamp {
+            headers.insert(TIMESTAMP_HEADER.to_string(), ts.to_string());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_construct_event_with_tolerance() {
+        let payload = br#"{"id":"evt-1","type":"message.received","timestamp":"2024-01-01T00:00:00Z","tenantId":"t-1","data":{}}"#;
+        let secret = "test-secret";
+        let now = Utc::now().timestamp();
+
+        let fresh = signed_headers(payload, secret, Some(now));
+        assert!(construct_event_with(payload, &fresh, secret, ToleranceOptions::strict()).is_ok());
+
+        let untimed = signed_headers(payload, secret, None);
+        assert!(construct_event_with(payload, &untimed, secret, ToleranceOptions::strict()).is_err());
+        assert!(construct_event_with(payload, &untimed, secret, ToleranceOptions::default()).is_ok());
+
+        let stale = signed_headers(payload, secret, Some(now - 10));
+        assert!(construct_event_with(payload, &stale, secret, ToleranceOptions::seconds(0)).is_err());
+        assert!(construct_event_with(payload, &stale, secret, ToleranceOptions::disabled()).is_ok());
+    }
 }
@@ -0,0 +1,29 @@
+use linktor::testing::MockLinktor;
+
+#[tokio::test]
+async fn test_score_conversation() {
+    let mock = MockLinktor::start().await;
+    let score = mock
+        .client()
+        .ai()
+        .score_conversation("conv-1", linktor::types::ScoringRubric::standard().scale(5))
+        .await
+        .unwrap();
+
+    assert_eq!(score.criterion("resolution").unwrap().score, 5.0);
+    assert_eq!(score.criterion("empathy").unwrap().evidence.len(), 1);
+    let body = mock.requests_to("POST", "/ai/conversations/conv-1/score")[0].json().unwrap();
+    assert_eq!(body["criteria"].as_array().unwrap().len(), 3);
+    assert_eq!(body["scale"], 5);
+}
+
+#[tokio::test]
+async fn test_summarize_conversation() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let summary = client.conversations().summarize("conv-1").await.unwrap();
+    assert_eq!(summary.sentiment, linktor::types::Sentiment::Positive);
+    assert_eq!(summary.suggested_tags, vec!["refund", "shipping"]);
+    assert_eq!(mock.requests_to("POST", "/conversations/conv-1/summarize").len(), 1);
+}
@@ -0,0 +1,291 @@
+use linktor::{DebugLogger, LinktorClient};
+use linktor::testing::{fixtures, MockLinktor};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+async fn test_token_refresh_before_expiry() {
+    let mock = MockLinktor::start().await;
+    mock.on(
+        "POST",
+        "/auth/login",
+        200,
+        linktor::types::LoginResponse {
+            expires_in: 2,
+            ..fixtures::login_response()
+        },
+    );
+    let client = mock.client();
+    client.auth().login("agent@example.com", "secret").await.unwrap();
+
+    let refresher = client.auth().start_token_refresh();
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    assert_eq!(mock.requests_to("POST", "/auth/refresh").len(), 1);
+
+    refresher.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_token_store_restores_session() {
+    use linktor::token_store::{MemoryTokenStore, TokenStore};
+
+    let mock = MockLinktor::start().await;
+    let store = Arc::new(MemoryTokenStore::new());
+    let build = || {
+        LinktorClient::builder()
+            .base_url(mock.url())
+            .token_store(store.clone())
+            .build()
+            .unwrap()
+    };
+
+    build().auth().login("user@example.com", "password").await.unwrap();
+    let login = fixtures::login_response();
+    let saved = store.load().unwrap().unwrap();
+    assert_eq!(saved.access_token, login.access_token);
+    assert_eq!(saved.refresh_token, login.refresh_token);
+
+    // A new client picks up the saved session without logging in.
+    let restored = build();
+    restored.auth().get_current_user().await.unwrap();
+    let sent = mock.requests_to("GET", "/auth/me");
+    assert_eq!(
+        sent[0].header("authorization"),
+        Some(format!("Bearer {}", login.access_token).as_str())
+    );
+
+    restored.auth().logout().await.unwrap();
+    assert_eq!(store.load().unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_oauth_code_exchange() {
+    use linktor::oauth::OAuthParams;
+
+    let mock = MockLinktor::start().await;
+    let client = LinktorClient::builder().base_url(mock.url()).build().unwrap();
+
+    let pending = client
+        .auth()
+        .authorize_url(OAuthParams::new("app-1", "http://127.0.0.1:8765/callback").scope("conversations:read"))
+        .unwrap();
+    let url = url::Url::parse(&pending.url).unwrap();
+    assert_eq!(url.path(), "/auth/oauth/authorize");
+    let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    assert_eq!(query["client_id"], "app-1");
+    assert_eq!(query["code_challenge_method"], "S256");
+    assert_eq!(query["code_challenge"], linktor::oauth::code_challenge(&pending.code_verifier));
+    assert_eq!(query["state"], pending.state);
+
+    let redirect = format!("http://127.0.0.1:8765/callback?code=auth-code&state={}", pending.state);
+    let code = pending.code_from_redirect(&redirect).unwrap();
+    client.auth().exchange_code(&pending, &code).await.unwrap();
+    client.auth().get_current_user().await.unwrap();
+
+    let body = mock.requests_to("POST", "/auth/oauth/token")[0].json().unwrap();
+    assert_eq!(body["grantType"], "authorization_code");
+    assert_eq!(body["code"], "auth-code");
+    assert_eq!(body["codeVerifier"], pending.code_verifier.as_str());
+    let me = &mock.requests_to("GET", "/auth/me")[0];
+    assert_eq!(me.header("authorization"), Some("Bearer oauth-access-token"));
+}
+
+#[tokio::test]
+async fn test_oauth_code_exchange_is_redacted_in_debug_log() {
+    use linktor::oauth::OAuthParams;
+
+    let mock = MockLinktor::start().await;
+    let (client, lines) = logged_client(&mock);
+
+    let pending = client
+        .auth()
+        .authorize_url(OAuthParams::new("app-1", "http://127.0.0.1:8765/callback"))
+        .unwrap();
+    client.auth().exchange_code(&pending, "auth-code-1").await.unwrap();
+
+    let log = lines.lock().unwrap().join("\n");
+    assert!(log.contains("/auth/oauth/token"));
+    for secret in ["auth-code-1", pending.code_verifier.as_str(), "oauth-access-token"] {
+        assert!(!log.contains(secret), "{} leaked into the debug log", secret);
+    }
+}
+
+#[tokio::test]
+async fn test_user_administration() {
+    use linktor::types::{InviteUserInput, ListUsersParams, UserRole, UserStatus};
+
+    let mock = MockLinktor::start().await;
+    let users = mock.client().users();
+
+    users
+        .list(Some(ListUsersParams::new().role(UserRole::Agent).status(UserStatus::Active)))
+        .await
+        .unwrap();
+    users
+        .invite(InviteUserInput::new("new@example.com", UserRole::Agent).name("New Agent"))
+        .await
+        .unwrap();
+    users.set_role("user-1", UserRole::Manager).await.unwrap();
+    users.deactivate("user-1").await.unwrap();
+
+    let listed = mock.requests_to("GET", "/users");
+    assert_eq!(listed[0].query.as_deref(), Some("role=agent&status=active"));
+    let invite = mock.requests_to("POST", "/users/invite")[0].json().unwrap();
+    assert_eq!(invite["email"], "new@example.com");
+    assert_eq!(invite["role"], "agent");
+    let update = mock.requests_to("PATCH", "/users/user-1")[0].json().unwrap();
+    assert_eq!(update, serde_json::json!({ "role": "manager" }));
+    assert_eq!(mock.requests_to("POST", "/users/user-1/deactivate").len(), 1);
+}
+
+#[tokio::test]
+async fn test_password_flows() {
+    let mock = MockLinktor::start().await;
+    let auth = mock.client().auth();
+
+    auth.request_password_reset("user@example.com").await.unwrap();
+    auth.confirm_password_reset("reset-token", "n3w-pass").await.unwrap();
+    let reset = mock.requests_to("POST", "/auth/password/reset")[0].json().unwrap();
+    assert_eq!(reset, serde_json::json!({ "token": "reset-token", "newPassword": "n3w-pass" }));
+
+    mock.on(
+        "POST",
+        "/auth/password/change",
+        401,
+        serde_json::json!({ "code": "INVALID_CREDENTIALS", "message": "Current password is incorrect" }),
+    );
+    let err = auth.change_password("wrong", "n3w-pass").await.unwrap_err();
+    assert!(matches!(err, linktor::LinktorError::Authentication { .. }));
+    let change = mock.requests_to("POST", "/auth/password/change")[0].json().unwrap();
+    assert_eq!(change["currentPassword"], "wrong");
+}
+
+/// Client whose debug log lines are collected into the returned buffer.
+fn logged_client(mock: &MockLinktor) -> (LinktorClient, Arc<Mutex<Vec<String>>>) {
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let sink = lines.clone();
+    let client = LinktorClient::builder()
+        .base_url(mock.url())
+        .api_key("test-api-key")
+        .logger(DebugLogger::new(move |line| sink.lock().unwrap().push(line.to_string())))
+        .build()
+        .unwrap();
+    (client, lines)
+}
+
+#[tokio::test]
+async fn test_password_flows_are_redacted_in_debug_log() {
+    let mock = MockLinktor::start().await;
+    let (client, lines) = logged_client(&mock);
+
+    client.auth().confirm_password_reset("reset-token-1", "n3w-pass").await.unwrap();
+    client.auth().change_password("0ld-pass", "n3w-pass").await.unwrap();
+
+    let log = lines.lock().unwrap().join("\n");
+    assert!(log.contains("/auth/password/change"));
+    for secret in ["reset-token-1", "n3w-pass", "0ld-pass"] {
+        assert!(!log.contains(secret), "{} leaked into the debug log", secret);
+    }
+}
+
+#[tokio::test]
+async fn test_mfa_flows_are_redacted_in_debug_log() {
+    let mock = MockLinktor::start().await;
+    mock.on(
+        "POST",
+        "/auth/login",
+        401,
+        serde_json::json!({
+            "code": "MFA_REQUIRED",
+            "message": "Second factor required",
+            "details": { "challengeToken": "chal-secret-1", "methods": ["totp"] }
+        }),
+    );
+    let (client, lines) = logged_client(&mock);
+
+    let challenge = match client.auth().login("user@example.com", "hunter2").await.unwrap() {
+        linktor::types::LoginOutcome::MfaRequired(challenge) => challenge,
+        linktor::types::LoginOutcome::Authenticated(_) => panic!("expected an MFA challenge"),
+    };
+    client.auth().complete_login(&challenge, "654321").await.unwrap();
+    client.auth().enroll_mfa().await.unwrap();
+    client.auth().verify_mfa("112233").await.unwrap();
+
+    let log = lines.lock().unwrap().join("\n");
+    assert!(log.contains("/auth/mfa/verify"));
+    let enrollment = fixtures::mfa_enrollment();
+    let mut secrets = vec!["hunter2", "chal-secret-1", "654321", "112233", enrollment.secret.as_str()];
+    let recovery = fixtures::mfa_verification().recovery_codes;
+    secrets.extend(recovery.iter().map(String::as_str));
+    for secret in secrets {
+        assert!(!log.contains(secret), "{} leaked into the debug log", secret);
+    }
+}
+
+#[tokio::test]
+async fn test_login_with_mfa_challenge() {
+    use linktor::types::{LoginOutcome, MfaMethod};
+
+    let mock = MockLinktor::start().await;
+    mock.on(
+        "POST",
+        "/auth/login",
+        401,
+        serde_json::json!({
+            "code": "MFA_REQUIRED",
+            "message": "Second factor required",
+            "details": { "challengeToken": "chal-1", "methods": ["totp", "recovery_code"] }
+        }),
+    );
+    let client = LinktorClient::builder().base_url(mock.url()).build().unwrap();
+
+    let challenge = match client.auth().login("user@example.com", "password").await.unwrap() {
+        LoginOutcome::MfaRequired(challenge) => challenge,
+        LoginOutcome::Authenticated(_) => panic!("expected an MFA challenge"),
+    };
+    assert_eq!(challenge.methods, vec![MfaMethod::Totp, MfaMethod::RecoveryCode]);
+
+    client.auth().complete_login(&challenge, "123456").await.unwrap();
+    client.auth().get_current_user().await.unwrap();
+
+    let body = mock.requests_to("POST", "/auth/mfa/challenge")[0].json().unwrap();
+    assert_eq!(body, serde_json::json!({ "challengeToken": "chal-1", "code": "123456" }));
+    let me = &mock.requests_to("GET", "/auth/me")[0];
+    assert_eq!(
+        me.header("authorization"),
+        Some(format!("Bearer {}", fixtures::login_response().access_token).as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_client_credentials_obtains_and_renews_token() {
+    let mock = MockLinktor::start().await;
+    let client = LinktorClient::builder()
+        .base_url(mock.url())
+        .client_credentials("worker", "s3cret")
+        .scope("conversations:write")
+        .build()
+        .unwrap();
+
+    client.auth().get_current_user().await.unwrap();
+    client.auth().get_current_user().await.unwrap();
+
+    let grants = mock.requests_to("POST", "/auth/token");
+    assert_eq!(grants.len(), 1);
+    assert_eq!(
+        grants[0].json().unwrap(),
+        serde_json::json!({
+            "grantType": "client_credentials",
+            "clientId": "worker",
+            "clientSecret": "s3cret",
+            "scope": "conversations:write"
+        })
+    );
+    let me = mock.requests_to("GET", "/auth/me");
+    assert_eq!(me[1].header("authorization"), Some("Bearer service-access-token"));
+
+    mock.on("GET", "/auth/me", 401, serde_json::json!({ "message": "token revoked" }));
+    assert!(client.auth().get_current_user().await.is_err());
+    assert_eq!(mock.requests_to("POST", "/auth/token").len(), 2);
+    assert_eq!(mock.requests_to("GET", "/auth/me").len(), 4);
+}
@@ -0,0 +1,150 @@
+use linktor::testing::MockLinktor;
+
+#[tokio::test]
+async fn test_bot_activation() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    client.bots().activate("bot-1").await.unwrap();
+    client.bots().deactivate("bot-1").await.unwrap();
+    client.bots().pause("bot-1", std::time::Duration::from_secs(900)).await.unwrap();
+
+    assert_eq!(mock.requests_to("POST", "/bots/bot-1/activate").len(), 1);
+    assert_eq!(mock.requests_to("POST", "/bots/bot-1/deactivate").len(), 1);
+    let paused = mock.requests_to("POST", "/bots/bot-1/pause");
+    assert_eq!(paused[0].json(), Some(serde_json::json!({ "durationSeconds": 900 })));
+}
+
+#[tokio::test]
+async fn test_bot_simulate() {
+    use linktor::types::SimulatedMessage;
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let simulation = client
+        .bots()
+        .simulate("bot-1", vec![SimulatedMessage::new("Where is my order 1234?").metadata("plan", "pro")])
+        .await
+        .unwrap();
+    assert_eq!(simulation.replies(), ["Order 1234 ships tomorrow."]);
+    assert_eq!(simulation.visited_nodes(), ["node-1"]);
+    assert_eq!(simulation.tool_calls(), ["lookup_order"]);
+    assert!(!simulation.escalated());
+
+    let requests = mock.requests_to("POST", "/bots/bot-1/simulate");
+    assert_eq!(
+        requests[0].json(),
+        Some(serde_json::json!({
+            "messages": [{ "text": "Where is my order 1234?", "metadata": { "plan": "pro" } }]
+        }))
+    );
+}
+
+#[tokio::test]
+async fn test_bot_duplicate() {
+    use linktor::types::BotStatus;
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let copy = client.bots().duplicate("bot-1", "Test Bot (copy)").await.unwrap();
+    assert_eq!(copy.id.as_str(), "bot-2");
+    assert_eq!(copy.status, BotStatus::Draft);
+    assert!(copy.channel_ids.is_empty());
+    assert_eq!(copy.agent_id.as_ref().map(|a| a.as_str()), Some("agent-1"));
+
+    let requests = mock.requests_to("POST", "/bots/bot-1/duplicate");
+    assert_eq!(requests[0].json(), Some(serde_json::json!({ "name": "Test Bot (copy)" })));
+}
+
+#[tokio::test]
+async fn test_bot_attach_detach_channel() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    client.bots().attach_channel("bot-1", "channel-2").await.unwrap();
+    client.bots().detach_channel("bot-1", "channel-1").await.unwrap();
+
+    let attached = mock.requests_to("POST", "/bots/bot-1/channels");
+    assert_eq!(attached[0].json(), Some(serde_json::json!({ "channelId": "channel-2" })));
+    assert_eq!(mock.requests_to("DELETE", "/bots/bot-1/channels/channel-1").len(), 1);
+    assert!(mock.requests_to("PATCH", "/bots/bot-1").is_empty());
+}
+
+#[tokio::test]
+async fn test_bot_intents() {
+    use linktor::types::{EntityKind, Intent, IntentEntity};
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+    let intents = client.bots().intents("bot-1");
+
+    let listed = intents.list().await.unwrap();
+    assert_eq!(listed[0].name, "order_status");
+    assert_eq!(listed[0].entities[0].kind, EntityKind::Number);
+
+    let greeting = Intent::new("greeting").phrase("hi").phrase("hello");
+    intents.create(greeting.clone()).await.unwrap();
+    intents.update("greeting", greeting.clone()).await.unwrap();
+    intents.delete("greeting").await.unwrap();
+    let cancel = Intent::new("cancel_order")
+        .entity(IntentEntity::new("reason", EntityKind::List).values(["late", "wrong item"]));
+    intents.replace(vec![greeting, cancel]).await.unwrap();
+
+    let created = mock.requests_to("POST", "/bots/bot-1/intents");
+    assert_eq!(
+        created[0].json(),
+        Some(serde_json::json!({ "name": "greeting", "trainingPhrases": ["hi", "hello"], "entities": [] }))
+    );
+    assert_eq!(mock.requests_to("PUT", "/bots/bot-1/intents/greeting").len(), 1);
+    assert_eq!(mock.requests_to("DELETE", "/bots/bot-1/intents/greeting").len(), 1);
+    // Names are a single path segment, whatever they contain.
+    intents.delete("../../users/u-1").await.unwrap();
+    assert_eq!(mock.requests_to("DELETE", "/bots/bot-1/intents/..%2F..%2Fusers%2Fu-1").len(), 1);
+    let replaced = mock.requests_to("PUT", "/bots/bot-1/intents")[0].json().unwrap();
+    assert_eq!(replaced["intents"][1]["entities"][0]["values"], serde_json::json!(["late", "wrong item"]));
+
+    let detection = client.bots().detect_intent("bot-1", "Has order 1234 shipped?").await.unwrap();
+    assert_eq!(detection.intent_name(), Some("order_status"));
+    assert_eq!(detection.entities["order_number"], "1234");
+    let detect = mock.requests_to("POST", "/bots/bot-1/detect-intent");
+    assert_eq!(detect[0].json(), Some(serde_json::json!({ "text": "Has order 1234 shipped?" })));
+}
+
+#[tokio::test]
+async fn test_bot_schedule() {
+    use linktor::types::{BotSchedule, BusinessHours, DaySchedule, OffHoursFallback};
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let schedule = client.bots().get_schedule("bot-1").await.unwrap();
+    assert_eq!(schedule.timezone.as_deref(), Some("America/Sao_Paulo"));
+    assert_eq!(
+        schedule.off_hours_fallback,
+        OffHoursFallback::Handoff {
+            agent_ids: vec!["user-1".into()]
+        }
+    );
+
+    let hours = BusinessHours {
+        enabled: true,
+        timezone: Some("UTC".to_string()),
+        schedule: Some(std::collections::HashMap::from([(
+            "friday".to_string(),
+            DaySchedule {
+                enabled: true,
+                start: Some("08:00".to_string()),
+                end: Some("12:00".to_string()),
+            },
+        )])),
+    };
+    let schedule = BotSchedule::from_business_hours(&hours).off_hours_reply("Back on Monday");
+    client.bots().set_schedule("bot-1", schedule).await.unwrap();
+
+    let sent = mock.requests_to("PUT", "/bots/bot-1/schedule")[0].json().unwrap();
+    assert_eq!(sent["schedule"]["friday"], serde_json::json!({ "enabled": true, "start": "08:00", "end": "12:00" }));
+    assert_eq!(sent["offHoursMessage"], "Back on Monday");
+    assert_eq!(sent["offHoursFallback"], serde_json::json!({ "type": "wait" }));
+}
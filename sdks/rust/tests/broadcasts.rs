@@ -0,0 +1,29 @@
+use linktor::testing::MockLinktor;
+
+#[tokio::test]
+async fn test_broadcasts() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let input = linktor::types::CreateBroadcastInput::new(
+        "Black Friday",
+        "channel-1",
+        linktor::types::SendMessageInput::text("20% off everything today"),
+        linktor::types::BroadcastAudience::segment(linktor::Filter::tag("vip")),
+    )
+    .throttle_per_minute(600);
+    let broadcast = client.broadcasts().create(input).await.unwrap();
+    assert_eq!(broadcast.stats.total, 3);
+    let sent = mock.requests_to("POST", "/broadcasts");
+    let body = sent[0].json().unwrap();
+    assert_eq!(body["audience"], serde_json::json!({ "type": "segment", "filter": "tags HAS \"vip\"" }));
+    assert_eq!(body["throttlePerMinute"], 600);
+
+    let params = linktor::types::ListBroadcastRecipientsParams::new().status(linktor::types::MessageStatus::Failed);
+    client.broadcasts().recipients(&broadcast.id, Some(params)).await.unwrap();
+    let listed = mock.requests_to("GET", "/broadcasts/broadcast-1/recipients");
+    assert_eq!(listed[0].query.as_deref(), Some("status=failed"));
+
+    let cancelled = client.broadcasts().cancel(&broadcast.id).await.unwrap();
+    assert_eq!(cancelled.status, linktor::types::BroadcastStatus::Cancelled);
+}
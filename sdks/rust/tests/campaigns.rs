@@ -0,0 +1,14 @@
+use linktor::testing::MockLinktor;
+
+#[tokio::test]
+async fn test_preview_for_contact() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let preview = client.campaigns().preview_for_contact("campaign-1", "contact-1").await.unwrap();
+    assert_eq!(preview.rendered_text.as_deref(), Some("Hi Ana, your Gold discount ends Friday."));
+    assert_eq!(preview.variables["first_name"], "Ana");
+    assert!(!preview.is_complete());
+
+    assert_eq!(mock.requests_to("GET", "/campaigns/campaign-1/contacts/contact-1/preview").len(), 1);
+}
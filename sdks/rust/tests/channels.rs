@@ -0,0 +1,263 @@
+use linktor::testing::{fixtures, MockLinktor};
+
+#[tokio::test]
+async fn test_compliance_notice() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    client
+        .channels()
+        .set_compliance_message("ch-1", fixtures::compliance_message_config())
+        .await
+        .unwrap();
+    let sent = mock.requests_to("PUT", "/channels/ch-1/compliance-message");
+    let body = sent[0].json().unwrap();
+    assert_eq!(body["regulation"], "lgpd");
+    assert_eq!(body["enabled"], true);
+    assert_eq!(body["translations"]["pt-BR"], "Tratamos seus dados conforme nossa política de privacidade.");
+
+    mock.on(
+        "POST",
+        "/conversations/conv-2/compliance-notice",
+        200,
+        linktor::types::ComplianceNotice {
+            sent: false,
+            message: None,
+            ..fixtures::compliance_notice()
+        },
+    );
+    let first = client.conversations().ensure_compliance_notice("conv-1").await.unwrap();
+    assert!(first.sent);
+    assert_eq!(first.message.unwrap().id, "msg-1");
+    let repeat = client.conversations().ensure_compliance_notice("conv-2").await.unwrap();
+    assert!(!repeat.sent);
+    assert!(repeat.notified_at.is_some());
+}
+
+#[tokio::test]
+async fn test_wait_until_connected() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let health = client.channels().wait_until_connected("ch-1", std::time::Duration::from_secs(5)).await.unwrap();
+    assert_eq!(health.status, linktor::types::ChannelStatus::Connected);
+    assert_eq!(mock.requests_to("GET", "/channels/ch-1/status").len(), 1);
+
+    mock.on(
+        "GET",
+        "/channels/*/status",
+        200,
+        linktor::types::ChannelStatusResponse {
+            status: linktor::types::ChannelStatus::Connecting,
+            ..fixtures::channel_status()
+        },
+    );
+    let err = client
+        .channels()
+        .wait_until_connected("ch-1", std::time::Duration::from_millis(700))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, linktor::LinktorError::DeadlineExceeded { .. }));
+}
+
+#[tokio::test]
+async fn test_channel_webhook() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let config = linktor::types::WebhookConfig::new("https://example.com/hooks/linktor", "whsec_test")
+        .events(["message.received", "conversation.created"])
+        .header("X-Api-Key", "k-1");
+    client.channels().set_webhook("ch-1", config).await.unwrap();
+    let sent = mock.requests_to("PUT", "/channels/ch-1/webhook");
+    assert_eq!(
+        sent[0].json(),
+        Some(serde_json::json!({
+            "url": "https://example.com/hooks/linktor",
+            "secret": "whsec_test",
+            "events": ["message.received", "conversation.created"],
+            "enabled": true,
+            "headers": { "X-Api-Key": "k-1" }
+        }))
+    );
+
+    let current = client.channels().get_webhook("ch-1").await.unwrap();
+    assert_eq!(current.events, vec!["message.received"]);
+}
+
+#[tokio::test]
+async fn test_channel_limits() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let limits = client.channels().limits("channel-1").await.unwrap();
+    assert_eq!(limits.quality_rating, Some(linktor::types::QualityRating::Yellow));
+    assert!(limits.can_send(60));
+    assert!(!limits.can_send(61));
+    assert_eq!(mock.requests_to("GET", "/channels/channel-1/limits").len(), 1);
+}
+
+#[tokio::test]
+async fn test_phone_verification() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let request = client
+        .channels()
+        .request_verification("channel-1", linktor::types::VerificationMethod::Voice)
+        .await
+        .unwrap();
+    assert!(request.phone_number.is_some());
+    let sent = mock.requests_to("POST", "/channels/channel-1/verification");
+    assert_eq!(sent[0].json(), Some(serde_json::json!({ "method": "voice" })));
+
+    client.channels().submit_verification_code("channel-1", "123456").await.unwrap();
+    let sent = mock.requests_to("POST", "/channels/channel-1/verification/verify");
+    assert_eq!(sent[0].json(), Some(serde_json::json!({ "code": "123456" })));
+}
+
+#[tokio::test]
+async fn test_reauthorize_channel() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let session = client.channels().reauthorize("channel-1").await.unwrap();
+    assert_eq!(session.status, linktor::types::ReauthorizationStatus::Pending);
+    assert!(session.auth_url.starts_with("https://"));
+
+    let session = client.channels().get_reauthorization("channel-1", &session.id).await.unwrap();
+    assert_eq!(session.status, linktor::types::ReauthorizationStatus::Completed);
+    assert_eq!(mock.requests_to("GET", "/channels/channel-1/reauthorize/reauth-1").len(), 1);
+}
+
+#[tokio::test]
+async fn test_email_channel_setup() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let config = linktor::types::EmailChannelConfig::new(
+        "support@acme.com",
+        linktor::types::SmtpSettings::new("smtp.acme.com", "support", "secret"),
+    )
+    .imap(linktor::types::ImapSettings::new("imap.acme.com", "support", "secret").folder("Support"));
+    let input = linktor::types::CreateChannelInput::email("Support inbox", &config).unwrap();
+    client.channels().create(input).await.unwrap();
+    let sent = mock.requests_to("POST", "/channels");
+    let body = sent[0].json().unwrap();
+    assert_eq!(body["type"], "email");
+    assert_eq!(body["config"]["smtp_encryption"], "starttls");
+    assert_eq!(body["config"]["imap_folder"], "Support");
+
+    let test = client.channels().test_connection("channel-1").await.unwrap();
+    assert!(test.success);
+    assert!(test.check("round_trip").unwrap().success);
+}
+
+#[tokio::test]
+async fn test_channel_analytics() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let range = linktor::types::AnalyticsRange::new(
+        chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        chrono::NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+    );
+    let analytics = client.channels().analytics("channel-1", range).await.unwrap();
+    assert_eq!(analytics.totals.delivery_rate(), Some(0.95));
+    assert_eq!(analytics.days[0].counts.failed, 10);
+    assert_eq!(analytics.days[0].errors["131026"], 10);
+
+    let sent = mock.requests_to("GET", "/channels/channel-1/analytics");
+    assert_eq!(sent[0].query.as_deref(), Some("from=2024-01-01&to=2024-01-31"));
+}
+
+#[tokio::test]
+async fn test_send_test_message() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let result = client.channels().send_test("channel-1", "+5511999999999", "deploy smoke test").await.unwrap();
+    assert_eq!(result.status, linktor::types::MessageStatus::Sent);
+    let sent = mock.requests_to("POST", "/channels/channel-1/test-message");
+    assert_eq!(sent[0].json(), Some(serde_json::json!({ "to": "+5511999999999", "text": "deploy smoke test" })));
+    assert!(mock.requests_to("POST", "/conversations").is_empty());
+}
+
+#[tokio::test]
+async fn test_clone_channel() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let overrides = linktor::types::CloneChannelInput::new()
+        .name("WhatsApp (prod)")
+        .tenant("tenant-2")
+        .config("phoneNumberId", "555");
+    let cloned = client.channels().clone("channel-1", overrides).await.unwrap();
+    assert_eq!(cloned.channel.tenant_id.as_str(), "tenant-2");
+    assert_eq!(cloned.reset_credentials, vec!["accessToken"]);
+
+    let sent = mock.requests_to("POST", "/channels/channel-1/clone");
+    assert_eq!(
+        sent[0].json(),
+        Some(serde_json::json!({
+            "name": "WhatsApp (prod)",
+            "tenantId": "tenant-2",
+            "config": { "phoneNumberId": "555" }
+        }))
+    );
+}
+
+#[tokio::test]
+async fn test_webchat_settings() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let settings = client.channels().webchat_settings("channel-1").await.unwrap();
+    assert_eq!(settings.position, Some(linktor::types::WidgetPosition::BottomRight));
+    assert!(settings.pre_chat_form.unwrap()[0].required);
+
+    let update = linktor::types::WebchatSettings::new()
+        .position(linktor::types::WidgetPosition::BottomLeft)
+        .auto_open(5)
+        .pre_chat_field(
+            linktor::types::PreChatField::new("plan", "Plan", linktor::types::PreChatFieldType::Select).options(["free", "pro"]),
+        );
+    client.channels().update_webchat_settings("channel-1", update).await.unwrap();
+    let sent = mock.requests_to("PUT", "/channels/channel-1/webchat");
+    assert_eq!(
+        sent[0].json(),
+        Some(serde_json::json!({
+            "position": "bottom-left",
+            "autoOpen": true,
+            "autoOpenDelay": 5,
+            "preChatForm": [{ "name": "plan", "label": "Plan", "type": "select", "required": false, "options": ["free", "pro"] }]
+        }))
+    );
+
+    let snippet = client.channels().embed_snippet("channel-1");
+    let widget_url = format!("{}/widget/linktor.js", mock.url()).replace('/', "\\/");
+    assert!(snippet.contains(&format!("'linktor', \"{}\"", widget_url)));
+    assert!(snippet.contains(r#"channelId: "channel-1""#));
+
+    let hostile = client.channels().embed_snippet("x'</script><script>alert(1)//");
+    assert_eq!(hostile.matches("</script>").count(), 1);
+    assert!(hostile.contains(r#"channelId: "x'\u003c\/script>\u003cscript>alert(1)\/\/""#));
+}
+
+#[tokio::test]
+async fn test_embed_snippet_uses_active_region() {
+    let down = MockLinktor::start().await;
+    let up = MockLinktor::start().await;
+    down.on("GET", "/health", 503, serde_json::json!({}));
+    up.on("GET", "/health", 200, serde_json::json!({ "status": "ok" }));
+    let client = linktor::LinktorClient::builder()
+        .base_urls([down.url(), up.url()])
+        .api_key("test-api-key")
+        .build()
+        .unwrap();
+    client.check_regions().await;
+
+    let snippet = client.channels().embed_snippet("channel-1");
+    assert!(snippet.contains(&up.url().replace('/', "\\/")));
+    assert!(!snippet.contains(&down.url().replace('/', "\\/")));
+}
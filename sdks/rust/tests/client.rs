@@ -0,0 +1,454 @@
+use linktor::LinktorClient;
+use linktor::testing::{fixtures, MockLinktor, MockResponse};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_rate_limit_reports_retry_after() {
+    let mock = MockLinktor::start().await;
+    mock.on_response(
+        "GET",
+        "/contacts/*",
+        MockResponse::json(429, serde_json::json!({"code": "RATE_LIMITED", "message": "slow down"}))
+            .header("Retry-After", "7"),
+    );
+
+    let err = mock.client().contacts().get("contact-1").await.unwrap_err();
+    assert_eq!(err.retry_after(), Some(7));
+}
+
+#[tokio::test]
+async fn test_idempotent_requests_fail_over_between_regions() {
+    let eu = MockLinktor::start().await;
+    let us = MockLinktor::start().await;
+    eu.on("GET", "/contacts/*", 503, serde_json::json!({"code": "INTERNAL_ERROR", "message": "down"}));
+
+    let client = LinktorClient::builder()
+        .api_key("test-api-key")
+        .region("eu", eu.url())
+        .region("us", us.url())
+        .max_retries(1)
+        .build()
+        .unwrap();
+
+    client.contacts().get("contact-1").await.unwrap();
+    assert_eq!(client.region().unwrap().name, "us");
+    assert_eq!(eu.requests_to("GET", "/contacts/contact-1").len(), 1);
+
+    // The failed region is skipped until its cooldown expires.
+    client.contacts().get("contact-1").await.unwrap();
+    assert_eq!(eu.requests_to("GET", "/contacts/contact-1").len(), 1);
+    assert_eq!(us.requests_to("GET", "/contacts/contact-1").len(), 2);
+}
+
+#[tokio::test]
+async fn test_unreachable_endpoint_fails_over_for_writes() {
+    let fallback = MockLinktor::start().await;
+    // Nothing listens on port 9 of the loopback interface, so connecting fails immediately.
+    let client = LinktorClient::builder()
+        .api_key("test-api-key")
+        .base_urls(["http://127.0.0.1:9", fallback.url().as_str()])
+        .max_retries(1)
+        .build()
+        .unwrap();
+
+    client.conversations().send_text("conv-1", "Hi").await.unwrap();
+    assert_eq!(fallback.requests_to("POST", "/conversations/conv-1/messages").len(), 1);
+    assert_eq!(client.region().unwrap().base_url, fallback.url());
+}
+
+#[cfg(feature = "trace-context")]
+#[tokio::test]
+async fn test_trace_context_headers() {
+    use linktor::TraceContext;
+
+    let mock = MockLinktor::start().await;
+    let context = TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+        .unwrap()
+        .tracestate("vendor=abc");
+    context
+        .scope(async { mock.client().contacts().get("contact-1").await.unwrap() })
+        .await;
+    mock.client().contacts().get("contact-1").await.unwrap();
+
+    let sent = mock.requests_to("GET", "/contacts/contact-1");
+    assert_eq!(
+        sent[0].header("traceparent"),
+        Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+    );
+    assert_eq!(sent[0].header("tracestate"), Some("vendor=abc"));
+    assert_eq!(sent[1].header("traceparent"), None);
+}
+
+#[tokio::test]
+async fn test_signed_requests() {
+    let mock = MockLinktor::start().await;
+    let client = LinktorClient::builder()
+        .base_url(mock.url())
+        .api_key("unused")
+        .request_signing("key-1", "secret")
+        .build()
+        .unwrap();
+
+    client.conversations().send_text("conv-1", "Hi").await.unwrap();
+
+    let sent = &mock.requests_to("POST", "/conversations/conv-1/messages")[0];
+    let timestamp: i64 = sent.header("x-linktor-timestamp").unwrap().parse().unwrap();
+    let expected = linktor::signing::RequestSigner::new("key-1", "secret").sign(
+        "POST",
+        "/conversations/conv-1/messages",
+        timestamp,
+        &sent.body,
+    );
+    assert_eq!(sent.header("x-linktor-key-id"), Some("key-1"));
+    assert_eq!(sent.header("x-linktor-signature"), Some(expected.as_str()));
+    assert_eq!(sent.header("x-api-key"), None);
+}
+
+#[tokio::test]
+async fn test_response_metadata() {
+    let mock = MockLinktor::start().await;
+    mock.on_response(
+        "GET",
+        "/contacts/*",
+        MockResponse::json(200, fixtures::contact()).header("X-Request-ID", "req-123"),
+    );
+
+    let response = mock
+        .client()
+        .get_with_response::<linktor::types::Contact>("/contacts/contact-1")
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.request_id(), Some("req-123"));
+    assert_eq!(response.region().name, "default");
+    assert_eq!(response.id, "contact-1");
+}
+
+#[tokio::test]
+async fn test_retry_hook_reports_failover() {
+    let eu = MockLinktor::start().await;
+    let us = MockLinktor::start().await;
+    eu.on("GET", "/contacts/*", 503, serde_json::json!({"code": "INTERNAL_ERROR", "message": "down"}));
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+    let client = LinktorClient::builder()
+        .api_key("test-api-key")
+        .region("eu", eu.url())
+        .region("us", us.url())
+        .on_retry(move |event| recorded.lock().unwrap().push(event.clone()))
+        .build()
+        .unwrap();
+
+    client.contacts().get("contact-1").await.unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].reason, linktor::hooks::RetryReason::Failover);
+    assert_eq!(events[0].status, Some(503));
+    assert_eq!(events[0].region, "eu");
+}
+
+#[tokio::test]
+async fn test_bulk_report() {
+    let mock = MockLinktor::start().await;
+    mock.on("GET", "/contacts/missing", 404, serde_json::json!({"code": "NOT_FOUND", "message": "gone"}));
+
+    let ids = vec!["a", "missing", "b"];
+    let report = mock
+        .client()
+        .bulk()
+        .concurrency(2)
+        .run(ids, |client, id| async move { client.contacts().get(id).await })
+        .await;
+
+    assert_eq!(report.total(), 3);
+    assert_eq!(report.succeeded.iter().map(|s| s.index).collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(report.failed[0].item, "missing");
+    assert_eq!(report.failed[0].attempts, 1);
+}
+
+#[tokio::test]
+async fn test_bulk_retries_network_errors_only_when_opted_in() {
+    // Nothing listens on port 9 of the loopback interface, so connecting fails immediately.
+    let client = LinktorClient::builder()
+        .api_key("test-api-key")
+        .base_url("http://127.0.0.1:9")
+        .max_retries(0)
+        .build()
+        .unwrap();
+    let send = |client: LinktorClient, id: &'static str| async move { client.conversations().send_text(id, "Hi").await };
+
+    let report = client.bulk().retries(2).retry_delay(Duration::ZERO).run(["conv-1"], send).await;
+    assert!(matches!(report.failed[0].error, linktor::LinktorError::Network(_)));
+    assert_eq!(report.failed[0].attempts, 1);
+
+    let report = client
+        .bulk()
+        .retries(2)
+        .retry_delay(Duration::ZERO)
+        .retry_idempotent(true)
+        .run(["conv-1"], send)
+        .await;
+    assert_eq!(report.failed[0].attempts, 3);
+}
+
+#[tokio::test]
+async fn test_bulk_retries_server_errors_only_when_opted_in() {
+    let mock = MockLinktor::start().await;
+    mock.on("POST", "/conversations/*/messages", 502, serde_json::json!({"code": "INTERNAL", "message": "bad gateway"}));
+    let client = LinktorClient::builder()
+        .api_key("test-api-key")
+        .base_url(mock.url())
+        .max_retries(0)
+        .build()
+        .unwrap();
+    let send = |client: LinktorClient, id: &'static str| async move { client.conversations().send_text(id, "Hi").await };
+
+    let report = client.bulk().retries(2).retry_delay(Duration::ZERO).run(["conv-1"], send).await;
+    assert!(matches!(report.failed[0].error, linktor::LinktorError::Server { .. }));
+    assert_eq!(report.failed[0].attempts, 1);
+
+    let report = client
+        .bulk()
+        .retries(2)
+        .retry_delay(Duration::ZERO)
+        .retry_idempotent(true)
+        .run(["conv-1"], send)
+        .await;
+    assert_eq!(report.failed[0].attempts, 3);
+}
+
+#[tokio::test]
+async fn test_deadline_skips_retries_that_cannot_fit() {
+    let mock = MockLinktor::start().await;
+    mock.on_response(
+        "GET",
+        "/contacts/*",
+        MockResponse::json(429, serde_json::json!({"code": "RATE_LIMITED", "message": "slow down"}))
+            .header("Retry-After", "30"),
+    );
+    let client = LinktorClient::builder()
+        .base_url(mock.url())
+        .api_key("test-api-key")
+        .max_retries(3)
+        .deadline(std::time::Duration::from_secs(5))
+        .build()
+        .unwrap();
+
+    let started = std::time::Instant::now();
+    let err = client.contacts().get("contact-1").await.unwrap_err();
+    assert_eq!(err.retry_after(), Some(30));
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    assert_eq!(mock.requests_to("GET", "/contacts/contact-1").len(), 1);
+}
+
+#[tokio::test]
+async fn test_list_params_encode_lists_and_maps() {
+    let mock = MockLinktor::start().await;
+    mock.client()
+        .contacts()
+        .list(Some(
+            linktor::types::ListContactsParams::new()
+                .tags(["vip", "b2b"])
+                .metadata("plan", "pro")
+                .limit(5),
+        ))
+        .await
+        .unwrap();
+
+    let sent = mock.requests_to("GET", "/contacts");
+    assert_eq!(
+        sent[0].query.as_deref(),
+        Some("limit=5&metadata%5Bplan%5D=pro&tags=vip&tags=b2b")
+    );
+}
+
+#[tokio::test]
+async fn test_restricted_client_rejects_locally() {
+    use linktor::{ApiResource, Capabilities};
+
+    let mock = MockLinktor::start().await;
+    let plugin = mock.client().restricted(Capabilities {
+        read_only: true,
+        allowed_resources: vec![ApiResource::Conversations],
+    });
+
+    plugin.conversations().get("conv-1").await.unwrap();
+    let err = plugin.conversations().send_text("conv-1", "hi").await.unwrap_err();
+    assert!(matches!(err, linktor::LinktorError::Authorization { .. }));
+    assert!(plugin.contacts().get("contact-1").await.is_err());
+
+    let mut batch = plugin.batch();
+    batch.get_conversation("conv-1");
+    batch.send_message("conv-1", linktor::types::SendMessageInput::text("hi")).unwrap();
+    assert!(batch.send().await.is_err());
+
+    // Restricting further cannot restore write access.
+    let widened = plugin.restricted(Capabilities::default());
+    assert!(widened.conversations().resolve("conv-1").await.is_err());
+
+    assert_eq!(mock.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_restricted_client_checks_absolute_download_urls() {
+    use linktor::{ApiResource, Capabilities};
+
+    let mock = MockLinktor::start().await;
+    mock.on("GET", "/users", 200, serde_json::json!([]));
+    mock.on("GET", "/media/*", 200, serde_json::json!({}));
+    let plugin = mock.client().restricted(Capabilities::resources([ApiResource::Media]));
+
+    let err = plugin.media().download(&format!("{}/users", mock.url())).await.unwrap_err();
+    assert!(matches!(err, linktor::LinktorError::Authorization { .. }));
+    assert!(mock.requests().is_empty());
+
+    plugin.media().download(&format!("{}/media/m-1", mock.url())).await.unwrap();
+    assert_eq!(mock.requests_to("GET", "/media/m-1")[0].header("x-api-key"), Some("test-api-key"));
+}
+
+#[tokio::test]
+async fn test_restricted_client_denies_path_traversal_ids() {
+    use linktor::{ApiResource, Capabilities};
+
+    let mock = MockLinktor::start().await;
+    let plugin = mock.client().restricted(Capabilities::resources([ApiResource::Contacts]));
+
+    for id in ["../users/u-1", "..", ".", "../.."] {
+        let err = plugin.contacts().get(id).await.unwrap_err();
+        assert!(matches!(err, linktor::LinktorError::Authorization { .. }), "{:?} was not denied", id);
+    }
+    assert!(plugin.contacts().delete("../bots/b-1").await.is_err());
+    assert!(mock.requests().is_empty());
+
+    // Unrestricted clients send the ID as a single encoded segment.
+    let _ = mock.client().contacts().get("a/b c").await;
+    assert_eq!(mock.requests()[0].path, "/contacts/a%2Fb%20c");
+}
+
+#[tokio::test]
+async fn test_with_tenant_scopes_requests() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let tenants = client.auth().list_tenants().await.unwrap();
+    assert_eq!(tenants[0].tenant.id.as_str(), "tenant-1");
+
+    let scoped = client.with_tenant("tenant-2");
+    scoped.auth().get_current_user().await.unwrap();
+    client.auth().get_current_user().await.unwrap();
+
+    let sent = mock.requests_to("GET", "/auth/me");
+    assert_eq!(sent[0].header("x-tenant-id"), Some("tenant-2"));
+    assert_eq!(sent[1].header("x-tenant-id"), None);
+    assert_eq!(mock.requests_to("GET", "/auth/tenants")[0].header("x-tenant-id"), None);
+}
+
+#[tokio::test]
+async fn test_act_as_sends_header() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let support = client.act_as("agent-7");
+    assert_eq!(support.acting_as().map(|id| id.as_str()), Some("agent-7"));
+    support.conversations().get("conv-1").await.unwrap();
+    client.conversations().get("conv-1").await.unwrap();
+
+    let sent = mock.requests_to("GET", "/conversations/conv-1");
+    assert_eq!(sent[0].header("x-act-as-user"), Some("agent-7"));
+    assert_eq!(sent[1].header("x-act-as-user"), None);
+
+    let built = LinktorClient::builder()
+        .base_url(mock.url())
+        .api_key("test-api-key")
+        .act_as("agent-8")
+        .build()
+        .unwrap();
+    built.conversations().get("conv-1").await.unwrap();
+    assert_eq!(mock.requests_to("GET", "/conversations/conv-1")[2].header("x-act-as-user"), Some("agent-8"));
+}
+
+#[tokio::test]
+async fn test_rate_limit_status_from_headers() {
+    let mock = MockLinktor::start().await;
+    mock.on_response(
+        "GET",
+        "/contacts/*",
+        MockResponse::json(200, fixtures::contact())
+            .header("X-RateLimit-Limit", "100")
+            .header("X-RateLimit-Remaining", "42")
+            .header("X-RateLimit-Reset", "1900000000"),
+    );
+
+    let client = mock.client();
+    assert!(client.rate_limit_status().is_none());
+    client.contacts().get("contact-1").await.unwrap();
+
+    let status = client.rate_limit_status().unwrap();
+    assert_eq!((status.limit, status.remaining), (100, 42));
+    assert_eq!(status.reset_at.unwrap().timestamp(), 1_900_000_000);
+}
+
+#[tokio::test]
+async fn test_batch_writes_invalidate_cached_responses() {
+    let mock = MockLinktor::start().await;
+    let conversation = serde_json::to_string(&fixtures::conversation()).unwrap();
+    mock.on_response(
+        "GET",
+        "/conversations/*",
+        MockResponse {
+            status: 200,
+            headers: vec![("ETag".to_string(), "\"v1\"".to_string())],
+            body: conversation,
+        },
+    );
+    mock.on(
+        "POST",
+        "/batch",
+        200,
+        serde_json::json!({"results": [{"id": "0", "status": 201, "body": fixtures::message()}]}),
+    );
+    let client = LinktorClient::builder()
+        .base_url(mock.url())
+        .api_key("test-api-key")
+        .response_cache(true)
+        .build()
+        .unwrap();
+
+    client.conversations().get("conv-1").await.unwrap();
+    client.conversations().get("conv-1").await.unwrap();
+    let revalidated = mock.requests_to("GET", "/conversations/conv-1");
+    assert_eq!(revalidated[1].header("if-none-match"), Some("\"v1\""));
+
+    let mut batch = client.batch();
+    batch.send_message("conv-1", linktor::types::SendMessageInput::text("hi")).unwrap();
+    batch.send().await.unwrap();
+
+    client.conversations().get("conv-1").await.unwrap();
+    let after = mock.requests_to("GET", "/conversations/conv-1");
+    assert_eq!(after[2].header("if-none-match"), None);
+}
+
+#[tokio::test]
+async fn test_batch_handles_only_resolve_their_own_batch() {
+    let mock = MockLinktor::start().await;
+    mock.on(
+        "POST",
+        "/batch",
+        200,
+        serde_json::json!({"results": [{"id": "0", "status": 200, "body": fixtures::contact()}]}),
+    );
+    let client = mock.client();
+
+    let mut first = client.batch();
+    let contact = first.get_contact("contact-1");
+    let mut second = client.batch();
+    let conversation = second.get_conversation("conv-1");
+
+    let results = first.send().await.unwrap();
+    assert_eq!(results.get(&contact).unwrap().id.as_str(), "contact-1");
+    let err = results.get(&conversation).unwrap_err();
+    assert!(matches!(err, linktor::LinktorError::Configuration { .. }));
+}
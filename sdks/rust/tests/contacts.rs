@@ -0,0 +1,62 @@
+use linktor::testing::MockLinktor;
+
+#[tokio::test]
+async fn test_upload_avatar_sends_raw_image() {
+    let mock = MockLinktor::start().await;
+    let png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A];
+
+    let avatar = mock.client().contacts().upload_avatar("contact-1", png.clone()).await.unwrap();
+    assert!(avatar.url.ends_with(".png"));
+
+    let sent = mock.requests_to("PUT", "/contacts/contact-1/avatar");
+    assert_eq!(sent[0].header("content-type"), Some("image/png"));
+    assert_eq!(sent[0].body, png);
+}
+
+#[tokio::test]
+async fn test_merge_contacts() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let result = client
+        .contacts()
+        .merge(linktor::types::MergeContactsInput::new("contact-1", ["contact-2"]))
+        .await
+        .unwrap();
+    assert_eq!(result.contact.id.as_str(), "contact-1");
+    assert_eq!(result.moved_conversation_ids.len(), 1);
+
+    let sent = mock.requests_to("POST", "/contacts/merge");
+    assert_eq!(
+        sent[0].json(),
+        Some(serde_json::json!({ "primaryContactId": "contact-1", "contactIdsToMerge": ["contact-2"] }))
+    );
+}
+
+#[tokio::test]
+async fn test_erase_contact() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let input = linktor::types::EraseContactInput::new(linktor::types::ComplianceRegulation::Lgpd).reason("DSR-88");
+    let receipt = client.contacts().erase_with("contact-1", input).await.unwrap();
+    assert_eq!(receipt.status, linktor::types::ErasureStatus::Pending);
+    let sent = mock.requests_to("POST", "/contacts/contact-1/erase");
+    assert_eq!(sent[0].json(), Some(serde_json::json!({ "regulation": "lgpd", "reason": "DSR-88" })));
+
+    let done = client.contacts().get_erasure("contact-1", &receipt.id).await.unwrap();
+    assert_eq!(done.status, linktor::types::ErasureStatus::Completed);
+    assert_eq!(mock.requests_to("GET", "/contacts/contact-1/erasures/erasure-1").len(), 1);
+}
+
+#[tokio::test]
+async fn test_contact_conversations() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let params = linktor::types::ListConversationsParams::new().status(linktor::types::ConversationStatus::Open);
+    let page = client.contacts().conversations("contact-1", Some(params)).await.unwrap();
+    assert_eq!(page.data.len(), 1);
+    let sent = mock.requests_to("GET", "/conversations");
+    assert_eq!(sent[0].query.as_deref(), Some("contactId=contact-1&status=open"));
+}
@@ -0,0 +1,580 @@
+use linktor::testing::{fixtures, MockLinktor, MockResponse};
+
+#[tokio::test]
+async fn test_claim_conflict() {
+    let mock = MockLinktor::start().await;
+    mock.on(
+        "POST",
+        "/conversations/*/claim",
+        409,
+        serde_json::json!({"code": "CONFLICT", "message": "already assigned", "details": {"assignedAgentId": "agent-7"}}),
+    );
+
+    let outcome = mock.client().conversations().claim("conv-1", "agent-1").await.unwrap();
+    assert!(matches!(outcome, linktor::types::ClaimOutcome::AlreadyClaimedBy(ref a) if a == "agent-7"));
+    assert!(mock.requests_to("POST", "/conversations/conv-1/claim")[0].json().unwrap()["expectedAgentId"].is_null());
+}
+
+#[tokio::test]
+async fn test_send_message_with_auto_chunk() {
+    let mock = MockLinktor::start().await;
+    let text = "A".repeat(3000) + ". " + &"B".repeat(3000) + ".";
+
+    let messages = mock
+        .client()
+        .conversations()
+        .send_message_with(
+            "conv-1",
+            linktor::types::SendMessageInput::text(text),
+            linktor::types::SendOptions::new().auto_chunk(true),
+        )
+        .await
+        .unwrap();
+
+    // The default channel fixture is WhatsApp, limited to 4096 characters.
+    assert_eq!(messages.len(), 2);
+    let sent = mock.requests_to("POST", "/conversations/conv-1/messages");
+    assert_eq!(sent[0].json().unwrap()["text"].as_str().unwrap().len(), 3001);
+}
+
+#[tokio::test]
+async fn test_auto_chunk_leaves_media_messages_whole() {
+    use linktor::types::{MediaContent, MessageType, SendMessageInput, SendOptions};
+
+    let mock = MockLinktor::start().await;
+    let media = MediaContent {
+        url: "https://files.linktor.io/media/media-1".to_string(),
+        mime_type: Some("image/png".to_string()),
+        filename: None,
+        size: None,
+        caption: None,
+    };
+    let input = SendMessageInput {
+        text: Some("A".repeat(3000) + ". " + &"B".repeat(3000) + "."),
+        ..SendMessageInput::media(MessageType::Image, media)
+    };
+
+    let messages = mock
+        .client()
+        .conversations()
+        .send_message_with("conv-1", input, SendOptions::new().auto_chunk(true))
+        .await
+        .unwrap();
+
+    assert_eq!(messages.len(), 1);
+    let sent = mock.requests_to("POST", "/conversations/conv-1/messages");
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].json().unwrap()["media"]["url"], "https://files.linktor.io/media/media-1");
+}
+
+#[tokio::test]
+async fn test_get_conversation_at() {
+    let mock = MockLinktor::start().await;
+    let at = chrono::DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z").unwrap().to_utc();
+
+    let snapshot = mock.client().conversations().get_at("conv-1", at).await.unwrap();
+    assert_eq!(snapshot.assigned_agent_id.unwrap(), "agent-1");
+    assert_eq!(snapshot.tags, vec!["billing"]);
+
+    let sent = mock.requests_to("GET", "/conversations/conv-1/state");
+    assert_eq!(sent[0].query.as_deref(), Some("at=2024-03-01T12%3A00%3A00Z"));
+}
+
+#[tokio::test]
+async fn test_reconcile_statuses() {
+    use linktor::types::ReconcileStatusesInput;
+
+    let mock = MockLinktor::start().await;
+    let from = chrono::DateTime::parse_from_rfc3339("2024-03-01T10:00:00Z").unwrap().to_utc();
+    let to = chrono::DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z").unwrap().to_utc();
+
+    let report = mock
+        .client()
+        .conversations()
+        .reconcile_statuses(ReconcileStatusesInput::range(from, to).channel_id("ch-1"))
+        .await
+        .unwrap();
+    assert_eq!(report.corrected[0].status, linktor::types::MessageStatus::Delivered);
+    assert_eq!(report.unchanged(), 8);
+
+    let body = mock.requests_to("POST", "/conversations/statuses/reconcile")[0].json().unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({ "from": "2024-03-01T10:00:00Z", "to": "2024-03-01T12:00:00Z", "channelId": "ch-1" })
+    );
+}
+
+#[tokio::test]
+async fn test_render_transcript_pdf() {
+    use linktor::types::{TranscriptDelivery, TranscriptPdf, TranscriptPdfOptions};
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let pdf = client
+        .vre()
+        .render_transcript_pdf("conv-1", TranscriptPdfOptions::new().timezone("America/Sao_Paulo"))
+        .await
+        .unwrap();
+    assert_eq!(pdf, TranscriptPdf::Bytes(b"%PDF-1.7\n".to_vec()));
+    let body = mock.requests_to("POST", "/vre/transcripts/pdf")[0].json().unwrap();
+    assert_eq!(body, serde_json::json!({ "conversation_id": "conv-1", "timezone": "America/Sao_Paulo" }));
+
+    mock.on(
+        "POST",
+        "/vre/transcripts/pdf",
+        200,
+        serde_json::json!({ "url": "https://files.linktor.io/t/conv-1.pdf" }),
+    );
+    let pdf = client
+        .vre()
+        .render_transcript_pdf("conv-1", TranscriptPdfOptions::new().delivery(TranscriptDelivery::Url))
+        .await
+        .unwrap();
+    assert!(matches!(pdf, TranscriptPdf::Url { ref url, .. } if url.ends_with("conv-1.pdf")));
+}
+
+#[tokio::test]
+async fn test_missed_events_pages_with_cursor() {
+    use linktor::types::{EventType, MissedEventsParams};
+    use futures_util::{StreamExt, TryStreamExt};
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+    let since = chrono::DateTime::from_timestamp(1_704_067_200, 0).unwrap();
+
+    let all: Vec<_> = client.webhooks().missed_events("wh-1", since).try_collect().await.unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].event.get_event_type(), Some(EventType::MessageReceived));
+    let first = &mock.requests_to("GET", "/webhooks/wh-1/missed-events")[0];
+    assert_eq!(first.query.as_deref(), Some("since=2024-01-01T00%3A00%3A00Z"));
+
+    let mut page = fixtures::missed_events_page();
+    page.has_more = true;
+    page.next_cursor = Some("cursor-evt-2".to_string());
+    mock.on("GET", "/webhooks/*/missed-events", 200, page);
+    mock.reset_requests();
+
+    let resumed: Vec<_> = client
+        .webhooks()
+        .missed_events_with("wh-1", MissedEventsParams::new().cursor("saved").limit(2))
+        .take(3)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(resumed.len(), 3);
+    let sent = mock.requests_to("GET", "/webhooks/wh-1/missed-events");
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0].query.as_deref(), Some("cursor=saved&limit=2"));
+    assert_eq!(sent[1].query.as_deref(), Some("cursor=cursor-evt-2&limit=2"));
+}
+
+#[tokio::test]
+async fn test_set_autopilot() {
+    use linktor::types::AutopilotConfig;
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let config = AutopilotConfig::enabled(0.85).escalation_tag("needs-human");
+    let state = client.conversations().set_autopilot("conv-1", config.clone()).await.unwrap();
+    assert_eq!(state.config, config);
+    assert!(state.overridden);
+
+    let body = mock.requests_to("PUT", "/conversations/conv-1/autopilot")[0].json().unwrap();
+    assert_eq!(body, serde_json::json!({ "enabled": true, "minConfidence": 0.85, "escalationTag": "needs-human" }));
+
+    let state = client.conversations().clear_autopilot("conv-1").await.unwrap();
+    assert!(!state.overridden);
+
+    let conversation: linktor::types::Conversation = serde_json::from_value(serde_json::json!({
+        "id": "conv-1", "tenantId": "t", "channelId": "c", "contactId": "ct", "status": "open",
+        "autopilot": { "enabled": false, "minConfidence": 1.0 },
+        "createdAt": "2024-01-01T00:00:00Z", "updatedAt": "2024-01-01T00:00:00Z"
+    }))
+    .unwrap();
+    let autopilot = conversation.autopilot.unwrap();
+    assert!(!autopilot.config.enabled);
+    assert!(!autopilot.overridden);
+}
+
+#[tokio::test]
+async fn test_typing_indicator() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    client.conversations().start_typing("conv-1").await.unwrap();
+    client.conversations().stop_typing("conv-1").await.unwrap();
+
+    let sent = mock.requests_to("POST", "/conversations/conv-1/typing");
+    assert_eq!(sent[0].json().unwrap(), serde_json::json!({ "is_typing": true }));
+    assert_eq!(sent[1].json().unwrap(), serde_json::json!({ "is_typing": false }));
+}
+
+#[tokio::test]
+async fn test_mark_read() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let conversation = client.conversations().mark_read("conv-1").await.unwrap();
+    assert_eq!(conversation.unread_count, 0);
+
+    client.conversations().mark_message_read("conv-1", "msg-1").await.unwrap();
+    let body = mock.requests_to("POST", "/conversations/conv-1/messages/read")[0].json().unwrap();
+    assert_eq!(body, serde_json::json!({ "message_ids": ["msg-1"] }));
+}
+
+#[tokio::test]
+async fn test_reactions() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    client.conversations().react("conv-1", "msg-1", "👍").await.unwrap();
+    client.conversations().remove_reaction("conv-1", "msg-1").await.unwrap();
+
+    let sent = mock.requests_to("POST", "/conversations/conv-1/messages/msg-1/reactions");
+    assert_eq!(sent[0].json().unwrap(), serde_json::json!({ "emoji": "👍" }));
+    assert_eq!(sent[1].json().unwrap(), serde_json::json!({ "emoji": "" }));
+
+    let message: linktor::types::Message = serde_json::from_value(serde_json::json!({
+        "id": "msg-1", "conversationId": "conv-1", "type": "text", "direction": "outbound", "status": "read",
+        "reactions": [{ "userId": "contact-1", "emoji": "❤️", "timestamp": "2024-01-01T00:00:00Z" }],
+        "createdAt": "2024-01-01T00:00:00Z", "updatedAt": "2024-01-01T00:00:00Z"
+    }))
+    .unwrap();
+    assert_eq!(message.reactions[0].emoji, "❤️");
+    assert!(fixtures::message().reactions.is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_message() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    client.conversations().delete_message("conv-1", "msg-1", true).await.unwrap();
+    client.conversations().delete_message("conv-1", "msg-2", false).await.unwrap();
+
+    let sent = mock.requests_to("DELETE", "/conversations/conv-1/messages/msg-1");
+    assert_eq!(sent[0].query.as_deref(), Some("forEveryone=true"));
+    let sent = mock.requests_to("DELETE", "/conversations/conv-1/messages/msg-2");
+    assert_eq!(sent[0].query.as_deref(), Some("forEveryone=false"));
+}
+
+#[tokio::test]
+async fn test_media_upload_and_send_image() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let png = b"\x89PNG\r\n\x1a\n".to_vec();
+    let uploaded = client.media().upload_reader(&png[..], "image/png", "logo \"v2\".png").await.unwrap();
+    assert_eq!(uploaded.id.as_str(), "media-1");
+
+    let upload = &mock.requests_to("POST", "/media")[0];
+    let content_type = upload.header("content-type").unwrap();
+    let boundary = content_type.strip_prefix("multipart/form-data; boundary=").unwrap();
+    let mut expected = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"logo _v2_.png\"\r\nContent-Type: image/png\r\n\r\n",
+        boundary
+    )
+    .into_bytes();
+    expected.extend_from_slice(&png);
+    expected.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    assert_eq!(upload.body, expected);
+
+    let broken = tokio_test::io::Builder::new()
+        .read(b"\x89PNG")
+        .read_error(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "disk gone"))
+        .build();
+    let err = client.media().upload_reader(broken, "image/png", "logo.png").await.unwrap_err();
+    assert!(matches!(err, linktor::LinktorError::Io(ref e) if e.kind() == std::io::ErrorKind::BrokenPipe));
+    assert_eq!(mock.requests_to("POST", "/media").len(), 1);
+
+    client.conversations().send_image("conv-1", png, Some("Our new logo")).await.unwrap();
+    let body = mock.requests_to("POST", "/conversations/conv-1/messages")[0].json().unwrap();
+    assert_eq!(body["type"], "image");
+    assert_eq!(body["media"]["url"], "https://files.linktor.io/media/media-1");
+    assert_eq!(body["media"]["caption"], "Our new logo");
+}
+
+#[tokio::test]
+async fn test_download_media() {
+    use tokio::io::AsyncReadExt;
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+    let pdf = MockResponse {
+        status: 200,
+        headers: vec![("Content-Type".to_string(), "application/pdf".to_string())],
+        body: "%PDF-1.7".to_string(),
+    };
+    mock.on_response("GET", "/conversations/*/messages/*/media", pdf.clone());
+
+    let bytes = client.conversations().download_media("conv-1", "msg-1").await.unwrap();
+    assert_eq!(&bytes[..], b"%PDF-1.7");
+    let sent = mock.requests_to("GET", "/conversations/conv-1/messages/msg-1/media");
+    assert_eq!(sent[0].header("x-api-key"), Some("test-api-key"));
+
+    let mut stream = client.conversations().download_media_stream("conv-1", "msg-1").await.unwrap();
+    assert_eq!(stream.content_type.as_deref(), Some("application/pdf"));
+    let mut data = Vec::new();
+    stream.read_to_end(&mut data).await.unwrap();
+    assert_eq!(data, b"%PDF-1.7");
+
+    // Credentials stay on the API host
+    let cdn = MockLinktor::start().await;
+    cdn.on_response("GET", "/files/*", pdf);
+    let bytes = client.media().download(&format!("{}/files/doc.pdf", cdn.url())).await.unwrap();
+    assert_eq!(&bytes[..], b"%PDF-1.7");
+    assert_eq!(cdn.requests_to("GET", "/files/doc.pdf")[0].header("x-api-key"), None);
+
+    let missing = client.media().download("/media/nope").await;
+    assert!(matches!(missing, Err(linktor::LinktorError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_scheduled_messages() {
+    use linktor::types::{ScheduledMessageStatus, SendMessageInput};
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let send_at = chrono::DateTime::parse_from_rfc3339("2024-06-01T12:00:00Z").unwrap().with_timezone(&chrono::Utc);
+    let scheduled = client
+        .conversations()
+        .schedule_message("conv-1", SendMessageInput::text("See you tomorrow"), send_at)
+        .await
+        .unwrap();
+    assert_eq!(scheduled.status, ScheduledMessageStatus::Pending);
+    let body = mock.requests_to("POST", "/conversations/conv-1/scheduled-messages")[0].json().unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "message": { "text": "See you tomorrow", "type": "text" },
+            "sendAt": "2024-06-01T12:00:00Z"
+        })
+    );
+
+    let pending = client.conversations().list_scheduled_messages("conv-1", None).await.unwrap();
+    assert_eq!(pending.data[0].id.as_str(), "sched-1");
+
+    let cancelled = client.conversations().cancel_scheduled_message("conv-1", "sched-1").await.unwrap();
+    assert_eq!(cancelled.status, ScheduledMessageStatus::Cancelled);
+}
+
+#[tokio::test]
+async fn test_list_conversations_advanced_filters() {
+    use linktor::types::{ChannelType, ConversationStatus, ListConversationsParams};
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let from = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().with_timezone(&chrono::Utc);
+    let to = chrono::DateTime::parse_from_rfc3339("2024-02-01T00:00:00Z").unwrap().with_timezone(&chrono::Utc);
+    let params = ListConversationsParams::new()
+        .statuses([ConversationStatus::Open, ConversationStatus::Pending])
+        .channel_types([ChannelType::Whatsapp, ChannelType::Telegram])
+        .tags(["vip", "billing"])
+        .unassigned()
+        .message_search("refund")
+        .created_between(from, to);
+    client.conversations().list(Some(params)).await.unwrap();
+
+    let sent = mock.requests_to("GET", "/conversations");
+    assert_eq!(
+        sent[0].query.as_deref(),
+        Some(
+            "channelTypes=whatsapp&channelTypes=telegram&createdAfter=2024-01-01T00%3A00%3A00Z\
+             &createdBefore=2024-02-01T00%3A00%3A00Z&messageSearch=refund&statuses=open&statuses=pending\
+             &tags=vip&tags=billing&unassigned=true"
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_get_message_with_receipts() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let mut message = serde_json::to_value(fixtures::message()).unwrap();
+    message["receipts"] = serde_json::json!([{
+        "recipient": "+5511999999999",
+        "channelType": "whatsapp",
+        "status": "read",
+        "sentAt": "2024-01-01T10:00:00Z",
+        "deliveredAt": "2024-01-01T10:00:02Z",
+        "readAt": "2024-01-01T10:05:00Z"
+    }]);
+    mock.on("GET", "/conversations/*/messages/*", 200, message);
+
+    let message = client.conversations().get_message("conv-1", "msg-1").await.unwrap();
+    let receipt = &message.receipts[0];
+    assert_eq!(receipt.status, linktor::types::MessageStatus::Read);
+    assert_eq!(receipt.delivery_time(), Some(chrono::Duration::seconds(2)));
+    assert_eq!(receipt.read_time(), Some(chrono::Duration::minutes(5)));
+    assert_eq!(mock.requests_to("GET", "/conversations/conv-1/messages/msg-1").len(), 1);
+}
+
+#[tokio::test]
+async fn test_reopen_and_close() {
+    use linktor::types::ConversationStatus;
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let reopened = client.conversations().reopen("conv-1").await.unwrap();
+    assert_eq!(reopened.status, ConversationStatus::Open);
+    let closed = client.conversations().close("conv-1").await.unwrap();
+    assert_eq!(closed.status, ConversationStatus::Closed);
+
+    assert_eq!(mock.requests_to("POST", "/conversations/conv-1/reopen").len(), 1);
+    assert_eq!(mock.requests_to("POST", "/conversations/conv-1/close").len(), 1);
+}
+
+#[tokio::test]
+async fn test_conversation_history() {
+    use linktor::types::{ActorType, ConversationEventKind, ConversationStatus, HandoffDirection};
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+    mock.on(
+        "GET",
+        "/conversations/*/history",
+        200,
+        serde_json::json!([
+            { "id": "ev-1", "type": "created", "occurredAt": "2024-01-01T10:00:00Z", "actor": { "type": "contact", "id": "contact-1" } },
+            { "id": "ev-2", "type": "bot_handoff", "occurredAt": "2024-01-01T10:01:00Z", "actor": { "type": "bot", "id": "bot-1" },
+              "direction": "to_agent", "botId": "bot-1", "reason": "low confidence" },
+            { "id": "ev-3", "type": "assigned", "occurredAt": "2024-01-01T10:02:00Z", "agentId": "user-1" },
+            { "id": "ev-4", "type": "tags_changed", "occurredAt": "2024-01-01T10:03:00Z", "added": ["vip"] },
+            { "id": "ev-5", "type": "status_changed", "occurredAt": "2024-01-01T10:30:00Z", "from": "open", "to": "resolved" },
+            { "id": "ev-6", "type": "sla_breached", "occurredAt": "2024-01-01T11:00:00Z" }
+        ]),
+    );
+
+    let history = client.conversations().history("conv-1").await.unwrap();
+    assert_eq!(history.len(), 6);
+    assert_eq!(history[0].actor.as_ref().unwrap().actor_type, ActorType::Contact);
+    assert!(matches!(
+        history[1].kind,
+        ConversationEventKind::BotHandoff { direction: HandoffDirection::ToAgent, ref reason, .. } if reason.as_deref() == Some("low confidence")
+    ));
+    assert!(matches!(history[2].kind, ConversationEventKind::Assigned { ref agent_id, .. } if agent_id.is_some()));
+    assert_eq!(
+        history[3].kind,
+        ConversationEventKind::TagsChanged { added: vec!["vip".to_string()], removed: Vec::new() }
+    );
+    assert_eq!(
+        history[4].kind,
+        ConversationEventKind::StatusChanged { from: Some(ConversationStatus::Open), to: ConversationStatus::Resolved }
+    );
+    assert_eq!(history[5].kind, ConversationEventKind::Other);
+}
+
+#[tokio::test]
+async fn test_send_template() {
+    use linktor::types::TemplateMessageBuilder;
+
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let template = TemplateMessageBuilder::new("order_shipped", "pt_BR").body("Maria").url_button(0, "AB123");
+    client.conversations().send_template("conv-1", template).await.unwrap();
+
+    let body = mock.requests_to("POST", "/conversations/conv-1/messages")[0].json().unwrap();
+    assert_eq!(body["type"], "template");
+    assert_eq!(body["template"]["name"], "order_shipped");
+    assert_eq!(body["template"]["components"][1]["subType"], "url");
+}
+
+#[tokio::test]
+async fn test_wait_for_message_status() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+    mock.on(
+        "GET",
+        "/conversations/*/messages/*",
+        200,
+        linktor::types::Message { status: linktor::types::MessageStatus::Read, ..fixtures::message() },
+    );
+
+    let message = client
+        .conversations()
+        .wait_for_message_status("conv-1", "msg-1", linktor::types::MessageStatus::Delivered, std::time::Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(message.status, linktor::types::MessageStatus::Read);
+    assert_eq!(mock.requests_to("GET", "/conversations/conv-1/messages/msg-1").len(), 1);
+}
+
+#[tokio::test]
+async fn test_wait_for_message_status_times_out() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let err = client
+        .conversations()
+        .wait_for_message_status("conv-1", "msg-1", linktor::types::MessageStatus::Delivered, std::time::Duration::from_millis(700))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, linktor::LinktorError::DeadlineExceeded { .. }));
+    assert!(mock.requests_to("GET", "/conversations/conv-1/messages/msg-1").len() >= 2);
+}
+
+#[tokio::test]
+async fn test_forward_message() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let forwarded = client.conversations().forward_message("conv-1", "msg-1", "conv-2").await.unwrap();
+    assert_eq!(forwarded.conversation_id.as_str(), "conv-2");
+    let origin = forwarded.forwarded_from.unwrap();
+    assert_eq!(origin.message_id.as_str(), "msg-1");
+    assert_eq!(origin.sender_name.as_deref(), Some("Test Contact"));
+
+    let requests = mock.requests_to("POST", "/conversations/conv-1/messages/msg-1/forward");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].json(), Some(serde_json::json!({ "targetConversationId": "conv-2" })));
+}
+
+#[tokio::test]
+async fn test_translated_messages() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    client
+        .conversations()
+        .send_message("conv-1", linktor::types::SendMessageInput::text("Hello!").translate_to("es"))
+        .await
+        .unwrap();
+    let sent = mock.requests_to("POST", "/conversations/conv-1/messages");
+    assert_eq!(sent[0].json().unwrap()["translateTo"], "es");
+
+    let page = linktor::types::PaginationParams { limit: Some(20), ..Default::default() };
+    client.conversations().get_messages_translated("conv-1", "es", Some(page)).await.unwrap();
+    let listed = mock.requests_to("GET", "/conversations/conv-1/messages");
+    assert_eq!(listed[0].query.as_deref(), Some("limit=20&translateTo=es"));
+}
+
+#[tokio::test]
+async fn test_external_references() {
+    let mock = MockLinktor::start().await;
+    let client = mock.client();
+
+    let reference = linktor::types::ExternalReference::new("jira", "SUP-1234").url("https://acme.atlassian.net/browse/SUP-1234");
+    let conversation = client.conversations().link_external("conv-1", reference).await.unwrap();
+    assert_eq!(conversation.external_reference("jira").unwrap().id, "SUP-1234");
+    let linked = mock.requests_to("POST", "/conversations/conv-1/external-references");
+    assert_eq!(
+        linked[0].json(),
+        Some(serde_json::json!({
+            "system": "jira",
+            "id": "SUP-1234",
+            "url": "https://acme.atlassian.net/browse/SUP-1234"
+        }))
+    );
+
+    let conversation = client.conversations().unlink_external("conv-1", "jira", "SUP-1234").await.unwrap();
+    assert!(conversation.external_references.is_empty());
+    let unlinked = mock.requests_to("DELETE", "/conversations/conv-1/external-references");
+    assert_eq!(unlinked[0].query.as_deref(), Some("id=SUP-1234&system=jira"));
+}
@@ -0,0 +1,36 @@
+use linktor::testing::{fixtures, MockLinktor, MockResponse};
+
+#[tokio::test]
+async fn test_stream_execution_resumes_with_last_event_id() {
+    use futures_util::StreamExt;
+
+    let mock = MockLinktor::start().await;
+    let step = serde_json::to_string(&fixtures::flow_execution_step()).unwrap();
+    mock.on_response(
+        "GET",
+        "/flows/executions/exec-1/events",
+        MockResponse::event_stream(format!(": connected\n\nid: 7\nevent: step\ndata: {}\n\n", step)),
+    );
+    let mut steps = mock.client().flows().stream_execution("exec-1");
+
+    let first = steps.next().await.unwrap().unwrap();
+    assert_eq!(first.node_id, "node-1");
+
+    // The first connection closes without an `end` event; the resumed one finishes.
+    mock.on_response(
+        "GET",
+        "/flows/executions/exec-1/events",
+        MockResponse::event_stream(format!(
+            "id: 8\nevent: progress\ndata: {{}}\n\nid: 9\nevent: step\ndata: {}\n\nid: 10\nevent: end\ndata: {{}}\n\n",
+            step
+        )),
+    );
+    assert!(steps.next().await.unwrap().is_ok());
+    assert!(steps.next().await.is_none());
+
+    let sent = mock.requests_to("GET", "/flows/executions/exec-1/events");
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0].header("accept"), Some("text/event-stream"));
+    assert_eq!(sent[0].header("last-event-id"), None);
+    assert_eq!(sent[1].header("last-event-id"), Some("7"));
+}
@@ -0,0 +1,49 @@
+use linktor::LinktorClient;
+use linktor::testing::{fixtures, MockLinktor};
+
+#[tokio::test]
+async fn test_vre_renderers_format_amounts_for_tenant() {
+    use linktor::types::{CardProdutoData, VREChannelType};
+
+    let mock = MockLinktor::start().await;
+    let mut tenant = fixtures::tenant();
+    tenant.settings = Some(
+        serde_json::from_value(serde_json::json!({ "language": "en-US", "currency": "USD" })).unwrap(),
+    );
+    mock.on("GET", "/auth/tenant", 200, tenant);
+    let client = mock.client();
+
+    let card = CardProdutoData::new("Coffee", 1234.5, "kg");
+    client.vre().render_product_card("tenant-1", card.clone(), VREChannelType::Whatsapp).await.unwrap();
+    client.vre().render_pix_payment("tenant-1", linktor::types::CobrancaPixData::new(10.0, "000201"), VREChannelType::Whatsapp).await.unwrap();
+
+    let renders = mock.requests_to("POST", "/vre/render");
+    assert_eq!(renders[0].json().unwrap()["data"]["preco_formatado"], "$1,234.50");
+    assert_eq!(renders[1].json().unwrap()["data"]["valor_formatado"], "$10.00");
+    assert_eq!(mock.requests_to("GET", "/auth/tenant").len(), 1);
+
+    let configured = LinktorClient::builder()
+        .base_url(mock.url())
+        .api_key("test-key")
+        .formatter(linktor::formatting::Formatter::new("pt-BR", "BRL"))
+        .build()
+        .unwrap();
+    configured.vre().render_product_card("tenant-1", card, VREChannelType::Whatsapp).await.unwrap();
+    assert_eq!(mock.requests_to("POST", "/vre/render")[2].json().unwrap()["data"]["preco_formatado"], "R$ 1.234,50");
+    assert_eq!(mock.requests_to("GET", "/auth/tenant").len(), 1);
+}
+
+#[tokio::test]
+async fn test_vre_renderers_fall_back_when_tenant_fetch_fails() {
+    use linktor::types::{CardProdutoData, VREChannelType};
+
+    let mock = MockLinktor::start().await;
+    mock.on("GET", "/auth/tenant", 500, serde_json::json!({ "code": "INTERNAL", "message": "boom" }));
+    let client = mock.client();
+
+    let card = CardProdutoData::new("Coffee", 1234.5, "kg");
+    client.vre().render_product_card("tenant-1", card, VREChannelType::Whatsapp).await.unwrap();
+
+    let renders = mock.requests_to("POST", "/vre/render");
+    assert_eq!(renders[0].json().unwrap()["data"]["preco_formatado"], "R$ 1.234,50");
+}